@@ -0,0 +1,90 @@
+//! 大库存列表的性能基准：1 万件物品时筛选排序、增量更新和滚动时每一帧的开销。
+//! 一帧的预算约 16 ms，滚动时只应花在计算可见行和取出这些行的物品上。
+
+use chrono::{Duration, NaiveDate};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use ukeep::models::{InventoryQuery, Item, Location, Quantity, SortOrder, SortedInventory, Unit};
+use ukeep::virtual_list::{padding, VisibleRows, ROW_HEIGHT};
+
+const ITEMS: usize = 10_000;
+const NAMES: [&str; 8] = ["牛奶", "鸡蛋", "面包", "苹果", "酸奶", "豆腐", "西兰花", "Cheese"];
+
+fn today() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()
+}
+
+fn inventory() -> Vec<Item> {
+    (0..ITEMS)
+        .map(|i| {
+            let name = format!("{}{}", NAMES[i % NAMES.len()], i);
+            let expiry = today() + Duration::days((i * 7919 % 365) as i64 - 30);
+            let location = if i % 3 == 0 { Location::Freezer } else { Location::Fridge };
+            Item::new_with_amount(name, expiry, Quantity::new((i % 12 + 1) as f64, Unit::Piece), today()).with_location(location)
+        })
+        .collect()
+}
+
+fn synced(query: InventoryQuery, items: &[Item]) -> SortedInventory {
+    let mut view = SortedInventory::new(query);
+    view.sync(items, &[], today());
+    view
+}
+
+fn build(c: &mut Criterion) {
+    let items = inventory();
+    let mut group = c.benchmark_group("build_10k");
+    for sort in SortOrder::ALL {
+        let query = InventoryQuery { sort, ..Default::default() };
+        group.bench_function(sort.key(), |b| b.iter(|| synced(query.clone(), black_box(&items))));
+    }
+    let search = InventoryQuery { text: "nn".to_string(), ..Default::default() };
+    group.bench_function("search_pinyin", |b| b.iter(|| synced(search.clone(), black_box(&items))));
+    group.finish();
+}
+
+fn incremental(c: &mut Criterion) {
+    let mut items = inventory();
+    let view = synced(InventoryQuery::default(), &items);
+    let mut group = c.benchmark_group("sync_10k");
+    // 没有变化：每次渲染都会同步一次
+    group.bench_function("unchanged", |b| {
+        b.iter_batched(|| view.clone(), |mut v| v.sync(black_box(&items), &[], today()), BatchSize::LargeInput)
+    });
+    // 吃掉一件：只有一个物品的排序键变化
+    items[ITEMS / 2].consume(1.0);
+    items[ITEMS / 2].shift_expiry(10);
+    group.bench_function("one_changed", |b| {
+        b.iter_batched(|| view.clone(), |mut v| v.sync(black_box(&items), &[], today()), BatchSize::LargeInput)
+    });
+    // 删除一件
+    let removed: Vec<Item> = items.iter().skip(1).cloned().collect();
+    group.bench_function("one_removed", |b| {
+        b.iter_batched(|| view.clone(), |mut v| v.sync(black_box(&removed), &[], today()), BatchSize::LargeInput)
+    });
+    group.finish();
+}
+
+fn scrolling(c: &mut Criterion) {
+    let items = inventory();
+    let view = synced(InventoryQuery::default(), &items);
+    let len = view.len();
+    // 从头滚到尾，每一帧下移 40 px：计算可见行并取出要渲染的物品
+    c.bench_function("scroll_frame_10k", |b| {
+        let mut offset = 0.0;
+        b.iter(|| {
+            offset = (offset + 40.0) % (len as f64 * ROW_HEIGHT);
+            let rows = VisibleRows::measure(-offset, 844.0);
+            let range = rows.range(len);
+            let spacing = padding(&range, len);
+            let window: Vec<&Item> = range
+                .filter_map(|index| view.id_at(index))
+                .filter_map(|id| view.position(id))
+                .map(|position| &items[position])
+                .collect();
+            black_box((spacing, window))
+        })
+    });
+}
+
+criterion_group!(benches, build, incremental, scrolling);
+criterion_main!(benches);
@@ -0,0 +1,77 @@
+//! 当前时间的来源。依赖“今天”的逻辑都从 `Clock` 取时间，而不是直接读取系统时间：
+//! 测试可以固定时间，开发者设置中可以模拟某一天，预览“下周会有什么过期”。
+//!
+//! 记录事件发生的时刻（历史记录、WebDAV 备份、同步状态）仍使用真实时间。
+
+use chrono::{Local, NaiveDate, NaiveDateTime};
+use std::fmt::Debug;
+
+/// 时间来源
+pub trait Clock: Debug {
+    /// 当前的本地时间
+    fn now(&self) -> NaiveDateTime;
+
+    /// 今天的日期
+    fn today(&self) -> NaiveDate {
+        self.now().date()
+    }
+
+    /// 开发者设置中模拟的日期，使用真实日期时为 None
+    fn simulated_date(&self) -> Option<NaiveDate> {
+        None
+    }
+}
+
+/// 系统时间
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> NaiveDateTime {
+        Local::now().naive_local()
+    }
+}
+
+/// 固定不变的时间，用于测试
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FixedClock(pub NaiveDateTime);
+
+impl FixedClock {
+    /// 固定在某一天的中午
+    pub fn on(date: NaiveDate) -> Self {
+        Self(date.and_hms_opt(12, 0, 0).unwrap_or_default())
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> NaiveDateTime {
+        self.0
+    }
+}
+
+/// 模拟的日期：日期固定为设置的那一天，一天中的时刻跟随系统时间
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SimulatedClock {
+    date: NaiveDate,
+}
+
+impl SimulatedClock {
+    pub fn new(date: NaiveDate) -> Self {
+        Self { date }
+    }
+
+    /// 模拟的日期
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+}
+
+impl Clock for SimulatedClock {
+    fn now(&self) -> NaiveDateTime {
+        self.date.and_time(Local::now().time())
+    }
+
+    fn simulated_date(&self) -> Option<NaiveDate> {
+        Some(self.date)
+    }
+}
@@ -0,0 +1,128 @@
+use crate::models::{format_amount, Item, Unit};
+use dioxus::prelude::*;
+
+// 加减按钮每次改变的数量
+fn increment(unit: Unit) -> f64 {
+    match unit {
+        Unit::Piece => 1.0,
+        Unit::Pack => 0.5,
+        Unit::Gram | Unit::Milliliter => 10.0,
+        Unit::Kilogram | Unit::Liter => 0.1,
+    }
+}
+
+/// 输入任意数量的弹窗，用于数量菜单中的“自定义…”
+/// - `initial`：初始数量，例如平时一次吃的数量
+/// - `on_submit`：携带输入的数量（按物品自身的单位），不会超过现有数量
+#[component]
+pub fn AmountDialog(item: Item, initial: f64, on_submit: EventHandler<f64>, on_cancel: EventHandler<()>) -> Element {
+    let quantity = item.quantity();
+    let total = quantity.amount();
+    let step = increment(quantity.unit()).min(total);
+    let mut amount_str = use_signal(move || format_amount(initial.min(total)));
+    let mut error = use_signal(|| Option::<String>::None);
+
+    let parsed = move || amount_str.read().trim().parse::<f64>().ok().filter(|a| a.is_finite() && *a > 0.0);
+
+    // 加减后保持在 (0, 全部] 之间，并去掉浮点误差
+    let mut adjust = move |delta: f64| {
+        let current = parsed().unwrap_or(0.0);
+        let next = ((current + delta) / step).round() * step;
+        amount_str.set(format_amount(next.clamp(step, total)));
+        error.set(None);
+    };
+
+    let mut submit = move || {
+        let Some(amount) = parsed() else {
+            error.set(Some("请输入有效的数量".to_string()));
+            return;
+        };
+        if amount > total + 1e-6 {
+            error.set(Some(format!("最多只有 {}", quantity)));
+            return;
+        }
+        on_submit.call(amount.min(total));
+    };
+
+    rsx! {
+        // 遮罩层
+        div { class: "fixed inset-0 z-50 bg-black/30 flex items-center justify-center p-6",
+            div {
+                class: "w-full max-w-sm bg-white rounded-2xl shadow-xl p-6 flex flex-col gap-4",
+                role: "dialog",
+                "aria-modal": "true",
+                "aria-label": "吃掉多少{item.name()}",
+                onkeydown: move |evt| {
+                    // 弹窗打开时按键不再传给下面的卡片
+                    evt.stop_propagation();
+                    if evt.key() == Key::Escape {
+                        on_cancel.call(());
+                    }
+                },
+                h3 { class: "text-lg font-semibold text-gray-900", "吃掉多少 {item.emoji()}{item.name()}？" }
+
+                // 步进器：减、输入框、加
+                div { class: "flex items-center justify-center gap-3",
+                    button {
+                        class: "material-symbols-outlined w-12 h-12 rounded-full bg-gray-100 hover:bg-gray-200 text-gray-700 transition-colors",
+                        "aria-label": "减少",
+                        onclick: move |_| adjust(-step),
+                        "remove"
+                    }
+                    input {
+                        r#type: "number",
+                        inputmode: "decimal",
+                        min: "0",
+                        max: "{total}",
+                        step: "any",
+                        "aria-label": "数量",
+                        class: "w-28 text-center bg-gray-50 border border-gray-200 rounded-xl px-3 py-3 text-xl font-semibold focus:outline-none focus:ring-2 focus:ring-green-500",
+                        value: "{amount_str}",
+                        oninput: move |evt| {
+                            amount_str.set(evt.value());
+                            error.set(None);
+                        },
+                        onkeydown: move |evt| {
+                            if evt.key() == Key::Enter {
+                                submit();
+                            }
+                        },
+                        onmounted: move |evt| async move {
+                            let _ = evt.set_focus(true).await;
+                        },
+                    }
+                    button {
+                        class: "material-symbols-outlined w-12 h-12 rounded-full bg-gray-100 hover:bg-gray-200 text-gray-700 transition-colors",
+                        "aria-label": "增加",
+                        onclick: move |_| adjust(step),
+                        "add"
+                    }
+                }
+                div { class: "flex items-center justify-center gap-2 text-sm text-gray-500",
+                    span { "共 {quantity}" }
+                    button {
+                        class: "text-green-600 hover:underline",
+                        onclick: move |_| amount_str.set(format_amount(total)),
+                        "全部"
+                    }
+                }
+
+                if let Some(err) = error() {
+                    div { class: "bg-red-50 text-red-700 px-4 py-3 rounded-xl text-sm", "{err}" }
+                }
+                div { class: "flex gap-3 mt-2",
+                    button {
+                        class: "flex-1 py-3 rounded-xl bg-gray-100 hover:bg-gray-200 text-gray-700 font-medium transition-colors",
+                        onclick: move |_| on_cancel.call(()),
+                        "取消"
+                    }
+                    button {
+                        class: "flex-1 py-3 rounded-xl bg-green-500 hover:bg-green-600 text-white font-medium transition-colors",
+                        onclick: move |_| submit(),
+                        "吃掉"
+                    }
+                }
+            }
+        }
+    }
+}
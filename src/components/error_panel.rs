@@ -0,0 +1,102 @@
+use crate::crypto::CryptoError;
+use crate::error::{FileProblem, RowError, RowField, UkeepError};
+use dioxus::prelude::*;
+
+// CSV 字段的中文名称
+fn field_label(field: RowField) -> &'static str {
+    match field {
+        RowField::Name => "名称",
+        RowField::Expiry => "过期日期",
+        RowField::Quantity => "数量",
+        RowField::Unit => "单位",
+        RowField::Location => "存放位置",
+        RowField::Currency => "货币",
+        RowField::Price => "价格",
+    }
+}
+
+// CSV 列名对应的中文名称
+fn column_label(column: &str) -> &'static str {
+    match column {
+        "name" => "名称",
+        "expiry" => "过期日期",
+        _ => "",
+    }
+}
+
+fn describe_row(row: &RowError) -> String {
+    match &row.value {
+        Some(value) => format!("第 {} 行：{}“{}”无效", row.line, field_label(row.field), value),
+        None => format!("第 {} 行：缺少{}", row.line, field_label(row.field)),
+    }
+}
+
+/// 展示给用户的错误说明
+pub fn describe_error(error: &UkeepError) -> String {
+    match error {
+        UkeepError::Serialization(_) => "数据序列化失败".to_string(),
+        UkeepError::QuotaExceeded => "浏览器存储空间已满，可以先删除一些历史快照".to_string(),
+        UkeepError::DomUnavailable(_) => "当前浏览器不支持这个操作".to_string(),
+        UkeepError::InvalidFile(FileProblem::Empty) => "文件是空的".to_string(),
+        UkeepError::InvalidFile(FileProblem::Encoding) => "文件编码错误，请使用 UTF-8 编码".to_string(),
+        UkeepError::InvalidFile(FileProblem::Malformed(_)) => "文件不是有效的 uKeep 备份".to_string(),
+        UkeepError::InvalidFile(FileProblem::MissingColumn(column)) => {
+            format!("缺少 {}（{}）列", column, column_label(column))
+        }
+        UkeepError::VersionMismatch { found, supported } => {
+            format!("文件的格式版本为 {}，当前只支持版本 {}，请更新 uKeep 后再试", found, supported)
+        }
+        UkeepError::InvalidRows(rows) => format!("有 {} 处数据无效，没有导入任何物品", rows.len()),
+        UkeepError::PassphraseRequired => "该备份已加密，请输入口令".to_string(),
+        UkeepError::Crypto(e) => match e {
+            CryptoError::NotEncrypted => "文件不是加密备份".to_string(),
+            CryptoError::UnsupportedVersion(v) => format!("不支持的加密格式版本: {}", v),
+            CryptoError::Truncated => "加密数据不完整".to_string(),
+            CryptoError::WrongPassphrase => "口令错误".to_string(),
+            CryptoError::Tampered => "数据已被篡改或损坏".to_string(),
+            CryptoError::Internal(_) => "加密失败".to_string(),
+        },
+        UkeepError::Network(_) => "无法连接服务器，请检查网络和地址".to_string(),
+        UkeepError::Unauthorized => "用户名或密码错误".to_string(),
+        UkeepError::HttpStatus(status) => format!("服务器返回错误 (HTTP {})", status),
+    }
+}
+
+// 展开后显示的详细信息：每个出错的行，以及供排查问题用的原始错误
+fn error_details(error: &UkeepError) -> Vec<String> {
+    let mut details: Vec<String> = match error {
+        UkeepError::InvalidRows(rows) => rows.iter().map(describe_row).collect(),
+        _ => Vec::new(),
+    };
+    details.push(error.to_string());
+    details
+}
+
+/// 错误提示，带可以展开的详细信息
+/// - `title`：出错的操作，例如“导入失败”
+#[component]
+pub fn ErrorPanel(title: String, error: UkeepError, on_dismiss: EventHandler<()>) -> Element {
+    let details = error_details(&error);
+
+    rsx! {
+        div { class: "bg-red-50 dark:bg-red-950/40 text-red-700 dark:text-red-300 p-4 rounded-xl mb-4 shadow-sm", role: "alert",
+            div { class: "flex justify-between items-start gap-3",
+                span { class: "flex-1", "{title}：{describe_error(&error)}" }
+                button {
+                    class: "text-red-700 hover:text-red-900",
+                    "aria-label": "关闭",
+                    onclick: move |_| on_dismiss.call(()),
+                    "✕"
+                }
+            }
+            details { class: "mt-2 text-sm",
+                summary { class: "cursor-pointer select-none", "查看详情" }
+                ul { class: "mt-2 flex flex-col gap-1 max-h-48 overflow-y-auto",
+                    for line in details {
+                        li { class: "font-mono text-xs break-all", "{line}" }
+                    }
+                }
+            }
+        }
+    }
+}
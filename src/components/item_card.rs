@@ -1,180 +1,301 @@
-use crate::models::Item;
+use crate::gesture::{
+    GestureAction, PointerInput, QuantitySteps, SwipeGesture, Viewport, DROPDOWN_ITEM_H, DROPDOWN_W, LONG_PRESS_MS, SWIPE_THRESHOLD,
+};
+use crate::components::{status_card_class, status_icon, AmountDialog};
+use crate::keyboard::KeyCommand;
+use crate::models::{format_amount, CardAction, Item, Quantity, StatusPalette, SwipeSettings, Thresholds, Unit};
+use crate::state::{AppearanceState, ClockState};
+use dioxus::core::Task;
 use dioxus::events::PointerEvent;
 use dioxus::prelude::*;
 use dioxus::web::WebEventExt;
+use gloo_timers::future::TimeoutFuture;
 use uuid::Uuid;
 use wasm_bindgen::JsCast;
 
 // ItemCard 组件：单个物品的“卡片”，支持左右滑动手势
-// - 向右滑：代表“吃掉了”，数量>1时会弹出数量选择菜单
-// - 向左滑：代表“扔掉了”
+// - 左右滑动执行的操作来自设置，默认右滑“吃掉了”、左滑“扔掉了”
+// - 右滑是“吃掉了”且数量>1时会弹出数量选择菜单
 // - 松手时根据滑动方向和位置触发回调
-
-// 触发左右滑动操作的最小水平位移（像素）
-const SWIPE_THRESHOLD: f64 = 80.0;
-// 下拉菜单中单个选项的高度（像素）
-const DROPDOWN_ITEM_H: f64 = 48.0;
-// 下拉菜单最小宽度（像素）
-const DROPDOWN_W: f64 = 72.0;
-// 菜单相对锚点的水平间距（像素）
-const DROPDOWN_GAP: f64 = 12.0;
-// 菜单距离视口边缘的最小留白（像素）
-const VIEWPORT_PAD: f64 = 12.0;
-// “划出屏幕”判定的边缘留白（像素）
-const OFFSCREEN_MARGIN: f64 = 8.0;
-// 菜单中最多显示的离散数量选项个数
-const MAX_VISIBLE_ITEMS: u32 = 10;
-
-// 滑动行为的枚举：向右消费 / 向左丢弃
-#[derive(Clone, Copy, PartialEq, Eq)]
-enum SwipeAction {
-    // 吃掉：向右滑动
-    Consume,
-    // 扔掉：向左滑动
-    Waste,
-}
+// - 长按或点“更多”按钮：打开操作菜单，列出所有操作和“多选”
+// - 多选模式下点击切换选中、不再响应滑动
+// - 键盘：卡片本身可以聚焦，按键含义见 `keyboard::KeyCommand`
+// 手势判断都在 `gesture::SwipeGesture` 中，这里只负责把浏览器事件交给它并渲染结果
 
 // 从浏览器 window 中获取当前视口宽高
-fn viewport_size() -> (f64, f64) {
+fn viewport_size() -> Viewport {
     web_sys::window()
-        .map(|w| {
-            // inner_width / inner_height 返回 JsValue，这里尽量转成 f64，失败就用默认值
-            let width = w.inner_width().ok().and_then(|v| v.as_f64()).unwrap_or(390.0);
-            let height = w.inner_height().ok().and_then(|v| v.as_f64()).unwrap_or(844.0);
-            (width, height)
+        .and_then(|w| {
+            // inner_width / inner_height 返回 JsValue，这里尽量转成 f64
+            let width = w.inner_width().ok()?.as_f64()?;
+            let height = w.inner_height().ok()?.as_f64()?;
+            Some(Viewport { width, height })
         })
         // 如果连 window 都拿不到（极端情况），使用一个常见移动端视口默认值
-        .unwrap_or((390.0, 844.0))
+        .unwrap_or_default()
 }
 
-// 根据物品数量构建“可选择的消费数量”步骤，例如 [1,2,3,5,10, quantity]
-fn build_steps(quantity: u32) -> Vec<u32> {
-    // 数量较少时，直接生成 1..=quantity 的线性列表
-    if quantity <= MAX_VISIBLE_ITEMS {
-        (1..=quantity).collect()
-    } else {
-        // 数量很多时，用几个常用步长 + “全部”
-        let mut steps: Vec<u32> = [1, 2, 3, 5, 10]
-            .iter()
-            .copied()
-            // 只保留小于总数量的项
-            .filter(|&v| v < quantity)
-            .collect();
-        // 再把“全部”放在最后一个选项
-        steps.push(quantity);
-        steps
+// 卡片在 DOM 中的 id，用于移动键盘焦点
+fn card_dom_id(id: Uuid) -> String {
+    format!("item-card-{id}")
+}
+
+// 把键盘焦点移到指定 id 的元素上
+fn focus_element(id: &str) {
+    let el = web_sys::window()
+        .and_then(|w| w.document())
+        .and_then(|d| d.get_element_by_id(id))
+        .and_then(|el| el.dyn_into::<web_sys::HtmlElement>().ok());
+    if let Some(el) = el {
+        let _ = el.focus();
     }
 }
 
-// 根据锚点位置和选项个数计算菜单外框的 left / top / height
-fn menu_frame(anchor_x: f64, anchor_y: f64, count: usize) -> (f64, f64, f64) {
-    let (vw, vh) = viewport_size();
-    // 菜单总高度 = 单项高度 * 项数
-    let h = DROPDOWN_ITEM_H * count as f64;
-    // 限制菜单左侧坐标不能超出视口左右边界
-    let max_left = (vw - DROPDOWN_W - VIEWPORT_PAD).max(VIEWPORT_PAD);
-    // 限制菜单顶部坐标不能超出视口上下边界
-    let max_top = (vh - h - VIEWPORT_PAD).max(VIEWPORT_PAD);
-    // 菜单左侧在锚点右侧稍微偏移 DROPDOWN_GAP
-    let left = (anchor_x + DROPDOWN_GAP).clamp(VIEWPORT_PAD, max_left);
-    // 菜单竖直方向以锚点为中心稍微往上偏移
-    let top = (anchor_y - DROPDOWN_ITEM_H / 2.0).clamp(VIEWPORT_PAD, max_top);
-    (left, top, h)
+/// 把键盘焦点移到某个物品的卡片上
+pub fn focus_card(id: Uuid) {
+    focus_element(&card_dom_id(id));
+}
+
+// 在菜单里按方向键时，把焦点移到当前焦点前后最近的菜单项
+fn focus_sibling_item(forward: bool) {
+    let mut next = web_sys::window().and_then(|w| w.document()).and_then(|d| d.active_element());
+    loop {
+        next = next.and_then(|el| if forward { el.next_element_sibling() } else { el.previous_element_sibling() });
+        match next {
+            Some(ref el) if el.get_attribute("role").as_deref() == Some("menuitem") => {
+                if let Ok(el) = el.clone().dyn_into::<web_sys::HtmlElement>() {
+                    let _ = el.focus();
+                }
+                return;
+            }
+            Some(_) => continue,
+            None => return,
+        }
+    }
 }
 
-// 在数量菜单中，根据指针位置 (px, py) 命中具体的数量选项
-fn hit_test(px: f64, py: f64, ax: f64, ay: f64, steps: &[u32]) -> Option<u32> {
-    let (left, top, h) = menu_frame(ax, ay, steps.len());
-    // 水平方向使用更宽松的命中区域（min-width + padding），提高命中容错
-    let effective_w = DROPDOWN_W + 32.0;
-    // 不在菜单矩形区域内，直接返回 None
-    if px < left || px > left + effective_w || py < top || py > top + h {
-        return None;
+// 把 Dioxus 的指针事件转换为手势状态机的输入
+fn pointer_input(evt: &PointerEvent) -> PointerInput {
+    let raw: web_sys::PointerEvent = evt.as_web_event();
+    let point = evt.client_coordinates();
+    PointerInput {
+        id: raw.pointer_id(),
+        x: point.x,
+        y: point.y,
     }
-    // 纵向通过 y 偏移所在的“行号”来推算 index
-    let idx = ((py - top) / DROPDOWN_ITEM_H).floor() as usize;
-    // 从 steps 中取出对应数量
-    steps.get(idx).copied()
 }
 
-// 判断指针是否已经接近屏幕右边缘（用于“快速吃掉 1 个”）
-fn is_offscreen(px: f64) -> bool {
-    let (vw, _) = viewport_size();
-    px >= vw - OFFSCREEN_MARGIN
+// 吃掉时可以选择的数量：包括常用数量和“自定义…”
+fn consume_steps(quantity: Quantity, usual: Option<f64>) -> QuantitySteps {
+    QuantitySteps::new(quantity).with_usual(usual).with_custom()
 }
 
-/// 单个库存条目的卡片组件，支持左右滑动与数量菜单
-/// - 右滑：触发 `on_consume`，数量>1 时可选择消费数量
-/// - 左滑：触发 `on_waste`，表示丢弃该条目
+// 右滑的数量选项：只有右滑是“吃掉了”时才有数量菜单
+fn steps_for(swipe: SwipeSettings, quantity: Quantity, usual: Option<f64>) -> QuantitySteps {
+    if swipe.right == CardAction::Consume {
+        consume_steps(quantity, usual)
+    } else {
+        QuantitySteps::default()
+    }
+}
+
+// 预留数量的标签，例如 "2/3"、"½/2包"、"500 ml/1 L"
+fn reserved_label(reserved: f64, quantity: Quantity) -> String {
+    let reserved = reserved.min(quantity.amount());
+    match quantity.unit() {
+        Unit::Piece => format!("{}/{}", format_amount(reserved), format_amount(quantity.amount())),
+        Unit::Pack => format!("{}/{}", format_amount(reserved), quantity),
+        unit => format!("{}/{}", Quantity::new(reserved, unit), quantity),
+    }
+}
+
+/// 单个库存条目的卡片组件，支持左右滑动、数量菜单和操作菜单
+/// - 吃掉：触发 `on_consume`，右滑且数量>1 时可选择消费数量
+/// - 扔掉：触发 `on_waste`，表示丢弃该条目
+/// - 其它操作（冷冻、开封、移动、加入购物清单）：触发 `on_action`
+/// - 操作菜单中的“多选”或多选模式下点击：触发 `on_select`
+/// - 方向键等移动焦点的按键：触发 `on_navigate`，由列表决定聚焦哪一张卡片
 #[component]
 pub fn ItemCard(
     item: Item,
-    // 吃掉回调：携带物品 ID 和要消费的数量
-    on_consume: EventHandler<(Uuid, u32)>,
+    // 物品所属分类的临期阈值，决定卡片颜色
+    #[props(default)] thresholds: Thresholds,
+    // 饭菜计划中预留的数量（按物品自身的单位），0 表示没有预留
+    #[props(default)] reserved: f64,
+    // 根据历史记录推断的平时一次吃的数量
+    #[props(default)] usual: Option<f64>,
+    // 吃掉回调：携带物品 ID 和要消费的数量（按物品自身的单位）
+    on_consume: EventHandler<(Uuid, f64)>,
     // 扔掉回调：只需要物品 ID
     on_waste: EventHandler<Uuid>,
+    // 左右滑动分别执行的操作
+    #[props(default)] swipe: SwipeSettings,
+    // 其它操作回调：携带物品 ID 和操作
+    on_action: EventHandler<(Uuid, CardAction)>,
+    // 是否处于多选模式，以及本卡片是否被选中
+    #[props(default)] selecting: bool,
+    #[props(default)] selected: bool,
+    // 切换选中回调：操作菜单中选“多选”或多选模式下点击时触发
+    on_select: EventHandler<Uuid>,
+    // 是否是列表中唯一可以用 Tab 聚焦的卡片（roving tabindex）
+    #[props(default)] active: bool,
+    // 卡片获得焦点
+    on_focus: EventHandler<Uuid>,
+    // 在卡片上按了移动焦点的按键
+    on_navigate: EventHandler<(Uuid, KeyCommand)>,
+    // 窗口化列表中卡片的位置（从 1 开始）和列表总数，供读屏软件使用，0 表示不提供
+    #[props(default)] position: usize,
+    #[props(default)] total: usize,
+    // 挂载后是否立即获得焦点：键盘移动到还没渲染的卡片时使用
+    #[props(default)] autofocus: bool,
 ) -> Element {
     // 预先取出常用字段，避免多次方法调用
     let item_id = item.id();
     let quantity = item.quantity();
+    let today = use_context::<ClockState>().today();
+    // 新鲜程度决定卡片配色，配色方案来自外观设置
+    let status = item.status(&thresholds, today);
+    let palette = use_context::<AppearanceState>().0.read().palette;
 
-    // 当前水平拖拽偏移量（相对于按下时的起点，单位：像素）
-    let mut drag_x = use_signal(|| 0.0_f64);
-    // PointerDown 时记录的起始 X 坐标，用于后续计算 delta
-    let mut start_x = use_signal(|| 0.0_f64);
-    // 当前是否处于拖拽中
-    let mut is_dragging = use_signal(|| false);
-    // 当前推断出的滑动动作（向左/向右），用于 PointerUp 时决定触发哪种行为
-    let mut pending_action = use_signal(|| None::<SwipeAction>);
-
-    // 当前正在跟踪的 Pointer 的 ID，用来忽略其它手指/设备的事件
-    let mut pointer_id = use_signal(|| None::<i32>);
+    // 手势状态：指针、拖拽偏移、滑动方向和数量菜单
+    let mut gesture = use_signal(SwipeGesture::default);
     // 被 set_pointer_capture 捕获事件的元素，用于结束时释放捕获
     let mut captured_el = use_signal(|| None::<web_sys::Element>);
-    // 数量菜单的锚点坐标（一般是第一次右滑达到阈值时的指针位置）
-    let mut menu_anchor = use_signal(|| None::<(f64, f64)>);
-    // 当前 hover 命中的数量（用于高亮菜单项）
-    let mut hovered = use_signal(|| None::<u32>);
-
-    // 根据物品数量预先计算好可选的消费数量列表（memo 避免重复计算）
-    let steps = use_memo(move || build_steps(quantity));
-
-    // 重置所有与拖拽和菜单相关的状态
-    let mut reset = move || {
-        // 如果之前捕获过 Pointer，需要先释放 capture
-        if let (Some(el), Some(pid)) = (captured_el.read().clone(), *pointer_id.read()) {
+    // 是否展开批次列表
+    let mut expanded = use_signal(|| false);
+    // 长按计时任务，手势结束时取消
+    let mut long_press = use_signal(|| None::<Task>);
+    // 是否打开操作菜单
+    let mut menu_open = use_signal(|| false);
+    // 是否打开键盘使用的数量选择
+    let mut choosing = use_signal(|| false);
+    // 是否打开“自定义…”数量输入
+    let mut custom = use_signal(|| false);
+
+    // 只有右滑是“吃掉了”时才有数量菜单，选项随物品数量变化，每次渲染重新计算
+    let steps = steps_for(swipe, quantity, usual);
+    // 键盘使用的数量选择和右滑的数量菜单一致，但不受滑动设置影响
+    let amounts = consume_steps(quantity, usual);
+    let quick = amounts.quick();
+    // 操作菜单中列出对这个物品有意义的操作
+    let actions: Vec<CardAction> = CardAction::ALL.into_iter().filter(|a| a.applies_to(&item)).collect();
+
+    // 结束一轮手势：释放 pointer capture、取消长按计时，状态机恢复初始状态
+    let mut finish = move || {
+        if let (Some(el), Some(pid)) = (captured_el.read().clone(), gesture.peek().pointer()) {
             let _ = el.release_pointer_capture(pid);
         }
-        // 清空 capture 相关状态
         captured_el.set(None);
-        pointer_id.set(None);
-        // 将拖拽相关状态恢复到初始值
-        drag_x.set(0.0);
-        is_dragging.set(false);
-        pending_action.set(None);
-        // 菜单与 hover 状态也一并清掉
-        menu_anchor.set(None);
-        hovered.set(None);
+        if let Some(task) = long_press.take() {
+            task.cancel();
+        }
+        gesture.write().reset();
+    };
+
+    // 执行一个操作：吃掉、扔掉有专门的回调，其它交给 on_action
+    let run = move |action: CardAction, amount: f64| match action {
+        CardAction::Consume => on_consume.call((item_id, amount)),
+        CardAction::Waste => on_waste.call(item_id),
+        action => on_action.call((item_id, action)),
+    };
+
+    // 把状态机给出的动作按设置转换为回调
+    let mut dispatch = move |action: GestureAction| match action {
+        GestureAction::SwipeRight(amount) => run(swipe.right, amount),
+        GestureAction::CustomAmount => custom.set(true),
+        GestureAction::SwipeLeft => run(swipe.left, quick),
+        GestureAction::LongPress => menu_open.set(true),
+    };
+
+    // 从操作菜单中选择一项：先关闭菜单、把焦点还给卡片再执行
+    let mut choose = move |action: Option<CardAction>| {
+        menu_open.set(false);
+        focus_card(item_id);
+        match action {
+            Some(action) => run(action, quick),
+            None => on_select.call(item_id),
+        }
+    };
+
+    // 从数量选择中选择一项，None 表示“自定义…”
+    let mut choose_amount = move |amount: Option<f64>| {
+        choosing.set(false);
+        match amount {
+            Some(amount) => {
+                focus_card(item_id);
+                on_consume.call((item_id, amount));
+            }
+            None => custom.set(true),
+        }
+    };
+
+    // 关闭数量选择或自定义数量，把焦点还给卡片
+    let mut close_amounts = move || {
+        choosing.set(false);
+        custom.set(false);
+        focus_card(item_id);
+    };
+
+    // 菜单内的按键：方向键在菜单项之间移动，Esc 关闭；按键不再冒泡到卡片
+    let mut menu_key = move |evt: KeyboardEvent| {
+        evt.stop_propagation();
+        match KeyCommand::from_key(&evt.key().to_string()) {
+            Some(KeyCommand::Next) => {
+                evt.prevent_default();
+                focus_sibling_item(true);
+            }
+            Some(KeyCommand::Previous) => {
+                evt.prevent_default();
+                focus_sibling_item(false);
+            }
+            Some(KeyCommand::Close) => {
+                menu_open.set(false);
+                close_amounts();
+            }
+            _ => {}
+        }
+    };
+
+    // 卡片上的按键：移动焦点、吃掉、扔掉、打开菜单、按数字选择数量
+    let on_key_down = {
+        let amounts = amounts.clone();
+        move |evt: KeyboardEvent| {
+            let modifiers = evt.modifiers();
+            if modifiers.ctrl() || modifiers.alt() || modifiers.meta() {
+                return;
+            }
+            let Some(command) = KeyCommand::from_key(&evt.key().to_string()) else {
+                return;
+            };
+            match command {
+                KeyCommand::Next | KeyCommand::Previous | KeyCommand::First | KeyCommand::Last => {
+                    // 阻止页面滚动
+                    evt.prevent_default();
+                    on_navigate.call((item_id, command));
+                }
+                // 多选模式下 Enter 切换选中，其它操作由工具栏完成
+                KeyCommand::Open if selecting => on_select.call(item_id),
+                _ if selecting => {}
+                KeyCommand::Open => menu_open.set(true),
+                KeyCommand::Consume if amounts.has_menu() => choosing.set(true),
+                KeyCommand::Consume => on_consume.call((item_id, quick)),
+                KeyCommand::Waste => on_waste.call(item_id),
+                KeyCommand::Quantity(index) if index < amounts.rows() => choose_amount(amounts.steps().get(index).copied()),
+                KeyCommand::Quantity(_) => {}
+                KeyCommand::Close => {}
+            }
+        }
     };
 
     // Pointer 按下：开始一轮新的拖拽序列
     let on_pointer_down = move |evt: PointerEvent| {
-        if *is_dragging.read() {
+        // 多选模式下只响应点击
+        if selecting || !gesture.write().pointer_down(pointer_input(&evt)) {
             return;
         }
         // 阻止浏览器原生拖拽（emoji 等内容会触发），否则后续 pointermove 被抑制
         evt.prevent_default();
         let raw: web_sys::PointerEvent = evt.as_web_event();
-        let cx = evt.client_coordinates().x;
-
-        is_dragging.set(true);
-        start_x.set(cx);
-        drag_x.set(0.0);
-        pending_action.set(None);
-        menu_anchor.set(None);
-        hovered.set(None);
-        pointer_id.set(Some(raw.pointer_id()));
-        captured_el.set(None);
 
         // 使用 target()（实际点击的子元素）而非 current_target()（事件委托根节点），
         // 确保后续 pointer 事件从该子元素冒泡经过卡片 div，Dioxus 才能正确路由到处理器
@@ -182,189 +303,149 @@ pub fn ItemCard(
             let _ = el.set_pointer_capture(raw.pointer_id());
             captured_el.set(Some(el));
         }
+
+        // 计时到期时由状态机判断是否仍算长按
+        long_press.set(Some(spawn(async move {
+            TimeoutFuture::new(LONG_PRESS_MS).await;
+            long_press.set(None);
+            let action = gesture.write().long_press();
+            if let Some(action) = action {
+                finish();
+                dispatch(action);
+            }
+        })));
     };
 
-    // Pointer 移动：更新拖拽偏移，并根据方向/距离更新 pending_action 和菜单状态
+    // Pointer 移动：交给状态机更新偏移、方向和菜单高亮
     let on_pointer_move = move |evt: PointerEvent| {
-        // 如果当前不在拖拽中，忽略 move 事件
-        if !*is_dragging.read() {
+        if !gesture.peek().is_active() {
             return;
         }
-        let raw: web_sys::PointerEvent = evt.as_web_event();
-        // 只处理当前记录的 pointer（避免多指场景的干扰）
-        if Some(raw.pointer_id()) != *pointer_id.read() {
-            return;
-        }
-
-        // 当前指针的坐标
-        let cx = evt.client_coordinates().x;
-        let cy = evt.client_coordinates().y;
-        // 水平位移 = 当前 X - 起点 X
-        let delta = cx - *start_x.read();
-        // 保存最新的拖拽偏移，用于 UI 展示
-        drag_x.set(delta);
-
-        // 当前是否已经激活过数量菜单（锚点是否存在）
-        let menu_active = menu_anchor.read().is_some();
-
-        // 向右滑动距离达到阈值：进入“吃掉”模式
-        if delta >= SWIPE_THRESHOLD {
-            pending_action.set(Some(SwipeAction::Consume));
-            // 数量>1时才显示数量菜单
-            if quantity > 1 {
-                // 如果还没有锚点，则用当前指针位置作为锚点
-                let anchor = (*menu_anchor.read()).unwrap_or((cx, cy));
-                if !menu_active {
-                    menu_anchor.set(Some(anchor));
-                }
-                // 根据指针位置在菜单中做 hit-test，更新 hover 项
-                hovered.set(hit_test(cx, cy, anchor.0, anchor.1, &steps.read()));
-            }
-        // 已经激活菜单，但手指又往回滑：菜单继续显示，只更新 hover 项
-        } else if menu_active {
-            // 菜单已激活，即使手指回退也保持菜单显示并继续 hit-test
-            pending_action.set(Some(SwipeAction::Consume));
-            let anchor = (*menu_anchor.read()).unwrap();
-            hovered.set(hit_test(cx, cy, anchor.0, anchor.1, &steps.read()));
-        // 向左滑动达到阈值：进入“扔掉”模式
-        } else if delta <= -SWIPE_THRESHOLD {
-            pending_action.set(Some(SwipeAction::Waste));
-        // 滑动距离尚未达到任何一侧阈值：还没有明确动作
-        } else {
-            pending_action.set(None);
-        }
+        gesture.write().pointer_move(pointer_input(&evt), &steps_for(swipe, quantity, usual), viewport_size());
     };
 
-    // Pointer 松开：根据 pending_action 和位置信息真正触发回调
+    // Pointer 松开：根据状态机返回的动作触发回调
     let on_pointer_up = move |evt: PointerEvent| {
-        // 如果当前不在拖拽中，忽略
-        if !*is_dragging.read() {
+        if !gesture.peek().is_active() {
             return;
         }
-        let raw: web_sys::PointerEvent = evt.as_web_event();
-        // 只处理之前记录的 pointer
-        if Some(raw.pointer_id()) != *pointer_id.read() {
+        let input = pointer_input(&evt);
+        if gesture.peek().pointer() != Some(input.id) {
             return;
         }
-
-        // 松手时指针的最终位置
-        let cx = evt.client_coordinates().x;
-        let cy = evt.client_coordinates().y;
-
-        match *pending_action.read() {
-            // 右滑 & 数量>1：优先根据菜单命中数量决定消费多少
-            Some(SwipeAction::Consume) if quantity > 1 => {
-                if let Some(anchor) = *menu_anchor.read() {
-                    if let Some(count) = hit_test(cx, cy, anchor.0, anchor.1, &steps.read()) {
-                        // 命中了某个数量选项
-                        on_consume.call((item_id, count));
-                    } else if is_offscreen(cx) {
-                        // 没有命中菜单，但滑到屏幕外：快速默认吃掉 1 个
-                        on_consume.call((item_id, 1));
-                    }
-                // 菜单还没出现，但已经划出屏幕：也按吃掉 1 个处理
-                } else if is_offscreen(cx) {
-                    on_consume.call((item_id, 1));
-                }
-            }
-            // 右滑 & 数量==1：直接吃掉 1 个
-            Some(SwipeAction::Consume) => on_consume.call((item_id, 1)),
-            // 左滑：扔掉整个条目
-            Some(SwipeAction::Waste) => on_waste.call(item_id),
-            // 没有明确动作：什么都不做，只恢复位置
-            None => {}
+        let action = gesture.write().pointer_up(input, &steps_for(swipe, quantity, usual), viewport_size());
+        finish();
+        if let Some(action) = action {
+            dispatch(action);
         }
-
-        // 无论是否触发动作，最后都要重置状态
-        reset();
     };
 
     // Pointer 被取消（例如系统打断）：安全地结束当前拖拽
     let on_pointer_cancel = move |evt: PointerEvent| {
-        if !*is_dragging.read() {
-            return;
-        }
-        let raw: web_sys::PointerEvent = evt.as_web_event();
-        if Some(raw.pointer_id()) != *pointer_id.read() {
-            return;
+        if gesture.peek().pointer() == Some(pointer_input(&evt).id) {
+            finish();
         }
-        reset();
     };
 
     // Pointer 离开元素范围：如果当前元素没有 pointer capture，则视为结束拖拽
     let on_pointer_leave = move |_: PointerEvent| {
-        // 如果已经捕获了 pointer，就说明仍可以在元素外收到事件，不需要 reset
-        if !*is_dragging.read() || captured_el.read().is_some() {
+        // 如果已经捕获了 pointer，就说明仍可以在元素外收到事件，不需要结束
+        if !gesture.peek().is_active() || captured_el.read().is_some() {
             return;
         }
-        reset();
+        finish();
     };
 
+    let state = gesture.read().clone();
     // 当前拖拽偏移量，用于平移前景卡片 & 决定背景显示
-    let drag = *drag_x.read();
+    let drag = state.drag_x();
     // 背景提示的显隐程度，0..=1，根据拖拽距离线性变化
     let reveal = (drag.abs() / SWIPE_THRESHOLD).min(1.0);
     // 拖拽中关闭过渡动画，松手后恢复过渡使卡片平滑回弹
-    let transition = if *is_dragging.read() { "none" } else { "transform 200ms ease-out" };
-
-    // 是否需要显示数量菜单（数量>1 且锚点已存在）
-    let show_menu = quantity > 1 && menu_anchor.read().is_some();
-    // 预先根据锚点计算菜单外框位置和高度
-    let menu_data = if show_menu {
-        (*menu_anchor.read()).map(|(ax, ay)| menu_frame(ax, ay, steps.read().len()))
-    } else {
-        None
-    };
-    // 当前 hover 的数量值，用于控制菜单项高亮
-    let hovered_val = *hovered.read();
+    let transition = if state.is_active() { "none" } else { "transform 200ms ease-out" };
+    // 数量菜单的位置（有多个选项且右滑达到阈值后出现）
+    let menu = state.menu(&steps, viewport_size());
+    // 当前 hover 的选项，用于控制菜单项高亮
+    let hovered = state.hovered();
+    // 多个批次时展示每批的日期和数量
+    let batch_count = item.batches().len();
+    // 屏幕阅读器读出的卡片描述
+    let mut description = format!("{}，{}，{}", item.name(), quantity, item.display_deadline(today));
+    if item.opened_on().is_some() {
+        description.push_str("，已开封");
+    }
+    if selecting {
+        description.push_str(if selected { "，已选中" } else { "，未选中" });
+    }
+    let batches: Vec<(String, String)> = item
+        .batches()
+        .iter()
+        .map(|b| {
+            (
+                b.expiry_date().format("%Y-%m-%d").to_string(),
+                Quantity::new(b.amount(), quantity.unit()).to_string(),
+            )
+        })
+        .collect();
 
     rsx! {
         // 外层容器：控制卡片整体背景（状态色）、圆角、间距等
         div {
-            class: "relative flex items-center justify-between overflow-hidden touch-pan-y rounded-xl mb-3 select-none {item.status_class()}",
+            class: "relative flex items-center justify-between overflow-hidden touch-pan-y rounded-xl mb-3 select-none {status_card_class(status, palette)}",
+            class: if selected { "ring-2 ring-blue-500" },
+            class: "focus:outline-none focus-visible:ring-2 focus-visible:ring-blue-600",
+            id: card_dom_id(item_id),
+            role: "listitem",
+            tabindex: if active { "0" } else { "-1" },
+            "aria-label": "{description}",
+            "aria-keyshortcuts": "E W Enter",
+            "aria-posinset": (position > 0).then(|| position.to_string()),
+            "aria-setsize": (total > 0).then(|| total.to_string()),
+            onfocus: move |_| on_focus.call(item_id),
+            onmounted: move |evt| async move {
+                if autofocus {
+                    let _ = evt.set_focus(true).await;
+                }
+            },
+            onkeydown: on_key_down,
 
-            // 右滑背景：左侧的“吃掉了”提示区域
+            // 右滑背景：左侧显示右滑对应的操作
             if drag > 0.0 {
                 div {
-                    class: "absolute left-0 top-0 bottom-0 w-24 flex items-center justify-start pl-4 text-green-600 bg-gradient-to-r from-green-100/50 to-transparent",
+                    class: "absolute left-0 top-0 bottom-0 w-32 flex items-center justify-start pl-4 bg-gradient-to-r to-transparent {swipe.right.tone_class()}",
                     style: "opacity: {reveal}; pointer-events: none;",
-                    span { class: "material-symbols-outlined text-2xl", "restaurant" }
-                    span { class: "ml-2 font-semibold text-sm", "吃掉了" }
+                    span { class: "material-symbols-outlined text-2xl", "{swipe.right.icon()}" }
+                    span { class: "ml-2 font-semibold text-sm whitespace-nowrap", "{swipe.right.label()}" }
                 }
             }
 
-            // 左滑背景：右侧的“扔掉了”提示区域
+            // 左滑背景：右侧显示左滑对应的操作
             if drag < 0.0 {
                 div {
-                    class: "absolute right-0 top-0 bottom-0 w-24 flex items-center justify-end pr-4 text-red-600 bg-gradient-to-l from-red-100/50 to-transparent",
+                    class: "absolute right-0 top-0 bottom-0 w-32 flex items-center justify-end pr-4 bg-gradient-to-l to-transparent {swipe.left.tone_class()}",
                     style: "opacity: {reveal}; pointer-events: none;",
-                    span { class: "mr-2 font-semibold text-sm", "扔掉了" }
-                    span { class: "material-symbols-outlined text-2xl", "delete" }
+                    span { class: "mr-2 font-semibold text-sm whitespace-nowrap", "{swipe.left.label()}" }
+                    span { class: "material-symbols-outlined text-2xl", "{swipe.left.icon()}" }
                 }
             }
 
-            // 数量选择菜单（右滑且数量>1 时出现），使用 fixed 以避免受列表滚动影响
-            if let Some((ml, mt, mh)) = menu_data {
+            // 数量选择菜单（右滑且有多个选项时出现），使用 fixed 以避免受列表滚动影响
+            if let Some(frame) = menu {
                 div {
                     class: "fixed z-50 overflow-hidden rounded-2xl bg-white/95 shadow-xl ring-1 ring-green-200 backdrop-blur-sm",
-                    style: "left:{ml}px;top:{mt}px;min-width:{DROPDOWN_W}px;width:max-content;height:{mh}px;pointer-events:none;padding-inline:8px;",
-                    // 遍历 steps，渲染每一个数量选项
-                    for &count in steps.read().iter() {
+                    style: "left:{frame.left}px;top:{frame.top}px;min-width:{DROPDOWN_W}px;width:max-content;height:{frame.height}px;pointer-events:none;padding-inline:8px;",
+                    // 遍历每一行数量选项，最后可能是“自定义…”
+                    for index in 0..steps.rows() {
                         div {
-                            key: "{item_id}-menu-{count}",
-                            class: if hovered_val == Some(count) {
+                            key: "{item_id}-menu-{index}",
+                            class: if hovered == Some(index) {
                                 // hover 项采用绿色高亮
                                 "flex items-center justify-center bg-green-500 text-white text-sm font-semibold"
                             } else {
                                 "flex items-center justify-center border-b border-gray-100 text-gray-700 text-sm font-medium last:border-b-0"
                             },
                             style: "height:{DROPDOWN_ITEM_H}px;",
-                            // 当数量过大时，最后一个选项显示“全部(xxx)”
-                            if count == quantity && quantity > MAX_VISIBLE_ITEMS {
-                                "全部({count})"
-                            } else {
-                                "{count}"
-                            }
+                            "{steps.label(index)}"
                         }
                     }
                 }
@@ -381,6 +462,21 @@ pub fn ItemCard(
                 onpointerup: on_pointer_up,
                 onpointercancel: on_pointer_cancel,
                 onpointerleave: on_pointer_leave,
+                onclick: move |_| {
+                    if selecting {
+                        on_select.call(item_id);
+                    }
+                },
+
+                // 多选模式下的勾选框
+                if selecting {
+                    div { class: "flex items-center pl-4",
+                        span {
+                            class: if selected { "material-symbols-outlined text-2xl text-blue-600" } else { "material-symbols-outlined text-2xl text-gray-300" },
+                            if selected { "check_circle" } else { "radio_button_unchecked" }
+                        }
+                    }
+                }
 
                 // 左侧：emoji + 名称 + 数量
                 div { class: "flex-1 flex items-center p-4",
@@ -389,32 +485,191 @@ pub fn ItemCard(
                         div { class: "flex items-center gap-2",
                             span { class: "text-lg font-medium text-gray-900", "{item.name()}" }
                             span { class: "inline-flex items-center rounded-full bg-gray-100 px-2 py-0.5 text-xs font-medium text-gray-600",
-                                "x{quantity}"
+                                "{quantity}"
+                            }
+                            if item.opened_on().is_some() {
+                                span { class: "inline-flex items-center rounded-full bg-amber-50 px-2 py-0.5 text-xs font-medium text-amber-600",
+                                    "已开封"
+                                }
+                            }
+                            if reserved > 0.0 {
+                                span { class: "inline-flex items-center rounded-full bg-violet-50 px-2 py-0.5 text-xs font-medium text-violet-600",
+                                    "已预留 {reserved_label(reserved, quantity)}"
+                                }
+                            }
+                        }
+                        // 展示最近的到期日期（yyyy-mm-dd），多个批次时可展开
+                        div { class: "flex items-center gap-2",
+                            span { class: "text-xs text-gray-500", "{item.expiry_date().format(\"%Y-%m-%d\")}" }
+                            if batch_count > 1 {
+                                button {
+                                    class: "inline-flex items-center text-xs text-blue-600 hover:text-blue-800",
+                                    "aria-expanded": "{expanded}",
+                                    // 点击展开时不触发卡片拖拽，按 Enter 时不打开操作菜单
+                                    onpointerdown: move |evt| evt.stop_propagation(),
+                                    onkeydown: move |evt| evt.stop_propagation(),
+                                    // 多选模式下展开批次不切换选中
+                                    onclick: move |evt| {
+                                        evt.stop_propagation();
+                                        expanded.toggle();
+                                    },
+                                    "{batch_count} 批"
+                                    span { class: "material-symbols-outlined text-sm",
+                                        if expanded() { "expand_less" } else { "expand_more" }
+                                    }
+                                }
+                            }
+                        }
+                        if expanded() && batch_count > 1 {
+                            ul { class: "flex flex-col gap-0.5 mt-1",
+                                for (date, amount) in batches {
+                                    li { class: "text-xs text-gray-500", "{date} · {amount}" }
+                                }
                             }
                         }
-                        // 展示到期日期（yyyy-mm-dd）
-                        span { class: "text-xs text-gray-500", "{item.expiry_date().format(\"%Y-%m-%d\")}" }
                     }
                 }
 
-                // 右侧：距离过期的描述（例如“还剩 X 天”）
-                div { class: "flex flex-col items-end justify-center p-4",
-                    span { class: "text-sm font-bold text-gray-700", "{item.display_deadline()}" }
+                // 右侧：距离过期的描述（例如“还剩 X 天”）和“更多”按钮
+                div { class: "flex items-center gap-1 py-4 pr-2",
+                    // 非标准配色下加上图标，不只靠颜色区分新鲜程度
+                    if palette != StatusPalette::Standard {
+                        span { class: "material-symbols-outlined text-base text-gray-700", "aria-hidden": "true", "{status_icon(status)}" }
+                    }
+                    span { class: "text-sm font-bold text-gray-700", "{item.display_deadline(today)}" }
+                    if !selecting {
+                        button {
+                            class: "material-symbols-outlined p-1 rounded-full text-gray-400 hover:text-gray-700 hover:bg-black/5 transition-colors",
+                            "aria-label": "{item.name()} 的更多操作",
+                            "aria-haspopup": "menu",
+                            "aria-expanded": "{menu_open}",
+                            // 点击按钮时不触发卡片拖拽，按键由按钮自己处理
+                            onpointerdown: move |evt| evt.stop_propagation(),
+                            onkeydown: move |evt| evt.stop_propagation(),
+                            onclick: move |evt| {
+                                evt.stop_propagation();
+                                menu_open.set(true);
+                            },
+                            "more_vert"
+                        }
+                    }
+                }
+            }
+
+            // 操作菜单：长按或点“更多”打开，Esc 或点遮罩关闭
+            if menu_open() {
+                div {
+                    class: "fixed inset-0 z-50 bg-black/30 flex items-end sm:items-center justify-center p-4",
+                    onclick: move |_| {
+                        menu_open.set(false);
+                        focus_card(item_id);
+                    },
+                    div {
+                        class: "w-full max-w-sm bg-white rounded-2xl shadow-xl py-2",
+                        role: "menu",
+                        "aria-label": "{item.name()} 的操作",
+                        onclick: move |evt| evt.stop_propagation(),
+                        onkeydown: menu_key,
+                        div { class: "px-4 py-2 text-sm font-semibold text-gray-900", "{item.emoji()}{item.name()}" }
+                        for (index, action) in actions.into_iter().enumerate() {
+                            button {
+                                key: "{action.key()}",
+                                class: "w-full text-left px-4 py-3 hover:bg-gray-50 focus:bg-gray-50 focus:outline-none flex items-center gap-3 text-sm text-gray-700 transition-colors",
+                                role: "menuitem",
+                                // 打开菜单后把焦点放到第一项，方便键盘操作
+                                onmounted: move |evt| async move {
+                                    if index == 0 {
+                                        let _ = evt.set_focus(true).await;
+                                    }
+                                },
+                                onclick: move |_| choose(Some(action)),
+                                span { class: "material-symbols-outlined text-xl", "{action.icon()}" }
+                                span { "{action.label()}" }
+                            }
+                        }
+                        div { class: "h-px bg-gray-100 my-1" }
+                        button {
+                            class: "w-full text-left px-4 py-3 hover:bg-gray-50 focus:bg-gray-50 focus:outline-none flex items-center gap-3 text-sm text-gray-700 transition-colors",
+                            role: "menuitem",
+                            onclick: move |_| choose(None),
+                            span { class: "material-symbols-outlined text-xl", "checklist" }
+                            span { "多选" }
+                        }
+                        button {
+                            class: "w-full text-left px-4 py-3 hover:bg-gray-50 focus:bg-gray-50 focus:outline-none flex items-center gap-3 text-sm text-gray-500 transition-colors",
+                            role: "menuitem",
+                            onclick: move |_| {
+                                menu_open.set(false);
+                                focus_card(item_id);
+                            },
+                            span { class: "material-symbols-outlined text-xl", "close" }
+                            span { "取消" }
+                        }
+                    }
                 }
             }
 
-            // 为无障碍/键盘用户提供的隐藏按钮，等价于左右滑动
-            button {
-                onclick: move |_| on_consume.call((item_id, 1)),
-                class: "sr-only",
-                "aria-label": "吃掉了 (Consumed)",
-                "吃掉了"
+            // 数量选择：键盘按 E 且有多个选项时打开，选项和右滑的数量菜单一致
+            if choosing() {
+                div {
+                    class: "fixed inset-0 z-50 bg-black/30 flex items-end sm:items-center justify-center p-4",
+                    onclick: move |_| close_amounts(),
+                    div {
+                        class: "w-full max-w-sm bg-white rounded-2xl shadow-xl py-2",
+                        role: "menu",
+                        "aria-label": "吃掉多少{item.name()}",
+                        onclick: move |evt| evt.stop_propagation(),
+                        onkeydown: {
+                            let amounts = amounts.clone();
+                            move |evt: KeyboardEvent| {
+                                // 数字键直接选择第几项
+                                if let Some(KeyCommand::Quantity(index)) = KeyCommand::from_key(&evt.key().to_string()) {
+                                    evt.stop_propagation();
+                                    if index < amounts.rows() {
+                                        choose_amount(amounts.steps().get(index).copied());
+                                    }
+                                    return;
+                                }
+                                menu_key(evt);
+                            }
+                        },
+                        div { class: "px-4 py-2 text-sm font-semibold text-gray-900", "吃掉多少 {item.emoji()}{item.name()}？" }
+                        for index in 0..amounts.rows() {
+                            button {
+                                key: "{item_id}-amount-{index}",
+                                class: "w-full text-left px-4 py-3 hover:bg-green-50 focus:bg-green-500 focus:text-white focus:outline-none flex items-center gap-3 text-sm text-gray-700 transition-colors",
+                                role: "menuitem",
+                                "aria-keyshortcuts": if index < 9 { "{index + 1}" } else { "" },
+                                onmounted: move |evt| async move {
+                                    if index == 0 {
+                                        let _ = evt.set_focus(true).await;
+                                    }
+                                },
+                                onclick: {
+                                    let amount = amounts.steps().get(index).copied();
+                                    move |_| choose_amount(amount)
+                                },
+                                if index < 9 {
+                                    span { class: "w-5 text-xs text-gray-400", "{index + 1}" }
+                                }
+                                span { "{amounts.label(index)}" }
+                            }
+                        }
+                    }
+                }
             }
-            button {
-                onclick: move |_| on_waste.call(item_id),
-                class: "sr-only",
-                "aria-label": "扔掉了 (Wasted)",
-                "扔掉了"
+
+            // 自定义数量：数量菜单中选“自定义…”时打开
+            if custom() {
+                AmountDialog {
+                    item: item.clone(),
+                    initial: quick,
+                    on_submit: move |amount| {
+                        close_amounts();
+                        on_consume.call((item_id, amount));
+                    },
+                    on_cancel: move |_| close_amounts(),
+                }
             }
         }
     }
@@ -0,0 +1,67 @@
+use crate::virtual_list::{row_offset, VisibleRows, ROW_HEIGHT};
+use dioxus::prelude::*;
+use std::rc::Rc;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+
+// 监听窗口的这些事件来更新可见行
+const EVENTS: [&str; 2] = ["scroll", "resize"];
+
+// 列表顶部相对视口的位置和视口高度
+fn measure(list_id: &str) -> Option<(f64, f64)> {
+    let window = web_sys::window()?;
+    let top = window.document()?.get_element_by_id(list_id)?.get_bounding_client_rect().top();
+    let height = window.inner_height().ok()?.as_f64()?;
+    Some((top, height))
+}
+
+/// 跟踪 id 为 `list_id` 的列表在窗口中可以看到哪些行，随窗口滚动和缩放更新；
+/// 只有可见行变化时才触发重新渲染
+pub fn use_visible_rows(list_id: &'static str) -> VisibleRows {
+    let mut rows = use_signal(VisibleRows::default);
+    let update = move || {
+        if let Some((top, height)) = measure(list_id) {
+            let measured = VisibleRows::measure(top, height);
+            if *rows.peek() != measured {
+                rows.set(measured);
+            }
+        }
+    };
+
+    // 回调需要在组件存在期间一直保留，卸载时移除监听
+    let listener = use_hook(move || {
+        let closure = Closure::<dyn FnMut()>::new(update);
+        if let Some(window) = web_sys::window() {
+            for event in EVENTS {
+                let _ = window.add_event_listener_with_callback(event, closure.as_ref().unchecked_ref());
+            }
+        }
+        Rc::new(closure)
+    });
+    use_drop(move || {
+        if let Some(window) = web_sys::window() {
+            for event in EVENTS {
+                let _ = window.remove_event_listener_with_callback(event, listener.as_ref().as_ref().unchecked_ref());
+            }
+        }
+    });
+    // 挂载后先测量一次
+    use_effect(update);
+
+    rows()
+}
+
+/// 把列表的第 `index` 行滚动到视口内，已经可见时不滚动
+pub fn scroll_row_into_view(list_id: &str, index: usize) {
+    let Some((top, height)) = measure(list_id) else {
+        return;
+    };
+    if VisibleRows::measure(top, height).contains(index) {
+        return;
+    }
+    // 行顶部在视口中的位置，滚动到距离顶部一行高的地方
+    let row_top = top + row_offset(index);
+    if let Some(window) = web_sys::window() {
+        window.scroll_by_with_x_and_y(0.0, row_top - ROW_HEIGHT);
+    }
+}
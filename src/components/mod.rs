@@ -1,3 +1,15 @@
+mod amount_dialog;
+mod error_panel;
 mod item_card;
+mod list_window;
+mod passphrase_dialog;
+mod theme;
+mod waste_dialog;
 
-pub use item_card::ItemCard;
+pub use amount_dialog::AmountDialog;
+pub use error_panel::{describe_error, ErrorPanel};
+pub use item_card::{focus_card, ItemCard};
+pub use list_window::{scroll_row_into_view, use_visible_rows};
+pub use passphrase_dialog::PassphraseDialog;
+pub use theme::{status_card_class, status_dot_class, status_icon, use_theme};
+pub use waste_dialog::WasteDialog;
@@ -0,0 +1,83 @@
+use dioxus::prelude::*;
+
+/// 口令输入弹窗，用于加密导出和解密导入
+/// - `confirm`：是否要求再次输入口令（加密时使用，避免输错）
+/// - `allow_empty`：是否允许留空（留空表示不加密）
+/// - `error`：由调用方传入的错误提示，例如“口令错误”
+#[component]
+pub fn PassphraseDialog(
+    title: String,
+    description: String,
+    confirm_label: String,
+    #[props(default)] confirm: bool,
+    #[props(default)] allow_empty: bool,
+    #[props(default)] error: Option<String>,
+    on_submit: EventHandler<String>,
+    on_cancel: EventHandler<()>,
+) -> Element {
+    let mut passphrase = use_signal(String::new);
+    let mut repeat = use_signal(String::new);
+    let mut local_error = use_signal(|| Option::<String>::None);
+
+    let submit = move |_| {
+        let value = passphrase.read().clone();
+        if value.is_empty() && !allow_empty {
+            local_error.set(Some("请输入口令".to_string()));
+            return;
+        }
+        if confirm && !value.is_empty() && *repeat.read() != value {
+            local_error.set(Some("两次输入的口令不一致".to_string()));
+            return;
+        }
+        local_error.set(None);
+        on_submit.call(value);
+    };
+
+    let shown_error = local_error().or(error);
+    let input_class = "w-full bg-gray-50 border border-gray-200 rounded-xl px-4 py-3 text-base focus:outline-none focus:ring-2 focus:ring-blue-500 focus:bg-white transition-all";
+
+    rsx! {
+        // 遮罩层
+        div { class: "fixed inset-0 z-50 bg-black/30 flex items-center justify-center p-6",
+            div {
+                class: "w-full max-w-sm bg-white rounded-2xl shadow-xl p-6 flex flex-col gap-4",
+                role: "dialog",
+                "aria-modal": "true",
+                h3 { class: "text-lg font-semibold text-gray-900", "{title}" }
+                p { class: "text-sm text-gray-500", "{description}" }
+                input {
+                    r#type: "password",
+                    class: input_class,
+                    autofocus: true,
+                    value: "{passphrase}",
+                    oninput: move |evt| passphrase.set(evt.value()),
+                    placeholder: "口令"
+                }
+                if confirm && !passphrase.read().is_empty() {
+                    input {
+                        r#type: "password",
+                        class: input_class,
+                        value: "{repeat}",
+                        oninput: move |evt| repeat.set(evt.value()),
+                        placeholder: "再次输入口令"
+                    }
+                }
+                if let Some(err) = shown_error {
+                    div { class: "bg-red-50 text-red-700 px-4 py-3 rounded-xl text-sm", "{err}" }
+                }
+                div { class: "flex gap-3 mt-2",
+                    button {
+                        class: "flex-1 py-3 rounded-xl bg-gray-100 hover:bg-gray-200 text-gray-700 font-medium transition-colors",
+                        onclick: move |_| on_cancel.call(()),
+                        "取消"
+                    }
+                    button {
+                        class: "flex-1 py-3 rounded-xl bg-blue-600 hover:bg-blue-700 text-white font-medium transition-colors",
+                        onclick: submit,
+                        "{confirm_label}"
+                    }
+                }
+            }
+        }
+    }
+}
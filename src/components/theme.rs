@@ -0,0 +1,113 @@
+use crate::models::{ItemStatus, StatusPalette};
+use crate::state::AppearanceState;
+use dioxus::prelude::*;
+use std::rc::Rc;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use web_sys::MediaQueryList;
+
+// 系统是否偏好深色的媒体查询
+const DARK_QUERY: &str = "(prefers-color-scheme: dark)";
+
+fn system_query() -> Option<MediaQueryList> {
+    web_sys::window()?.match_media(DARK_QUERY).ok()?
+}
+
+/// 应用外观设置：在根元素上切换 `dark` 类，主题为“跟随系统”时随系统设置实时变化；
+/// 返回当前是否使用深色界面
+pub fn use_theme() -> bool {
+    let appearance = use_context::<AppearanceState>().0;
+    let mut system_dark = use_signal(|| system_query().is_some_and(|q| q.matches()));
+
+    // 监听的是同一个 MediaQueryList 对象，卸载时才能移除
+    let listener = use_hook(move || {
+        let query = system_query();
+        let closure = Closure::<dyn FnMut()>::new(move || {
+            let dark = system_query().is_some_and(|q| q.matches());
+            if *system_dark.peek() != dark {
+                system_dark.set(dark);
+            }
+        });
+        if let Some(query) = &query {
+            let _ = query.add_event_listener_with_callback("change", closure.as_ref().unchecked_ref());
+        }
+        Rc::new((query, closure))
+    });
+    use_drop(move || {
+        let (query, closure) = listener.as_ref();
+        if let Some(query) = query {
+            let _ = query.remove_event_listener_with_callback("change", closure.as_ref().unchecked_ref());
+        }
+    });
+
+    let dark = appearance.read().theme.is_dark(system_dark());
+    use_effect(move || {
+        let dark = appearance.read().theme.is_dark(system_dark());
+        let root = web_sys::window().and_then(|w| w.document()).and_then(|d| d.document_element());
+        if let Some(root) = root {
+            let _ = root.class_list().toggle_with_force("dark", dark);
+        }
+    });
+    dark
+}
+
+/// 物品卡片按新鲜程度使用的背景和边框类名 (Tailwind CSS Class)
+pub fn status_card_class(status: ItemStatus, palette: StatusPalette) -> &'static str {
+    match (palette, status) {
+        (StatusPalette::Standard, ItemStatus::Expired | ItemStatus::Critical) => {
+            "bg-red-50 dark:bg-red-950/40 border-l-4 border-red-500 shadow-sm"
+        }
+        (StatusPalette::Standard, ItemStatus::Warning) => {
+            "bg-amber-50 dark:bg-amber-950/40 border-l-4 border-amber-500 shadow-sm"
+        }
+        (StatusPalette::Standard, ItemStatus::Safe) => {
+            "bg-white border border-gray-100 border-l-4 border-l-green-500 shadow-sm"
+        }
+        (StatusPalette::HighContrast, ItemStatus::Expired) => {
+            "bg-red-100 dark:bg-red-950 border-2 border-l-8 border-red-800 dark:border-red-400 shadow-sm"
+        }
+        (StatusPalette::HighContrast, ItemStatus::Critical) => {
+            "bg-red-100 dark:bg-red-950 border-2 border-l-8 border-red-600 dark:border-red-300 shadow-sm"
+        }
+        (StatusPalette::HighContrast, ItemStatus::Warning) => {
+            "bg-yellow-100 dark:bg-yellow-950 border-2 border-l-8 border-yellow-600 dark:border-yellow-300 shadow-sm"
+        }
+        (StatusPalette::HighContrast, ItemStatus::Safe) => {
+            "bg-white border-2 border-gray-300 border-l-8 border-l-green-700 dark:border-l-green-400 shadow-sm"
+        }
+        (StatusPalette::ColorBlind, ItemStatus::Expired | ItemStatus::Critical) => {
+            "bg-orange-50 dark:bg-orange-950/40 border-l-4 border-orange-600 shadow-sm"
+        }
+        (StatusPalette::ColorBlind, ItemStatus::Warning) => {
+            "bg-yellow-50 dark:bg-yellow-950/40 border-l-4 border-yellow-400 shadow-sm"
+        }
+        (StatusPalette::ColorBlind, ItemStatus::Safe) => {
+            "bg-white border border-gray-100 border-l-4 border-l-sky-600 shadow-sm"
+        }
+    }
+}
+
+/// 日历中物品表情的底色类名
+pub fn status_dot_class(status: ItemStatus, palette: StatusPalette) -> &'static str {
+    match (palette, status) {
+        (StatusPalette::Standard, ItemStatus::Expired | ItemStatus::Critical) => "bg-red-100 dark:bg-red-900",
+        (StatusPalette::Standard, ItemStatus::Warning) => "bg-amber-100 dark:bg-amber-900",
+        (StatusPalette::Standard, ItemStatus::Safe) => "bg-green-100 dark:bg-green-900",
+        (StatusPalette::HighContrast, ItemStatus::Expired | ItemStatus::Critical) => "bg-red-300 dark:bg-red-700",
+        (StatusPalette::HighContrast, ItemStatus::Warning) => "bg-yellow-300 dark:bg-yellow-600",
+        (StatusPalette::HighContrast, ItemStatus::Safe) => "bg-green-300 dark:bg-green-700",
+        (StatusPalette::ColorBlind, ItemStatus::Expired | ItemStatus::Critical) => "bg-orange-200 dark:bg-orange-800",
+        (StatusPalette::ColorBlind, ItemStatus::Warning) => "bg-yellow-100 dark:bg-yellow-800",
+        (StatusPalette::ColorBlind, ItemStatus::Safe) => "bg-sky-100 dark:bg-sky-900",
+    }
+}
+
+/// 新鲜程度的图标，非标准配色下和颜色一起显示，不只靠颜色区分
+pub fn status_icon(status: ItemStatus) -> &'static str {
+    match status {
+        ItemStatus::Expired => "error",
+        ItemStatus::Critical => "warning",
+        ItemStatus::Warning => "schedule",
+        ItemStatus::Safe => "check_circle",
+    }
+}
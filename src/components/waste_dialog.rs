@@ -0,0 +1,111 @@
+use crate::models::{format_amount, Item, WasteReason};
+use crate::state::ClockState;
+use dioxus::prelude::*;
+
+/// 扔掉物品后弹出的原因选择，可以只扔掉一部分
+/// - `on_submit`：携带扔掉的数量（按物品自身的单位）、原因和备注
+#[component]
+pub fn WasteDialog(
+    item: Item,
+    on_submit: EventHandler<(f64, Option<WasteReason>, Option<String>)>,
+    on_cancel: EventHandler<()>,
+) -> Element {
+    let quantity = item.quantity();
+    // 已经过期的物品默认选“过期了”
+    let expired = item.days_remaining(use_context::<ClockState>().today()) < 0;
+    let mut reason = use_signal(move || expired.then_some(WasteReason::Expired));
+    let mut amount_str = use_signal(move || format_amount(quantity.amount()));
+    let mut note = use_signal(String::new);
+    let mut error = use_signal(|| Option::<String>::None);
+
+    let submit = move |_| {
+        let amount = amount_str.read().trim().parse::<f64>().ok().filter(|a| a.is_finite() && *a > 0.0);
+        let Some(amount) = amount else {
+            error.set(Some("请输入有效的数量".to_string()));
+            return;
+        };
+        if amount > quantity.amount() + 1e-6 {
+            error.set(Some(format!("最多只有 {}", quantity)));
+            return;
+        }
+        on_submit.call((amount.min(quantity.amount()), reason(), Some(note.read().clone())));
+    };
+
+    rsx! {
+        // 遮罩层
+        div { class: "fixed inset-0 z-50 bg-black/30 flex items-center justify-center p-6",
+            div {
+                class: "w-full max-w-sm bg-white rounded-2xl shadow-xl p-6 flex flex-col gap-4",
+                role: "dialog",
+                "aria-modal": "true",
+                "aria-label": "扔掉{item.name()}",
+                onkeydown: move |evt| {
+                    if evt.key() == Key::Escape {
+                        on_cancel.call(());
+                    }
+                },
+                h3 { class: "text-lg font-semibold text-gray-900", "扔掉 {item.emoji()}{item.name()}" }
+
+                // 扔掉的数量，默认全部
+                div { class: "flex items-center gap-2",
+                    label { class: "text-sm text-gray-500", r#for: "waste-amount", "数量" }
+                    input {
+                        id: "waste-amount",
+                        r#type: "number",
+                        min: "0",
+                        step: "any",
+                        class: "w-24 bg-gray-50 border border-gray-200 rounded-xl px-3 py-2 text-base focus:outline-none focus:ring-2 focus:ring-blue-500",
+                        value: "{amount_str}",
+                        oninput: move |evt| amount_str.set(evt.value()),
+                        // 打开时聚焦数量，键盘用户可以直接修改
+                        onmounted: move |evt| async move {
+                            let _ = evt.set_focus(true).await;
+                        },
+                    }
+                    span { class: "text-sm text-gray-500", "共 {quantity}" }
+                }
+
+                // 原因，可以不选
+                div { class: "flex flex-wrap gap-2", role: "radiogroup", "aria-label": "原因",
+                    for option in WasteReason::ALL {
+                        button {
+                            class: if reason() == Some(option) {
+                                "px-3 py-1.5 rounded-full text-sm font-medium bg-red-500 text-white transition-colors"
+                            } else {
+                                "px-3 py-1.5 rounded-full text-sm font-medium bg-gray-100 text-gray-700 hover:bg-gray-200 transition-colors"
+                            },
+                            role: "radio",
+                            "aria-checked": "{reason() == Some(option)}",
+                            onclick: move |_| reason.set((reason() != Some(option)).then_some(option)),
+                            "{option.label()}"
+                        }
+                    }
+                }
+
+                input {
+                    r#type: "text",
+                    class: "w-full bg-gray-50 border border-gray-200 rounded-xl px-4 py-3 text-base focus:outline-none focus:ring-2 focus:ring-blue-500 focus:bg-white transition-all",
+                    value: "{note}",
+                    oninput: move |evt| note.set(evt.value()),
+                    placeholder: "备注（可选）"
+                }
+
+                if let Some(err) = error() {
+                    div { class: "bg-red-50 text-red-700 px-4 py-3 rounded-xl text-sm", "{err}" }
+                }
+                div { class: "flex gap-3 mt-2",
+                    button {
+                        class: "flex-1 py-3 rounded-xl bg-gray-100 hover:bg-gray-200 text-gray-700 font-medium transition-colors",
+                        onclick: move |_| on_cancel.call(()),
+                        "取消"
+                    }
+                    button {
+                        class: "flex-1 py-3 rounded-xl bg-red-500 hover:bg-red-600 text-white font-medium transition-colors",
+                        onclick: submit,
+                        "扔掉"
+                    }
+                }
+            }
+        }
+    }
+}
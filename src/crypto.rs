@@ -0,0 +1,242 @@
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// 加密数据格式（所有整数均为大端序）：
+// | MAGIC (8) | VERSION (1) | salt (16) | m_cost (4) | t_cost (4) | p_cost (4) | key_check (32) | nonce (24) | ciphertext |
+// 其中 nonce 之前的部分作为 AEAD 的附加数据参与认证，任何改动都会被识别为篡改
+
+const MAGIC: &[u8; 8] = b"UKEEPENC";
+/// 加密格式的版本
+pub const VERSION: u8 = 1;
+const SALT_LEN: usize = 16;
+const KEY_LEN: usize = 32;
+const CHECK_LEN: usize = 32;
+const NONCE_LEN: usize = 24;
+const HEADER_LEN: usize = MAGIC.len() + 1 + SALT_LEN + 4 * 3 + CHECK_LEN;
+
+// Argon2id 参数：19 MiB 内存、2 次迭代、单线程（OWASP 推荐的最低配置，兼顾 WASM 性能）
+const M_COST: u32 = 19 * 1024;
+const T_COST: u32 = 2;
+const P_COST: u32 = 1;
+// 解密时允许的最大内存参数，防止恶意文件让浏览器分配过多内存
+const MAX_M_COST: u32 = 256 * 1024;
+
+/// 加解密过程中的错误
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CryptoError {
+    /// 数据不是 uKeep 加密格式
+    NotEncrypted,
+    /// 加密格式版本不受支持
+    UnsupportedVersion(u8),
+    /// 数据不完整
+    Truncated,
+    /// 口令错误
+    WrongPassphrase,
+    /// 数据被篡改或损坏
+    Tampered,
+    /// 密钥派生或随机数生成失败
+    Internal(String),
+}
+
+impl fmt::Display for CryptoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CryptoError::NotEncrypted => write!(f, "文件不是加密备份"),
+            CryptoError::UnsupportedVersion(v) => write!(f, "不支持的加密格式版本: {}", v),
+            CryptoError::Truncated => write!(f, "加密数据不完整"),
+            CryptoError::WrongPassphrase => write!(f, "口令错误"),
+            CryptoError::Tampered => write!(f, "数据已被篡改或损坏"),
+            CryptoError::Internal(e) => write!(f, "加密失败: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for CryptoError {}
+
+/// 由口令派生出的密钥，可以缓存下来反复使用，避免每次都运行 Argon2
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct DerivedKey {
+    salt: [u8; SALT_LEN],
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+    key: [u8; KEY_LEN],
+    check: [u8; CHECK_LEN],
+}
+
+impl fmt::Debug for DerivedKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // 不输出密钥本身
+        f.debug_struct("DerivedKey").field("salt", &self.salt).finish_non_exhaustive()
+    }
+}
+
+impl DerivedKey {
+    /// 使用指定的盐派生密钥；同一口令和盐总能得到相同的密钥
+    pub fn derive(passphrase: &str, salt: [u8; SALT_LEN]) -> Result<Self, CryptoError> {
+        Self::derive_with_params(passphrase, salt, M_COST, T_COST, P_COST)
+    }
+
+    /// 使用随机盐派生密钥，用于一次性的加密备份
+    pub fn derive_random(passphrase: &str) -> Result<Self, CryptoError> {
+        let mut salt = [0u8; SALT_LEN];
+        getrandom::getrandom(&mut salt).map_err(|e| CryptoError::Internal(e.to_string()))?;
+        Self::derive(passphrase, salt)
+    }
+
+    fn derive_with_params(
+        passphrase: &str,
+        salt: [u8; SALT_LEN],
+        m_cost: u32,
+        t_cost: u32,
+        p_cost: u32,
+    ) -> Result<Self, CryptoError> {
+        let params = Params::new(m_cost, t_cost, p_cost, Some(KEY_LEN + CHECK_LEN))
+            .map_err(|e| CryptoError::Internal(e.to_string()))?;
+        let mut output = [0u8; KEY_LEN + CHECK_LEN];
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase.as_bytes(), &salt, &mut output)
+            .map_err(|e| CryptoError::Internal(e.to_string()))?;
+
+        // 前半部分作为加密密钥，后半部分作为口令校验值，用于区分“口令错误”和“数据被篡改”
+        let mut key = [0u8; KEY_LEN];
+        let mut check = [0u8; CHECK_LEN];
+        key.copy_from_slice(&output[..KEY_LEN]);
+        check.copy_from_slice(&output[KEY_LEN..]);
+        Ok(Self {
+            salt,
+            m_cost,
+            t_cost,
+            p_cost,
+            key,
+            check,
+        })
+    }
+
+    fn header(&self) -> Vec<u8> {
+        let mut header = Vec::with_capacity(HEADER_LEN);
+        header.extend_from_slice(MAGIC);
+        header.push(VERSION);
+        header.extend_from_slice(&self.salt);
+        header.extend_from_slice(&self.m_cost.to_be_bytes());
+        header.extend_from_slice(&self.t_cost.to_be_bytes());
+        header.extend_from_slice(&self.p_cost.to_be_bytes());
+        header.extend_from_slice(&self.check);
+        header
+    }
+
+    /// 加密数据，每次调用使用新的随机 nonce
+    pub fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let mut nonce = [0u8; NONCE_LEN];
+        getrandom::getrandom(&mut nonce).map_err(|e| CryptoError::Internal(e.to_string()))?;
+
+        let header = self.header();
+        let cipher = XChaCha20Poly1305::new(Key::from_slice(&self.key));
+        let ciphertext = cipher
+            .encrypt(XNonce::from_slice(&nonce), Payload { msg: plaintext, aad: &header })
+            .map_err(|e| CryptoError::Internal(e.to_string()))?;
+
+        let mut out = header;
+        out.extend_from_slice(&nonce);
+        out.extend_from_slice(&ciphertext);
+        Ok(out)
+    }
+
+    /// 使用缓存的密钥解密；数据必须是用相同盐和参数派生的密钥加密的
+    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let header = Header::parse(data)?;
+        if header.salt != self.salt || header.params() != (self.m_cost, self.t_cost, self.p_cost) {
+            return Err(CryptoError::WrongPassphrase);
+        }
+        self.open(&header, data)
+    }
+
+    fn open(&self, header: &Header, data: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        if header.check != self.check {
+            return Err(CryptoError::WrongPassphrase);
+        }
+        let nonce = &data[HEADER_LEN..HEADER_LEN + NONCE_LEN];
+        let cipher = XChaCha20Poly1305::new(Key::from_slice(&self.key));
+        cipher
+            .decrypt(
+                XNonce::from_slice(nonce),
+                Payload {
+                    msg: &data[HEADER_LEN + NONCE_LEN..],
+                    aad: &data[..HEADER_LEN],
+                },
+            )
+            .map_err(|_| CryptoError::Tampered)
+    }
+}
+
+// 解析后的数据头
+struct Header {
+    salt: [u8; SALT_LEN],
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+    check: [u8; CHECK_LEN],
+}
+
+impl Header {
+    fn parse(data: &[u8]) -> Result<Self, CryptoError> {
+        if !is_encrypted(data) {
+            return Err(CryptoError::NotEncrypted);
+        }
+        let version = data[MAGIC.len()];
+        if version != VERSION {
+            return Err(CryptoError::UnsupportedVersion(version));
+        }
+        if data.len() < HEADER_LEN + NONCE_LEN {
+            return Err(CryptoError::Truncated);
+        }
+
+        let mut pos = MAGIC.len() + 1;
+        let mut take = |len: usize| {
+            let slice = &data[pos..pos + len];
+            pos += len;
+            slice
+        };
+        let mut salt = [0u8; SALT_LEN];
+        salt.copy_from_slice(take(SALT_LEN));
+        let m_cost = u32::from_be_bytes(take(4).try_into().unwrap());
+        let t_cost = u32::from_be_bytes(take(4).try_into().unwrap());
+        let p_cost = u32::from_be_bytes(take(4).try_into().unwrap());
+        let mut check = [0u8; CHECK_LEN];
+        check.copy_from_slice(take(CHECK_LEN));
+        if m_cost > MAX_M_COST || t_cost > 16 || p_cost > 16 {
+            return Err(CryptoError::Tampered);
+        }
+        Ok(Self {
+            salt,
+            m_cost,
+            t_cost,
+            p_cost,
+            check,
+        })
+    }
+
+    fn params(&self) -> (u32, u32, u32) {
+        (self.m_cost, self.t_cost, self.p_cost)
+    }
+}
+
+/// 判断数据是否为 uKeep 加密格式
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.len() > MAGIC.len() && data.starts_with(MAGIC)
+}
+
+/// 使用口令加密数据（随机盐）
+pub fn encrypt(plaintext: &[u8], passphrase: &str) -> Result<Vec<u8>, CryptoError> {
+    DerivedKey::derive_random(passphrase)?.encrypt(plaintext)
+}
+
+/// 使用口令解密数据，口令错误与数据篡改会返回不同的错误
+pub fn decrypt(data: &[u8], passphrase: &str) -> Result<Vec<u8>, CryptoError> {
+    let header = Header::parse(data)?;
+    let key = DerivedKey::derive_with_params(passphrase, header.salt, header.m_cost, header.t_cost, header.p_cost)?;
+    key.open(&header, data)
+}
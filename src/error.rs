@@ -0,0 +1,132 @@
+//! 存储、导入、导出和远程备份过程中的错误。
+//!
+//! 错误只携带结构化的信息，`Display` 输出面向日志的英文描述；
+//! 展示给用户的文字由界面层（`components::describe_error`）按语言生成。
+
+use crate::crypto::CryptoError;
+use gloo_storage::errors::StorageError;
+use std::fmt;
+
+/// 文件本身的问题
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FileProblem {
+    /// 文件是空的
+    Empty,
+    /// 不是 UTF-8 文本
+    Encoding,
+    /// 内容不是预期的格式，附带解析器给出的细节
+    Malformed(String),
+    /// CSV 缺少必需的列，附带列名
+    MissingColumn(&'static str),
+}
+
+/// CSV 中出错的字段
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RowField {
+    Name,
+    Expiry,
+    Quantity,
+    Unit,
+    Location,
+    Currency,
+    Price,
+}
+
+impl RowField {
+    /// 对应的 CSV 列名
+    pub fn column(&self) -> &'static str {
+        match self {
+            RowField::Name => "name",
+            RowField::Expiry => "expiry",
+            RowField::Quantity => "quantity",
+            RowField::Unit => "unit",
+            RowField::Location => "location",
+            RowField::Currency => "currency",
+            RowField::Price => "price",
+        }
+    }
+}
+
+/// CSV 中某一行的错误
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RowError {
+    /// 行号，表头是第 1 行
+    pub line: usize,
+    pub field: RowField,
+    /// 无效的原始值，缺少必填值时为 None
+    pub value: Option<String>,
+}
+
+/// uKeep 的错误
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UkeepError {
+    /// 序列化或反序列化失败
+    Serialization(String),
+    /// 浏览器存储空间已满
+    QuotaExceeded,
+    /// 无法使用浏览器提供的对象，附带对象名称
+    DomUnavailable(&'static str),
+    /// 文件无效
+    InvalidFile(FileProblem),
+    /// 文件的格式版本不受支持
+    VersionMismatch { found: u32, supported: u32 },
+    /// CSV 中有无效的行，包含所有出错的行
+    InvalidRows(Vec<RowError>),
+    /// 加密备份需要口令
+    PassphraseRequired,
+    /// 加密或解密失败
+    Crypto(CryptoError),
+    /// 无法连接远程服务器，附带传输层给出的细节
+    Network(String),
+    /// 远程服务器拒绝了用户名或密码
+    Unauthorized,
+    /// 远程服务器返回了错误状态码
+    HttpStatus(u16),
+}
+
+impl fmt::Display for UkeepError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UkeepError::Serialization(e) => write!(f, "serialization failed: {}", e),
+            UkeepError::QuotaExceeded => write!(f, "storage quota exceeded"),
+            UkeepError::DomUnavailable(what) => write!(f, "{} is unavailable", what),
+            UkeepError::InvalidFile(FileProblem::Empty) => write!(f, "file is empty"),
+            UkeepError::InvalidFile(FileProblem::Encoding) => write!(f, "file is not valid UTF-8"),
+            UkeepError::InvalidFile(FileProblem::Malformed(e)) => write!(f, "malformed file: {}", e),
+            UkeepError::InvalidFile(FileProblem::MissingColumn(c)) => write!(f, "missing column: {}", c),
+            UkeepError::VersionMismatch { found, supported } => {
+                write!(f, "unsupported format version {} (supported: {})", found, supported)
+            }
+            UkeepError::InvalidRows(rows) => write!(f, "{} invalid row(s)", rows.len()),
+            UkeepError::PassphraseRequired => write!(f, "passphrase required"),
+            UkeepError::Crypto(e) => write!(f, "crypto error: {:?}", e),
+            UkeepError::Network(e) => write!(f, "network error: {}", e),
+            UkeepError::Unauthorized => write!(f, "unauthorized"),
+            UkeepError::HttpStatus(status) => write!(f, "unexpected HTTP status {}", status),
+        }
+    }
+}
+
+impl std::error::Error for UkeepError {}
+
+impl From<CryptoError> for UkeepError {
+    fn from(e: CryptoError) -> Self {
+        match e {
+            CryptoError::UnsupportedVersion(found) => UkeepError::VersionMismatch {
+                found: found.into(),
+                supported: crate::crypto::VERSION.into(),
+            },
+            e => UkeepError::Crypto(e),
+        }
+    }
+}
+
+impl From<StorageError> for UkeepError {
+    fn from(e: StorageError) -> Self {
+        match e {
+            StorageError::SerdeError(e) => UkeepError::Serialization(e.to_string()),
+            StorageError::JsError(e) if e.name == "QuotaExceededError" => UkeepError::QuotaExceeded,
+            StorageError::JsError(_) | StorageError::KeyNotFound(_) => UkeepError::DomUnavailable("localStorage"),
+        }
+    }
+}
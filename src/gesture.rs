@@ -0,0 +1,370 @@
+//! 物品卡片的滑动手势状态机：输入指针事件和视口大小，输出要执行的动作。
+//! 不依赖浏览器，所有坐标都是视口坐标（像素）。
+//!
+//! - 向右滑：默认吃掉，提供数量选项时出现数量菜单，松手位置决定数量，
+//!   菜单可以带一个“自定义…”项，由调用方另外询问数量
+//! - 向左滑：默认扔掉
+//! - 按住不动：长按（计时由调用方负责，到时调用 `long_press`）
+//!
+//! 左右滑动具体执行什么操作由调用方按设置决定
+
+use crate::models::{format_amount, Quantity, Unit};
+
+/// 触发左右滑动操作的最小水平位移（像素）
+pub const SWIPE_THRESHOLD: f64 = 80.0;
+/// 下拉菜单中单个选项的高度（像素）
+pub const DROPDOWN_ITEM_H: f64 = 48.0;
+/// 下拉菜单最小宽度（像素）
+pub const DROPDOWN_W: f64 = 72.0;
+/// 按住多久算长按（毫秒）
+pub const LONG_PRESS_MS: u32 = 500;
+/// 菜单中最多显示的离散数量选项个数
+pub const MAX_VISIBLE_ITEMS: u32 = 10;
+// 菜单相对锚点的水平间距（像素）
+const DROPDOWN_GAP: f64 = 12.0;
+// 菜单距离视口边缘的最小留白（像素）
+const VIEWPORT_PAD: f64 = 12.0;
+// “划出屏幕”判定的边缘留白（像素）
+const OFFSCREEN_MARGIN: f64 = 8.0;
+// 菜单命中区域比最小宽度多出的容错（像素）
+const HIT_SLOP_W: f64 = 32.0;
+// 长按期间允许的最大水平位移（像素），超过视为滑动
+const LONG_PRESS_SLOP: f64 = 10.0;
+
+/// 视口大小（像素）
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Viewport {
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Default for Viewport {
+    // 拿不到窗口时使用一个常见移动端视口
+    fn default() -> Self {
+        Self {
+            width: 390.0,
+            height: 844.0,
+        }
+    }
+}
+
+impl Viewport {
+    /// 指针是否已经接近屏幕右边缘（用于“快速吃掉”）
+    pub fn is_offscreen(&self, x: f64) -> bool {
+        x >= self.width - OFFSCREEN_MARGIN
+    }
+}
+
+/// 一次指针事件：指针 ID 和视口坐标
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct PointerInput {
+    pub id: i32,
+    pub x: f64,
+    pub y: f64,
+}
+
+/// 滑动方向
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Swipe {
+    /// 向右
+    Right,
+    /// 向左
+    Left,
+}
+
+/// 手势结束时要执行的动作
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum GestureAction {
+    /// 向右滑，带上菜单中选择的数量（按物品自身的单位），没有菜单时为默认数量
+    SwipeRight(f64),
+    /// 向右滑后选择了“自定义…”，由调用方询问数量
+    CustomAmount,
+    /// 向左滑
+    SwipeLeft,
+    /// 长按
+    LongPress,
+}
+
+/// 数量菜单外框在视口中的位置
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct MenuFrame {
+    pub left: f64,
+    pub top: f64,
+    pub height: f64,
+}
+
+impl MenuFrame {
+    /// 根据锚点位置和选项个数计算菜单外框，保证不超出视口
+    pub fn new(anchor: (f64, f64), count: usize, viewport: Viewport) -> Self {
+        let height = DROPDOWN_ITEM_H * count as f64;
+        let max_left = (viewport.width - DROPDOWN_W - VIEWPORT_PAD).max(VIEWPORT_PAD);
+        let max_top = (viewport.height - height - VIEWPORT_PAD).max(VIEWPORT_PAD);
+        // 菜单在锚点右侧稍微偏移，竖直方向以锚点为中心稍微往上
+        let left = (anchor.0 + DROPDOWN_GAP).clamp(VIEWPORT_PAD, max_left);
+        let top = (anchor.1 - DROPDOWN_ITEM_H / 2.0).clamp(VIEWPORT_PAD, max_top);
+        Self { left, top, height }
+    }
+
+    /// 指针位置命中的选项序号，水平方向使用更宽松的命中区域
+    pub fn hit(&self, x: f64, y: f64) -> Option<usize> {
+        let width = DROPDOWN_W + HIT_SLOP_W;
+        if x < self.left || x > self.left + width || y < self.top || y > self.top + self.height {
+            return None;
+        }
+        Some(((y - self.top) / DROPDOWN_ITEM_H).floor() as usize)
+    }
+}
+
+/// 右滑时可以选择的数量，最后一项总是“全部”；`default()` 表示没有数量菜单
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct QuantitySteps {
+    unit: Unit,
+    steps: Vec<f64>,
+    // 平时一次吃的数量，会出现在选项中并作为默认数量
+    usual: Option<f64>,
+    // 是否在“全部”之后追加“自定义…”一行
+    custom: bool,
+}
+
+impl QuantitySteps {
+    /// 根据物品数量和单位构建选项
+    /// - 个：数量较少时为 1..=quantity，较多时为 [1,2,3,5,10, quantity]
+    /// - 包：额外提供 ¼、½，例如半袋面粉
+    /// - 重量/体积：常用分量，例如 100 g、250 ml、0.5 kg
+    pub fn new(quantity: Quantity) -> Self {
+        let total = quantity.amount();
+        let linear = total <= MAX_VISIBLE_ITEMS as f64;
+        let candidates: Vec<f64> = match quantity.unit() {
+            Unit::Piece if linear => (1..=total.floor() as u32).map(f64::from).collect(),
+            Unit::Piece => vec![1.0, 2.0, 3.0, 5.0, 10.0],
+            Unit::Pack if linear => [0.25, 0.5].into_iter().chain((1..=total.floor() as u32).map(f64::from)).collect(),
+            Unit::Pack => vec![0.25, 0.5, 1.0, 2.0, 3.0, 5.0, 10.0],
+            Unit::Gram | Unit::Milliliter => vec![50.0, 100.0, 250.0, 500.0],
+            Unit::Kilogram | Unit::Liter => vec![0.1, 0.25, 0.5, 1.0],
+        };
+        // 只保留小于总数量的项，再把“全部”放在最后
+        let mut steps: Vec<f64> = candidates.into_iter().filter(|&v| v < total - 1e-6).collect();
+        steps.push(total);
+        Self {
+            unit: quantity.unit(),
+            steps,
+            ..Self::default()
+        }
+    }
+
+    /// 加入平时一次吃的数量（例如根据历史记录推断的“常用 2 个”），
+    /// 不在选项中时按大小插入，不少于全部时忽略
+    pub fn with_usual(mut self, usual: Option<f64>) -> Self {
+        let total = self.steps.last().copied().unwrap_or(0.0);
+        let Some(usual) = usual.filter(|&u| u > 0.0 && u < total - 1e-6) else {
+            return self;
+        };
+        if !self.steps.iter().any(|&v| (v - usual).abs() < 1e-6) {
+            let index = self.steps.iter().position(|&v| v > usual).unwrap_or(self.steps.len());
+            self.steps.insert(index, usual);
+        }
+        self.usual = Some(usual);
+        self
+    }
+
+    /// 在“全部”之后追加“自定义…”；只剩一个（一包）时没有别的数量可选，不追加
+    pub fn with_custom(mut self) -> Self {
+        self.custom = self.steps.len() > 1 || (self.unit.is_continuous() && !self.steps.is_empty());
+        self
+    }
+
+    /// 所有选项
+    pub fn steps(&self) -> &[f64] {
+        &self.steps
+    }
+
+    /// 菜单的行数，包括“自定义…”
+    pub fn rows(&self) -> usize {
+        self.steps.len() + usize::from(self.custom)
+    }
+
+    /// 第 `index` 行是否为“自定义…”
+    pub fn is_custom(&self, index: usize) -> bool {
+        self.custom && index == self.steps.len()
+    }
+
+    /// 平时一次吃的数量
+    pub fn usual(&self) -> Option<f64> {
+        self.usual
+    }
+
+    /// 只有一行（例如只剩 1 个）时不需要数量菜单
+    pub fn has_menu(&self) -> bool {
+        self.rows() > 1
+    }
+
+    /// 不经过菜单时（划出屏幕、键盘操作）默认消耗的数量：有常用数量时用常用数量，
+    /// 否则一个 / 一包，重量和体积取最小步长
+    pub fn quick(&self) -> f64 {
+        if let Some(usual) = self.usual {
+            return usual;
+        }
+        let total = self.steps.last().copied().unwrap_or(1.0);
+        if self.unit.is_continuous() {
+            self.steps.first().copied().unwrap_or(total)
+        } else {
+            total.min(1.0)
+        }
+    }
+
+    /// 第 `index` 个选项的文本：个数只显示数字，其它单位带上单位符号，
+    /// 非线性列表的最后一项显示为“全部(xxx)”，常用数量后面标注“常用”
+    pub fn label(&self, index: usize) -> String {
+        if self.is_custom(index) {
+            return "自定义…".to_string();
+        }
+        let Some(&step) = self.steps.get(index) else {
+            return String::new();
+        };
+        let text = match self.unit {
+            Unit::Piece => format_amount(step),
+            unit => Quantity::new(step, unit).to_string(),
+        };
+        if index + 1 == self.steps.len() && (self.unit != Unit::Piece || step > MAX_VISIBLE_ITEMS as f64) {
+            format!("全部({})", text)
+        } else if self.usual.is_some_and(|u| (u - step).abs() < 1e-6) {
+            format!("{} 常用", text)
+        } else {
+            text
+        }
+    }
+}
+
+/// 一张卡片上的手势状态，一次只跟踪一个指针
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct SwipeGesture {
+    pointer: Option<i32>,
+    start_x: f64,
+    drag_x: f64,
+    // 本轮是否移动超过长按容差，移动过就不再算长按
+    moved: bool,
+    pending: Option<Swipe>,
+    // 数量菜单的锚点（第一次右滑达到阈值时的指针位置）
+    menu_anchor: Option<(f64, f64)>,
+    hovered: Option<usize>,
+}
+
+impl SwipeGesture {
+    /// 是否正在跟踪某个指针
+    pub fn is_active(&self) -> bool {
+        self.pointer.is_some()
+    }
+
+    /// 当前跟踪的指针 ID
+    pub fn pointer(&self) -> Option<i32> {
+        self.pointer
+    }
+
+    /// 相对按下位置的水平偏移，用于平移卡片
+    pub fn drag_x(&self) -> f64 {
+        self.drag_x
+    }
+
+    /// 松手时会触发的滑动方向
+    pub fn pending(&self) -> Option<Swipe> {
+        self.pending
+    }
+
+    /// 数量菜单的位置，菜单未出现时为 None
+    pub fn menu(&self, steps: &QuantitySteps, viewport: Viewport) -> Option<MenuFrame> {
+        self.menu_anchor
+            .filter(|_| steps.has_menu())
+            .map(|anchor| MenuFrame::new(anchor, steps.rows(), viewport))
+    }
+
+    /// 当前指针所在的菜单选项序号，用于高亮
+    pub fn hovered(&self) -> Option<usize> {
+        self.hovered
+    }
+
+    /// 指针按下：开始一轮新的手势，已经在跟踪其它指针时忽略并返回 false
+    pub fn pointer_down(&mut self, input: PointerInput) -> bool {
+        if self.is_active() {
+            return false;
+        }
+        *self = Self {
+            pointer: Some(input.id),
+            start_x: input.x,
+            ..Self::default()
+        };
+        true
+    }
+
+    /// 指针移动：更新偏移、滑动方向和菜单的高亮项
+    pub fn pointer_move(&mut self, input: PointerInput, steps: &QuantitySteps, viewport: Viewport) {
+        if self.pointer != Some(input.id) {
+            return;
+        }
+        let delta = input.x - self.start_x;
+        self.drag_x = delta;
+        if delta.abs() >= LONG_PRESS_SLOP {
+            self.moved = true;
+        }
+
+        if delta >= SWIPE_THRESHOLD {
+            // 向右达到阈值：有多个选项时以当前位置为锚点显示菜单
+            self.pending = Some(Swipe::Right);
+            if steps.has_menu() {
+                self.menu_anchor.get_or_insert((input.x, input.y));
+            }
+        } else if self.menu_anchor.is_some() {
+            // 菜单出现后手指往回滑：菜单继续显示，仍然是向右
+            self.pending = Some(Swipe::Right);
+        } else if delta <= -SWIPE_THRESHOLD {
+            self.pending = Some(Swipe::Left);
+        } else {
+            self.pending = None;
+        }
+        self.hovered = self.menu(steps, viewport).and_then(|m| m.hit(input.x, input.y));
+    }
+
+    /// 指针松开：结束手势并返回要执行的动作，不是当前指针时忽略
+    pub fn pointer_up(&mut self, input: PointerInput, steps: &QuantitySteps, viewport: Viewport) -> Option<GestureAction> {
+        if self.pointer != Some(input.id) {
+            return None;
+        }
+        let action = match self.pending {
+            Some(Swipe::Right) if steps.has_menu() => {
+                // 优先按命中的菜单项，没有命中但划出屏幕时使用默认数量
+                let hit = self.menu(steps, viewport).and_then(|m| m.hit(input.x, input.y));
+                match hit {
+                    Some(index) if steps.is_custom(index) => Some(GestureAction::CustomAmount),
+                    Some(index) if index < steps.steps().len() => Some(GestureAction::SwipeRight(steps.steps()[index])),
+                    _ if viewport.is_offscreen(input.x) => Some(GestureAction::SwipeRight(steps.quick())),
+                    _ => None,
+                }
+            }
+            Some(Swipe::Right) => Some(GestureAction::SwipeRight(steps.quick())),
+            Some(Swipe::Left) => Some(GestureAction::SwipeLeft),
+            None => None,
+        };
+        self.reset();
+        action
+    }
+
+    /// 指针被系统取消：放弃当前手势，不是当前指针时忽略
+    pub fn pointer_cancel(&mut self, id: i32) {
+        if self.pointer == Some(id) {
+            self.reset();
+        }
+    }
+
+    /// 长按计时到期：仍按着且没有移动时结束手势并返回长按
+    pub fn long_press(&mut self) -> Option<GestureAction> {
+        if !self.is_active() || self.moved || self.pending.is_some() {
+            return None;
+        }
+        self.reset();
+        Some(GestureAction::LongPress)
+    }
+
+    /// 恢复到初始状态
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
@@ -0,0 +1,66 @@
+//! 库存列表的键盘操作：把按键映射为命令，并计算焦点应该移到哪一项。
+//! 不依赖浏览器，按键使用 `KeyboardEvent.key` 的标准名称。
+//!
+//! - ↑ / ↓ / Home / End：在卡片之间移动焦点（roving tabindex，列表只占一个 Tab 停靠点）
+//! - E：吃掉，数量菜单有多个选项时先打开数量选择
+//! - W：扔掉
+//! - Enter：打开操作菜单
+//! - 1-9：直接选择数量菜单中的第几项
+
+/// 一次按键对应的命令
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum KeyCommand {
+    /// 下一项
+    Next,
+    /// 上一项
+    Previous,
+    /// 第一项
+    First,
+    /// 最后一项
+    Last,
+    /// 打开菜单
+    Open,
+    /// 吃掉
+    Consume,
+    /// 扔掉
+    Waste,
+    /// 选择数量菜单中的第几项（从 0 开始）
+    Quantity(usize),
+    /// 关闭菜单
+    Close,
+}
+
+impl KeyCommand {
+    /// 按键名称对应的命令，不相关的按键返回 None 以保留浏览器默认行为
+    pub fn from_key(key: &str) -> Option<Self> {
+        let command = match key {
+            "ArrowDown" => KeyCommand::Next,
+            "ArrowUp" => KeyCommand::Previous,
+            "Home" => KeyCommand::First,
+            "End" => KeyCommand::Last,
+            "Enter" => KeyCommand::Open,
+            "e" | "E" => KeyCommand::Consume,
+            "w" | "W" => KeyCommand::Waste,
+            "Escape" => KeyCommand::Close,
+            _ => {
+                let digit = key.parse::<usize>().ok().filter(|d| (1..=9).contains(d))?;
+                KeyCommand::Quantity(digit - 1)
+            }
+        };
+        Some(command)
+    }
+
+    /// 在 `len` 项中从 `current` 移动焦点后的位置，不是移动命令或列表为空时返回 None；
+    /// 到达两端时停住，不循环
+    pub fn move_focus(self, current: usize, len: usize) -> Option<usize> {
+        let last = len.checked_sub(1)?;
+        let current = current.min(last);
+        match self {
+            KeyCommand::Next => Some((current + 1).min(last)),
+            KeyCommand::Previous => Some(current.saturating_sub(1)),
+            KeyCommand::First => Some(0),
+            KeyCommand::Last => Some(last),
+            _ => None,
+        }
+    }
+}
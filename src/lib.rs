@@ -1,7 +1,14 @@
+pub mod clock;
 pub mod components;
+pub mod crypto;
+pub mod error;
+pub mod gesture;
+pub mod keyboard;
 pub mod models;
 pub mod pages;
 pub mod router;
 pub mod state;
 pub mod storage;
+pub mod sync;
 pub mod utils;
+pub mod virtual_list;
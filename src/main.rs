@@ -1,9 +1,20 @@
 #![allow(non_snake_case)]
 
 use dioxus::prelude::*;
+use ukeep::components::{use_theme, ErrorPanel};
+use ukeep::models::{needs_auto_snapshot, record_snapshot, Appearance, Snapshot, SnapshotKind};
 use ukeep::router::Route;
-use ukeep::state::InventoryState;
-use ukeep::storage::{load_inventory, save_inventory};
+use ukeep::state::{
+    AppearanceState, BackupState, CategoryState, ClockState, HistoryState, HouseholdState, InventoryState, PlanState, RecipeState,
+    ShoppingState, SnapshotState, StorageErrorState, SwipeState, SyncState,
+};
+use ukeep::storage::{
+    load_appearance, load_categories, load_history, load_household, load_inventory, load_meal_plan, load_recipes,
+    load_shopping_list, load_simulated_date, load_snapshots, load_swipe_settings, load_webdav_settings, save_appearance,
+    save_categories, save_history, save_household, save_inventory, save_meal_plan, save_recipes, save_shopping_list,
+    save_simulated_date, save_snapshots, save_swipe_settings, save_webdav_settings,
+};
+use ukeep::sync::{use_household_sync, use_scheduled_backup, SyncStatus};
 
 static CSS: Asset = asset!("/assets/tailwind.css");
 
@@ -12,17 +23,73 @@ fn main() {
 }
 
 fn App() -> Element {
-    // 从 LocalStorage 加载数据初始化全局状态
-    use_context_provider(|| InventoryState(Signal::new(load_inventory())));
+    // 从 LocalStorage 加载数据初始化全局状态，时钟最先创建，旧数据补入库日期时要用到“今天”
+    let clock = use_context_provider(|| ClockState::simulating(load_simulated_date()));
+    use_context_provider(|| InventoryState(Signal::new(load_inventory(clock.0.peek().today()))));
+    use_context_provider(|| HistoryState(Signal::new(load_history())));
+    use_context_provider(|| HouseholdState(Signal::new(load_household())));
+    use_context_provider(|| SyncState(Signal::new(SyncStatus::Idle)));
+    use_context_provider(|| BackupState(Signal::new(load_webdav_settings())));
+    use_context_provider(|| SnapshotState(Signal::new(load_snapshots())));
+    use_context_provider(|| ShoppingState(Signal::new(load_shopping_list())));
+    use_context_provider(|| CategoryState(Signal::new(load_categories())));
+    use_context_provider(|| RecipeState(Signal::new(load_recipes())));
+    use_context_provider(|| PlanState(Signal::new(load_meal_plan())));
+    use_context_provider(|| SwipeState(Signal::new(load_swipe_settings())));
+    use_context_provider(|| AppearanceState(Signal::new(load_appearance())));
+    use_context_provider(|| StorageErrorState(Signal::new(None)));
 
     // 获取全局状态用于监听变化
     let inventory = use_context::<InventoryState>().0;
+    let history = use_context::<HistoryState>().0;
+    let household = use_context::<HouseholdState>().0;
+    let backup_settings = use_context::<BackupState>().0;
+    let mut snapshots = use_context::<SnapshotState>().0;
+    let shopping = use_context::<ShoppingState>().0;
+    let categories = use_context::<CategoryState>().0;
+    let recipes = use_context::<RecipeState>().0;
+    let meal_plan = use_context::<PlanState>().0;
+    let swipe = use_context::<SwipeState>().0;
+    let appearance = use_context::<AppearanceState>().0;
+    let mut storage_error = use_context::<StorageErrorState>();
 
-    // 自动保存：监听状态变化，自动持久化到 LocalStorage
+    // 自动保存：监听状态变化，自动持久化到 LocalStorage，保存失败时在页面顶部提示
     use_effect(move || {
         let items = inventory.read().clone();
-        save_inventory(&items);
+        storage_error.report(save_inventory(&items));
     });
+    use_effect(move || storage_error.report(save_history(&history.read())));
+    use_effect(move || storage_error.report(save_household(&household.read())));
+    use_effect(move || storage_error.report(save_webdav_settings(&backup_settings.read())));
+    use_effect(move || storage_error.report(save_snapshots(&snapshots.read())));
+    use_effect(move || storage_error.report(save_shopping_list(&shopping.read())));
+    use_effect(move || storage_error.report(save_categories(&categories.read())));
+    use_effect(move || storage_error.report(save_recipes(&recipes.read())));
+    use_effect(move || storage_error.report(save_meal_plan(&meal_plan.read())));
+    use_effect(move || storage_error.report(save_swipe_settings(&swipe.read())));
+    use_effect(move || storage_error.report(save_simulated_date(&clock.simulated_date())));
+    use_effect(move || storage_error.report(save_appearance(&appearance.read())));
+
+    // 按外观设置切换深色界面
+    let dark = use_theme();
+
+    // 自动快照：每小时第一次改动库存时，保存改动前的库存，并按保留策略清理旧快照
+    let mut previous_inventory = use_signal(|| inventory.peek().clone());
+    use_effect(move || {
+        let items = inventory.read();
+        let now = clock.0.peek().now();
+        let previous = previous_inventory.peek().clone();
+        if needs_auto_snapshot(&snapshots.peek(), &previous, &items, now) {
+            record_snapshot(&mut snapshots.write(), Snapshot::new_at(&previous, SnapshotKind::Auto, now), now);
+        }
+        previous_inventory.set(items.clone());
+    });
+
+    // 加入家庭后与其他成员同步库存
+    use_household_sync();
+
+    // 按设置定时备份到 WebDAV
+    use_scheduled_backup();
 
     // 注册 Service Worker (PWA 支持)
     use_effect(move || {
@@ -52,12 +119,23 @@ fn App() -> Element {
 
         // PWA 支持
         document::Link { rel: "manifest", href: "/assets/manifest.json" }
-        document::Meta { name: "theme-color", content: "#2563eb" }
+        document::Meta { name: "theme-color", content: Appearance::theme_color(dark) }
         document::Meta { name: "apple-mobile-web-app-capable", content: "yes" }
         document::Meta { name: "apple-mobile-web-app-status-bar-style", content: "default" }
         document::Meta { name: "apple-mobile-web-app-title", content: "uKeep" }
         document::Link { rel: "apple-touch-icon", href: "/assets/icon-512.png" }
 
+        // 保存失败（例如存储空间已满）时的提示，所有页面都能看到
+        if let Some(error) = storage_error.0() {
+            div { class: "fixed top-4 inset-x-4 max-w-md mx-auto z-50",
+                ErrorPanel {
+                    title: "保存失败",
+                    error,
+                    on_dismiss: move |_| storage_error.0.set(None),
+                }
+            }
+        }
+
         Router::<Route> {}
     }
 }
@@ -0,0 +1,143 @@
+use super::{category_of, Category, FoodDatabase, Item, Location};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// 物品卡片上可以执行的操作，可以分配给左右滑动，也都出现在长按菜单中
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum CardAction {
+    /// 吃掉
+    Consume,
+    /// 扔掉
+    Waste,
+    /// 放进冷冻室，并按冷冻保质期延长过期日期
+    Freeze,
+    /// 标记已开封，并按开封后的保质期缩短过期日期
+    MarkOpened,
+    /// 移到另一个存放位置，不改变过期日期
+    Move(Location),
+    /// 加入购物清单
+    AddToShopping,
+}
+
+impl CardAction {
+    /// 所有操作，用于设置和长按菜单；放进冷冻室由“冷冻”负责
+    pub const ALL: [CardAction; 7] = [
+        CardAction::Consume,
+        CardAction::Waste,
+        CardAction::Freeze,
+        CardAction::MarkOpened,
+        CardAction::Move(Location::Fridge),
+        CardAction::Move(Location::Pantry),
+        CardAction::AddToShopping,
+    ];
+
+    /// 用于展示的文本
+    pub fn label(&self) -> String {
+        match self {
+            CardAction::Consume => "吃掉了".to_string(),
+            CardAction::Waste => "扔掉了".to_string(),
+            CardAction::Freeze => "冷冻".to_string(),
+            CardAction::MarkOpened => "已开封".to_string(),
+            CardAction::Move(location) => format!("移到{}", location.label()),
+            CardAction::AddToShopping => "加入购物清单".to_string(),
+        }
+    }
+
+    /// Material Symbols 图标名
+    pub fn icon(&self) -> &'static str {
+        match self {
+            CardAction::Consume => "restaurant",
+            CardAction::Waste => "delete",
+            CardAction::Freeze => "ac_unit",
+            CardAction::MarkOpened => "lock_open",
+            CardAction::Move(location) => location.icon(),
+            CardAction::AddToShopping => "add_shopping_cart",
+        }
+    }
+
+    /// 滑动时背景提示的颜色类名 (Tailwind CSS Class)
+    pub fn tone_class(&self) -> &'static str {
+        match self {
+            CardAction::Consume => "text-green-600 from-green-100/50",
+            CardAction::Waste => "text-red-600 from-red-100/50",
+            CardAction::Freeze => "text-sky-600 from-sky-100/50",
+            CardAction::MarkOpened => "text-amber-600 from-amber-100/50",
+            CardAction::Move(_) => "text-violet-600 from-violet-100/50",
+            CardAction::AddToShopping => "text-orange-600 from-orange-100/50",
+        }
+    }
+
+    /// 用于设置表单选项的标识
+    pub fn key(&self) -> String {
+        match self {
+            CardAction::Consume => "consume".to_string(),
+            CardAction::Waste => "waste".to_string(),
+            CardAction::Freeze => "freeze".to_string(),
+            CardAction::MarkOpened => "opened".to_string(),
+            CardAction::Move(location) => format!("move-{}", location.key()),
+            CardAction::AddToShopping => "shopping".to_string(),
+        }
+    }
+
+    /// 从标识解析
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|a| a.key() == key)
+    }
+
+    /// 对这个物品执行是否有意义，例如已经开封的不能再开封
+    pub fn applies_to(&self, item: &Item) -> bool {
+        match self {
+            CardAction::Freeze => item.location() != Location::Freezer,
+            CardAction::MarkOpened => item.opened_on().is_none(),
+            CardAction::Move(location) => item.location() != *location,
+            _ => true,
+        }
+    }
+
+    /// 执行只改变物品本身的操作（冷冻、开封、移动），返回是否有改动；
+    /// 吃掉、扔掉和加入购物清单需要记录历史或修改其它状态，由调用方处理
+    pub fn apply(&self, item: &mut Item, categories: &[Category], today: NaiveDate) -> bool {
+        if !self.applies_to(item) {
+            return false;
+        }
+        match self {
+            CardAction::Freeze => {
+                let days = shelf_life_days(item, categories, Location::Freezer, item.opened_on().is_some());
+                item.freeze(today, days);
+            }
+            CardAction::MarkOpened => {
+                let days = shelf_life_days(item, categories, item.location(), true);
+                item.mark_opened(today, days);
+            }
+            CardAction::Move(location) => item.set_location(*location),
+            _ => return false,
+        }
+        true
+    }
+}
+
+// 物品在指定位置的建议保质期：优先查食物知识库，未开封时再按分类的默认值
+fn shelf_life_days(item: &Item, categories: &[Category], location: Location, opened: bool) -> Option<i64> {
+    let food = FoodDatabase::bundled().lookup(item.name());
+    match food {
+        Some(food) => food.days(location, opened),
+        None if !opened => category_of(categories, item).and_then(|c| c.shelf_life.for_location(location)),
+        None => None,
+    }
+}
+
+/// 左右滑动分别执行的操作
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct SwipeSettings {
+    pub right: CardAction,
+    pub left: CardAction,
+}
+
+impl Default for SwipeSettings {
+    fn default() -> Self {
+        Self {
+            right: CardAction::Consume,
+            left: CardAction::Waste,
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+
+/// 界面的明暗主题
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum Theme {
+    /// 跟随系统设置
+    #[default]
+    System,
+    /// 浅色
+    Light,
+    /// 深色
+    Dark,
+}
+
+impl Theme {
+    /// 所有主题，用于设置
+    pub const ALL: [Theme; 3] = [Theme::System, Theme::Light, Theme::Dark];
+
+    /// 用于展示的文本
+    pub fn label(&self) -> &'static str {
+        match self {
+            Theme::System => "跟随系统",
+            Theme::Light => "浅色",
+            Theme::Dark => "深色",
+        }
+    }
+
+    /// Material Symbols 图标名
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Theme::System => "brightness_auto",
+            Theme::Light => "light_mode",
+            Theme::Dark => "dark_mode",
+        }
+    }
+
+    /// 是否使用深色界面，`system_dark` 为系统当前是否偏好深色
+    pub fn is_dark(&self, system_dark: bool) -> bool {
+        match self {
+            Theme::System => system_dark,
+            Theme::Light => false,
+            Theme::Dark => true,
+        }
+    }
+}
+
+/// 表示物品新鲜程度的配色
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum StatusPalette {
+    /// 红、黄、绿
+    #[default]
+    Standard,
+    /// 更深的颜色和更粗的边框
+    HighContrast,
+    /// 不依赖红绿区分的配色（橙、黄、蓝）
+    ColorBlind,
+}
+
+impl StatusPalette {
+    /// 所有配色，用于设置
+    pub const ALL: [StatusPalette; 3] = [StatusPalette::Standard, StatusPalette::HighContrast, StatusPalette::ColorBlind];
+
+    /// 用于展示的文本
+    pub fn label(&self) -> &'static str {
+        match self {
+            StatusPalette::Standard => "标准",
+            StatusPalette::HighContrast => "高对比度",
+            StatusPalette::ColorBlind => "色盲友好",
+        }
+    }
+
+    /// 配色的说明
+    pub fn description(&self) -> &'static str {
+        match self {
+            StatusPalette::Standard => "过期为红色，临期为黄色，新鲜为绿色",
+            StatusPalette::HighContrast => "颜色更深、边框更粗，强光下也容易分辨",
+            StatusPalette::ColorBlind => "过期为橙色，临期为黄色，新鲜为蓝色，不依赖红绿区分",
+        }
+    }
+}
+
+/// 外观设置
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub struct Appearance {
+    pub theme: Theme,
+    pub palette: StatusPalette,
+}
+
+impl Appearance {
+    /// 浏览器地址栏等系统界面使用的主题色，manifest.json 不再固定主题色，以页面上的 theme-color 为准
+    pub fn theme_color(dark: bool) -> &'static str {
+        if dark {
+            "#111827"
+        } else {
+            "#2563eb"
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use crate::crypto::DerivedKey;
+use chrono::{Duration, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+// 默认保留的远程快照份数
+const DEFAULT_KEEP: u32 = 7;
+
+/// 自动备份频率
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum BackupSchedule {
+    /// 仅手动备份
+    #[default]
+    Manual,
+    Daily,
+    Weekly,
+}
+
+impl BackupSchedule {
+    /// 所有可选频率，用于设置页
+    pub const ALL: [BackupSchedule; 3] = [BackupSchedule::Manual, BackupSchedule::Daily, BackupSchedule::Weekly];
+
+    /// 用于展示的文本
+    pub fn label(&self) -> &'static str {
+        match self {
+            BackupSchedule::Manual => "仅手动",
+            BackupSchedule::Daily => "每天",
+            BackupSchedule::Weekly => "每周",
+        }
+    }
+
+    /// 两次自动备份之间的间隔，手动模式返回 None
+    pub fn interval(&self) -> Option<Duration> {
+        match self {
+            BackupSchedule::Manual => None,
+            BackupSchedule::Daily => Some(Duration::days(1)),
+            BackupSchedule::Weekly => Some(Duration::weeks(1)),
+        }
+    }
+}
+
+fn default_keep() -> u32 {
+    DEFAULT_KEEP
+}
+
+/// WebDAV 备份设置（例如 Nextcloud）
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct WebDavSettings {
+    /// 备份目录地址
+    pub url: String,
+    pub username: String,
+    pub password: String,
+    /// 保留最近多少份快照
+    #[serde(default = "default_keep")]
+    pub keep: u32,
+    #[serde(default)]
+    pub schedule: BackupSchedule,
+    /// 备份口令派生的密钥，设置后上传的备份为加密的 .ukeep 文件
+    #[serde(default)]
+    pub encryption: Option<DerivedKey>,
+    /// 最近一次成功备份的时间
+    #[serde(default)]
+    pub last_backup: Option<NaiveDateTime>,
+}
+
+impl Default for WebDavSettings {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            username: String::new(),
+            password: String::new(),
+            keep: DEFAULT_KEEP,
+            schedule: BackupSchedule::Manual,
+            encryption: None,
+            last_backup: None,
+        }
+    }
+}
+
+impl WebDavSettings {
+    /// 是否已填写备份地址
+    pub fn is_configured(&self) -> bool {
+        !self.url.trim().is_empty()
+    }
+
+    /// 按备份频率判断现在是否需要自动备份
+    pub fn is_due(&self, now: NaiveDateTime) -> bool {
+        match (self.is_configured(), self.schedule.interval(), self.last_backup) {
+            (true, Some(interval), Some(last)) => now - last >= interval,
+            (true, Some(_), None) => true,
+            _ => false,
+        }
+    }
+}
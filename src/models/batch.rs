@@ -0,0 +1,59 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// 同一物品的一批：各自有数量和过期日期，例如先后买的两盒鸡蛋
+/// 数量使用所属物品的单位
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Batch {
+    amount: f64,
+    expiry_date: NaiveDate,
+    /// 旧数据没有入库日期，加载时由所属物品补上物品的入库日期
+    #[serde(default)]
+    added_on: NaiveDate,
+}
+
+impl Batch {
+    /// 创建一批，`added_on` 是入库日期，通常取 `Clock::today`
+    pub fn new(amount: f64, expiry_date: NaiveDate, added_on: NaiveDate) -> Self {
+        Self {
+            amount,
+            expiry_date,
+            added_on,
+        }
+    }
+
+    /// 获取数量（所属物品的单位）
+    pub fn amount(&self) -> f64 {
+        self.amount
+    }
+
+    /// 获取过期日期
+    pub fn expiry_date(&self) -> NaiveDate {
+        self.expiry_date
+    }
+
+    /// 获取入库日期
+    pub fn added_on(&self) -> NaiveDate {
+        self.added_on
+    }
+
+    /// 计算剩余天数：负数表示已过期
+    pub fn days_remaining(&self, today: NaiveDate) -> i64 {
+        (self.expiry_date - today).num_days()
+    }
+
+    pub(super) fn set_added_on(&mut self, added_on: NaiveDate) {
+        self.added_on = added_on;
+    }
+
+    pub(super) fn set_expiry_date(&mut self, expiry_date: NaiveDate) {
+        self.expiry_date = expiry_date;
+    }
+
+    // 从这一批中取出至多 `amount`，返回实际取出的数量
+    pub(super) fn take(&mut self, amount: f64) -> f64 {
+        let taken = amount.min(self.amount);
+        self.amount -= taken;
+        taken
+    }
+}
@@ -0,0 +1,115 @@
+use super::{EventKind, HistoryEvent, Item, Location, Member};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// 多选后对所有选中物品执行的操作
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BulkAction {
+    /// 全部吃掉
+    Consume,
+    /// 全部扔掉
+    Waste,
+    /// 移到另一个存放位置
+    Move(Location),
+    /// 过期日期提前（负数）或推后若干天
+    ShiftExpiry(i64),
+    /// 直接删除，例如录错的物品；会记一条“删除了”，但不计入吃掉/扔掉的金额
+    Delete,
+}
+
+impl BulkAction {
+    /// 用于提示的动作文本，例如“移到冷冻”
+    pub fn label(&self) -> String {
+        match self {
+            BulkAction::Consume => "吃掉".to_string(),
+            BulkAction::Waste => "扔掉".to_string(),
+            BulkAction::Move(location) => format!("移到{}", location.label()),
+            BulkAction::ShiftExpiry(days) if *days < 0 => format!("过期日期提前 {} 天", days.abs()),
+            BulkAction::ShiftExpiry(days) => format!("过期日期推后 {} 天", days),
+            BulkAction::Delete => "删除".to_string(),
+        }
+    }
+}
+
+/// 一次批量操作：保存受影响物品的原样和产生的历史记录，用于整体撤销
+#[derive(Clone, PartialEq, Debug)]
+pub struct BulkOperation {
+    action: BulkAction,
+    before: Vec<Item>,
+    events: Vec<HistoryEvent>,
+}
+
+impl BulkOperation {
+    /// 对 `ids` 中仍在库存里的物品执行操作，返回的记录需要追加到历史中
+    pub fn apply(action: BulkAction, ids: &[Uuid], items: &mut Vec<Item>, member: Option<Member>) -> Self {
+        let ids: HashSet<Uuid> = ids.iter().copied().collect();
+        let before: Vec<Item> = items.iter().filter(|i| ids.contains(&i.id())).cloned().collect();
+        let mut events = Vec::new();
+        match action {
+            BulkAction::Consume | BulkAction::Waste => {
+                let kind = if action == BulkAction::Consume { EventKind::Consumed } else { EventKind::Wasted };
+                for item in &before {
+                    events.push(
+                        HistoryEvent::new(item, kind, item.quantity(), true, member.clone()).with_value(item.value()),
+                    );
+                }
+                items.retain(|i| !ids.contains(&i.id()));
+            }
+            BulkAction::Delete => {
+                // 同步合并时靠这条记录判断物品已被移除，否则另一台设备会把它带回来
+                for item in &before {
+                    events.push(HistoryEvent::new(item, EventKind::Deleted, item.quantity(), true, member.clone()));
+                }
+                items.retain(|i| !ids.contains(&i.id()));
+            }
+            BulkAction::Move(location) => {
+                for item in items.iter_mut().filter(|i| ids.contains(&i.id())) {
+                    item.set_location(location);
+                }
+            }
+            BulkAction::ShiftExpiry(days) => {
+                for item in items.iter_mut().filter(|i| ids.contains(&i.id())) {
+                    item.shift_expiry(days);
+                }
+            }
+        }
+        Self { action, before, events }
+    }
+
+    /// 获取执行的操作
+    pub fn action(&self) -> BulkAction {
+        self.action
+    }
+
+    /// 受影响的物品个数
+    pub fn count(&self) -> usize {
+        self.before.len()
+    }
+
+    /// 获取这次操作产生的历史记录
+    pub fn events(&self) -> &[HistoryEvent] {
+        &self.events
+    }
+
+    /// 用于提示的一句话描述，例如“已批量吃掉 5 项”
+    pub fn describe(&self) -> String {
+        format!("已批量{} {} 项", self.action.label(), self.count())
+    }
+
+    /// 撤销：把受影响的物品恢复原样（已移除的重新放回），并为这次产生的每条记录补记一条抵消记录；
+    /// 已有的历史记录只增不删，其他设备同步后才能看到物品被放回
+    pub fn undo(&self, items: &mut Vec<Item>, history: &mut Vec<HistoryEvent>, member: Option<Member>) {
+        for item in &self.before {
+            match items.iter_mut().find(|i| i.id() == item.id()) {
+                Some(existing) => *existing = item.clone(),
+                None => items.push(item.clone()),
+            }
+        }
+        for event in &self.events {
+            let Some(item) = self.before.iter().find(|i| i.id() == event.item_id()) else {
+                continue;
+            };
+            history.push(HistoryEvent::new(item, EventKind::Restored, event.count(), false, member.clone()).reverting(event));
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use super::{Item, Location};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use uuid::Uuid;
+
+// 用户自定义分类的标识前缀
+const CUSTOM_PREFIX: &str = "custom-";
+
+/// 各存放位置的默认保质期（天），None 表示不建议这样存放
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub struct ShelfLife {
+    pub fridge: Option<i64>,
+    pub freezer: Option<i64>,
+    pub pantry: Option<i64>,
+}
+
+impl ShelfLife {
+    /// 指定存放位置的保质期
+    pub fn for_location(&self, location: Location) -> Option<i64> {
+        match location {
+            Location::Fridge => self.fridge,
+            Location::Freezer => self.freezer,
+            Location::Pantry => self.pantry,
+        }
+    }
+}
+
+/// 临期提醒阈值：剩余天数不超过 `warning_days` 显示黄色，不超过 `critical_days` 显示红色
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Thresholds {
+    pub warning_days: i64,
+    pub critical_days: i64,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Self {
+            warning_days: 3,
+            critical_days: 1,
+        }
+    }
+}
+
+/// 物品分类，物品上只保存分类的 `id`
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Category {
+    id: String,
+    pub name: String,
+    /// 名称里没有表情时使用的默认表情
+    pub emoji: String,
+    /// 用于从物品名称自动识别分类的关键词
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    #[serde(default)]
+    pub shelf_life: ShelfLife,
+    #[serde(default)]
+    pub thresholds: Thresholds,
+}
+
+// 内置分类：标识、名称、表情、冷藏/冷冻/常温保质期、黄色/红色阈值、关键词
+type BuiltinRow = (&'static str, &'static str, &'static str, [Option<i64>; 3], (i64, i64), &'static [&'static str]);
+
+const BUILTIN: [BuiltinRow; 10] = [
+    ("dairy", "乳制品", "🥛", [Some(7), Some(30), None], (3, 1), &["奶", "酸奶", "奶酪", "芝士", "黄油", "milk", "yogurt", "cheese", "butter"]),
+    ("eggs", "蛋类", "🥚", [Some(30), None, Some(14)], (5, 2), &["蛋", "鸡蛋", "鸭蛋", "egg"]),
+    ("meat", "肉类", "🥩", [Some(3), Some(90), None], (2, 1), &["肉", "排骨", "鸡", "猪", "牛排", "羊", "培根", "香肠", "火腿", "meat", "beef", "pork", "chicken", "bacon", "sausage"]),
+    ("seafood", "水产", "🐟", [Some(2), Some(90), None], (2, 1), &["鱼", "虾", "蟹", "贝", "海鲜", "三文鱼", "fish", "shrimp", "salmon"]),
+    ("produce", "果蔬", "🥬", [Some(5), Some(180), Some(7)], (2, 1), &["菜", "果", "瓜", "葱", "姜", "蒜", "番茄", "西红柿", "土豆", "香蕉", "橙", "梨", "莓", "葡萄", "柠檬", "蘑菇", "vegetable", "fruit", "apple", "banana"]),
+    ("bakery", "烘焙", "🍞", [Some(5), Some(60), Some(3)], (2, 1), &["面包", "吐司", "蛋糕", "馒头", "包子", "bread", "toast", "cake"]),
+    ("drinks", "饮品", "🥤", [Some(7), None, Some(180)], (7, 3), &["饮料", "果汁", "可乐", "啤酒", "茶", "咖啡", "juice", "cola", "beer", "coffee", "tea"]),
+    ("condiments", "调味品", "🧂", [Some(90), None, Some(365)], (14, 7), &["酱", "醋", "盐", "糖", "油", "调料", "sauce", "salt", "sugar", "oil"]),
+    ("snacks", "零食", "🍪", [None, None, Some(90)], (14, 3), &["零食", "饼干", "薯片", "巧克力", "坚果", "snack", "cookie", "chips", "chocolate"]),
+    ("other", "其他", "📦", [Some(7), Some(30), Some(30)], (3, 1), &[]),
+];
+
+impl Category {
+    /// 所有内置分类
+    pub fn builtin() -> Vec<Category> {
+        BUILTIN
+            .iter()
+            .map(|(id, name, emoji, [fridge, freezer, pantry], (warning, critical), keywords)| Category {
+                id: id.to_string(),
+                name: name.to_string(),
+                emoji: emoji.to_string(),
+                keywords: keywords.iter().map(|k| k.to_string()).collect(),
+                shelf_life: ShelfLife {
+                    fridge: *fridge,
+                    freezer: *freezer,
+                    pantry: *pantry,
+                },
+                thresholds: Thresholds {
+                    warning_days: *warning,
+                    critical_days: *critical,
+                },
+            })
+            .collect()
+    }
+
+    /// 内置分类加上用户自定义分类
+    pub fn all(custom: &[Category]) -> Vec<Category> {
+        let mut all = Self::builtin();
+        all.extend(custom.iter().cloned());
+        all
+    }
+
+    /// 创建用户自定义分类
+    pub fn custom(name: String, emoji: String) -> Self {
+        Self {
+            id: format!("{}{}", CUSTOM_PREFIX, Uuid::new_v4()),
+            name,
+            emoji,
+            keywords: Vec::new(),
+            shelf_life: ShelfLife::default(),
+            thresholds: Thresholds::default(),
+        }
+    }
+
+    /// 获取标识
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// 获取名称
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// 获取默认表情
+    pub fn emoji(&self) -> &str {
+        &self.emoji
+    }
+
+    /// 是否为内置分类（内置分类不可编辑）
+    pub fn is_builtin(&self) -> bool {
+        !self.id.starts_with(CUSTOM_PREFIX)
+    }
+
+    // 名称中命中的最长关键词长度，没有命中时返回 None
+    fn match_len(&self, name: &str) -> Option<usize> {
+        let name = name.to_lowercase();
+        self.keywords
+            .iter()
+            .filter(|k| !k.is_empty() && name.contains(&k.to_lowercase()))
+            .map(|k| k.chars().count())
+            .max()
+    }
+}
+
+/// 按标识查找分类
+pub fn find_category<'a>(categories: &'a [Category], id: &str) -> Option<&'a Category> {
+    categories.iter().find(|c| c.id == id)
+}
+
+/// 物品所属的分类
+pub fn category_of<'a>(categories: &'a [Category], item: &Item) -> Option<&'a Category> {
+    item.category().and_then(|id| find_category(categories, id))
+}
+
+/// 物品适用的临期阈值，没有分类时使用默认值
+pub fn thresholds_of(categories: &[Category], item: &Item) -> Thresholds {
+    category_of(categories, item).map(|c| c.thresholds).unwrap_or_default()
+}
+
+/// 根据名称猜测分类：命中关键词最长的优先，同样长时自定义分类优先
+pub fn guess_category<'a>(categories: &'a [Category], name: &str) -> Option<&'a Category> {
+    categories
+        .iter()
+        .rev()
+        .filter_map(|c| c.match_len(name).map(|len| (len, c)))
+        // 自定义分类排在后面，倒过来遍历后一样长时保留先遇到的那个
+        .max_by(|a, b| a.0.cmp(&b.0).then(Ordering::Greater))
+        .map(|(_, c)| c)
+}
@@ -0,0 +1,170 @@
+use super::{Location, ShelfLife};
+use chrono::{Duration, NaiveDate};
+use serde::Deserialize;
+use std::sync::OnceLock;
+
+// 随应用打包的常见食物保质期数据，修改内容时需要递增 version
+const BUNDLED_FOODS: &str = include_str!("foods.json");
+
+/// 常见食物保质期知识库中的一条记录
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize)]
+pub struct FoodEntry {
+    id: String,
+    zh: String,
+    en: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+    emoji: String,
+    /// 对应的内置分类标识
+    category: String,
+    unopened: ShelfLife,
+    /// 开封后的保质期，缺省时与未开封相同
+    #[serde(default)]
+    opened: Option<ShelfLife>,
+}
+
+impl FoodEntry {
+    /// 获取标识
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// 获取中文名称
+    pub fn name_zh(&self) -> &str {
+        &self.zh
+    }
+
+    /// 获取英文名称
+    pub fn name_en(&self) -> &str {
+        &self.en
+    }
+
+    /// 获取别名
+    pub fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+
+    /// 获取表情符号
+    pub fn emoji(&self) -> &str {
+        &self.emoji
+    }
+
+    /// 获取对应的分类标识
+    pub fn category(&self) -> &str {
+        &self.category
+    }
+
+    /// 未开封或开封后的保质期
+    pub fn shelf_life(&self, opened: bool) -> &ShelfLife {
+        match &self.opened {
+            Some(shelf_life) if opened => shelf_life,
+            _ => &self.unopened,
+        }
+    }
+
+    /// 指定存放位置的建议保质期（天），不建议这样存放时返回 None
+    pub fn days(&self, location: Location, opened: bool) -> Option<i64> {
+        self.shelf_life(opened).for_location(location)
+    }
+
+    /// 从生产（或开封）日期推算建议的过期日期
+    pub fn expiry_from(&self, production: NaiveDate, location: Location, opened: bool) -> Option<NaiveDate> {
+        self.days(location, opened).map(|days| production + Duration::days(days))
+    }
+
+    // 中英文名称和所有别名
+    fn terms(&self) -> impl Iterator<Item = &str> {
+        [self.zh.as_str(), self.en.as_str()]
+            .into_iter()
+            .chain(self.aliases.iter().map(String::as_str))
+    }
+
+    // 与名称的匹配程度：完全相同最高，其次按命中词的长度，没有命中时返回 None
+    fn score(&self, name: &str) -> Option<usize> {
+        self.terms()
+            .map(normalize)
+            .filter(|term| !term.is_empty())
+            .filter_map(|term| {
+                if term == name {
+                    Some(usize::MAX)
+                } else if contains_term(name, &term) {
+                    Some(term.chars().count())
+                } else {
+                    None
+                }
+            })
+            .max()
+    }
+}
+
+/// 常见食物保质期知识库
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize)]
+pub struct FoodDatabase {
+    version: u32,
+    foods: Vec<FoodEntry>,
+}
+
+impl FoodDatabase {
+    /// 随应用打包的数据
+    pub fn bundled() -> &'static FoodDatabase {
+        static DATABASE: OnceLock<FoodDatabase> = OnceLock::new();
+        DATABASE.get_or_init(|| Self::from_json(BUNDLED_FOODS).expect("bundled food data is valid"))
+    }
+
+    /// 从 JSON 解析
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|e| format!("食物数据格式错误: {}", e))
+    }
+
+    /// 数据版本
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// 所有记录
+    pub fn foods(&self) -> &[FoodEntry] {
+        &self.foods
+    }
+
+    /// 按标识查找
+    pub fn get(&self, id: &str) -> Option<&FoodEntry> {
+        self.foods.iter().find(|f| f.id == id)
+    }
+
+    /// 按物品名称查找最匹配的记录：名称或别名完全相同的优先，否则取名称中命中的最长词，
+    /// 例如“蒙牛纯牛奶”匹配“牛奶”，“番茄酱”匹配“番茄酱”而不是“番茄”
+    pub fn lookup(&self, name: &str) -> Option<&FoodEntry> {
+        let name = normalize(name);
+        if name.is_empty() {
+            return None;
+        }
+        self.foods
+            .iter()
+            .rev()
+            .filter_map(|f| f.score(&name).map(|score| (score, f)))
+            .max_by_key(|(score, _)| *score)
+            .map(|(_, f)| f)
+    }
+}
+
+// 去掉开头的表情和首尾空白，统一为小写
+fn normalize(name: &str) -> String {
+    name.trim()
+        .trim_start_matches(|c: char| !c.is_alphanumeric())
+        .trim()
+        .to_lowercase()
+}
+
+// 中文按子串匹配；英文按整词匹配，避免 "egg" 命中 "eggplant"
+pub(super) fn contains_term(name: &str, term: &str) -> bool {
+    if !term.is_ascii() {
+        return name.contains(term);
+    }
+    let (name_words, term_words) = (ascii_words(name), ascii_words(term));
+    !term_words.is_empty() && name_words.windows(term_words.len()).any(|w| w == term_words.as_slice())
+}
+
+// 按非字母数字拆分出的英文单词
+fn ascii_words(s: &str) -> Vec<&str> {
+    s.split(|c: char| !c.is_ascii_alphanumeric()).filter(|w| !w.is_empty()).collect()
+}
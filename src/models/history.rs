@@ -0,0 +1,245 @@
+use super::{Item, Member, Price, Quantity};
+use chrono::{Local, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// 库存变动的类型
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum EventKind {
+    /// 新物品入库
+    Added,
+    /// 吃掉了
+    Consumed,
+    /// 扔掉了
+    Wasted,
+    /// 直接删除，例如录错的物品
+    Deleted,
+    /// 撤销操作后放回库存
+    Restored,
+}
+
+impl EventKind {
+    /// 用于展示的动作文本
+    pub fn label(&self) -> &'static str {
+        match self {
+            EventKind::Added => "添加了",
+            EventKind::Consumed => "吃掉了",
+            EventKind::Wasted => "扔掉了",
+            EventKind::Deleted => "删除了",
+            EventKind::Restored => "放回了",
+        }
+    }
+}
+
+/// 扔掉的原因
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum WasteReason {
+    /// 放到过期了
+    Expired,
+    /// 没到日期就坏了
+    SpoiledEarly,
+    /// 忘了吃
+    Forgotten,
+    /// 不喜欢吃
+    Disliked,
+    /// 买多了
+    BoughtTooMuch,
+    /// 其他原因，具体见备注
+    Other,
+}
+
+impl WasteReason {
+    /// 所有原因，用于选择
+    pub const ALL: [WasteReason; 6] = [
+        WasteReason::Expired,
+        WasteReason::SpoiledEarly,
+        WasteReason::Forgotten,
+        WasteReason::Disliked,
+        WasteReason::BoughtTooMuch,
+        WasteReason::Other,
+    ];
+
+    /// 用于展示的文本
+    pub fn label(&self) -> &'static str {
+        match self {
+            WasteReason::Expired => "过期了",
+            WasteReason::SpoiledEarly => "提前坏了",
+            WasteReason::Forgotten => "忘了吃",
+            WasteReason::Disliked => "不喜欢",
+            WasteReason::BoughtTooMuch => "买多了",
+            WasteReason::Other => "其他",
+        }
+    }
+}
+
+/// 一条库存变动记录，用于追溯“谁在什么时候做了什么”
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct HistoryEvent {
+    id: Uuid,
+    item_id: Uuid,
+    item_name: String,
+    emoji: String,
+    kind: EventKind,
+    /// 变动数量，旧数据中的整数会按“个”迁移
+    count: Quantity,
+    /// 该操作后物品是否已从库存中移除
+    #[serde(default)]
+    removed: bool,
+    /// 操作的家庭成员，未加入家庭时为 None
+    #[serde(default)]
+    member: Option<Member>,
+    at: NaiveDateTime,
+    /// 吃掉或扔掉部分的价值，物品没有价格时为 None
+    #[serde(default)]
+    value: Option<Price>,
+    /// 扔掉的原因，只有扔掉的记录才可能有
+    #[serde(default)]
+    reason: Option<WasteReason>,
+    /// 用户填写的备注
+    #[serde(default)]
+    note: Option<String>,
+    /// 撤销时补记的记录所抵消的那条记录
+    #[serde(default)]
+    reverts: Option<Uuid>,
+}
+
+impl HistoryEvent {
+    /// 根据物品当前状态创建一条记录
+    pub fn new(item: &Item, kind: EventKind, count: Quantity, removed: bool, member: Option<Member>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            item_id: item.id(),
+            item_name: item.name().to_string(),
+            emoji: item.emoji().to_string(),
+            kind,
+            count,
+            removed,
+            member,
+            at: Local::now().naive_local(),
+            value: None,
+            reason: None,
+            note: None,
+            reverts: None,
+        }
+    }
+
+    /// 设置这次变动涉及的价值
+    pub fn with_value(mut self, value: Option<Price>) -> Self {
+        self.value = value;
+        self
+    }
+
+    /// 设置扔掉的原因和备注，空白备注视为没有
+    pub fn with_reason(mut self, reason: Option<WasteReason>, note: Option<String>) -> Self {
+        self.reason = reason;
+        self.note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+        self
+    }
+
+    /// 标记为抵消 `event` 的补记记录，例如撤销批量吃掉时的“放回了”
+    pub fn reverting(mut self, event: &HistoryEvent) -> Self {
+        self.reverts = Some(event.id);
+        self
+    }
+
+    /// 获取 ID
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// 获取关联物品的 ID
+    pub fn item_id(&self) -> Uuid {
+        self.item_id
+    }
+
+    /// 获取物品名称（记录时的快照）
+    pub fn item_name(&self) -> &str {
+        &self.item_name
+    }
+
+    /// 获取表情符号
+    pub fn emoji(&self) -> &str {
+        &self.emoji
+    }
+
+    /// 获取变动类型
+    pub fn kind(&self) -> EventKind {
+        self.kind
+    }
+
+    /// 获取变动数量
+    pub fn count(&self) -> Quantity {
+        self.count
+    }
+
+    /// 该操作是否把物品从库存中移除
+    pub fn removed(&self) -> bool {
+        self.removed
+    }
+
+    /// 获取操作成员
+    pub fn member(&self) -> Option<&Member> {
+        self.member.as_ref()
+    }
+
+    /// 获取发生时间
+    pub fn at(&self) -> NaiveDateTime {
+        self.at
+    }
+
+    /// 获取这次变动涉及的价值
+    pub fn value(&self) -> Option<Price> {
+        self.value
+    }
+
+    /// 获取扔掉的原因
+    pub fn reason(&self) -> Option<WasteReason> {
+        self.reason
+    }
+
+    /// 获取备注
+    pub fn note(&self) -> Option<&str> {
+        self.note.as_deref()
+    }
+
+    /// 被这条记录抵消的记录 ID
+    pub fn reverts(&self) -> Option<Uuid> {
+        self.reverts
+    }
+
+    /// 用于展示的一句话描述，例如“小明 吃掉了 🥛牛奶 x2”、“我 扔掉了 🍞面包 x1（过期了）”
+    pub fn describe(&self) -> String {
+        let who = self.member.as_ref().map(|m| m.name()).unwrap_or("我");
+        let mut text = format!("{} {} {}{} {}", who, self.kind.label(), self.emoji, self.item_name, self.count);
+        let detail: Vec<&str> = self.reason.map(|r| r.label()).into_iter().chain(self.note()).collect();
+        if !detail.is_empty() {
+            text.push_str(&format!("（{}）", detail.join(" · ")));
+        }
+        text
+    }
+}
+
+// 推断常用分量时最多参考的最近几次吃掉记录
+const USUAL_AMOUNT_WINDOW: usize = 10;
+
+/// 根据最近的吃掉记录推断这个物品平时一次吃多少（按物品当前的单位），
+/// 同名同单位、没有吃完的记录中出现至少两次的数量才算，次数相同时取最近的一次
+pub fn usual_amount(item: &Item, history: &[HistoryEvent]) -> Option<f64> {
+    let unit = item.quantity().unit();
+    let recent: Vec<f64> = history
+        .iter()
+        .rev()
+        .filter(|e| e.kind == EventKind::Consumed && !e.removed && e.item_name == item.name() && e.count.unit() == unit)
+        .map(|e| e.count.amount())
+        .take(USUAL_AMOUNT_WINDOW)
+        .collect();
+    let mut best: Option<(f64, usize)> = None;
+    // recent 从新到旧排列，只有严格更多的次数才替换，保证平局时取最近的
+    for &amount in &recent {
+        let times = recent.iter().filter(|&&a| (a - amount).abs() < 1e-6).count();
+        if times >= 2 && best.is_none_or(|(_, most)| times > most) {
+            best = Some((amount, times));
+        }
+    }
+    best.map(|(amount, _)| amount)
+}
@@ -0,0 +1,110 @@
+use crate::crypto::DerivedKey;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// 邀请码字符集：去掉了容易混淆的 0/O、1/I/L
+const INVITE_ALPHABET: &[u8] = b"ABCDEFGHJKMNPQRSTUVWXYZ23456789";
+// 邀请码长度
+pub const INVITE_CODE_LEN: usize = 6;
+
+/// 家庭成员
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Member {
+    id: Uuid,
+    name: String,
+}
+
+impl Member {
+    /// 创建新成员
+    pub fn new(name: String) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name: name.trim().to_string(),
+        }
+    }
+
+    /// 获取 ID
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// 获取昵称
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// 一个家庭：多个成员共享同一份库存
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Household {
+    id: Uuid,
+    name: String,
+    invite_code: String,
+    members: Vec<Member>,
+}
+
+impl Household {
+    /// 获取 ID
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// 获取家庭名称
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// 获取邀请码
+    pub fn invite_code(&self) -> &str {
+        &self.invite_code
+    }
+
+    /// 获取成员列表
+    pub fn members(&self) -> &[Member] {
+        &self.members
+    }
+
+    /// 按 ID 查找成员
+    pub fn member(&self, id: Uuid) -> Option<&Member> {
+        self.members.iter().find(|m| m.id == id)
+    }
+}
+
+/// 当前设备加入的家庭会话，持久化在本地
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct HouseholdSession {
+    /// 同步服务器地址，例如 https://sync.example.com
+    pub server_url: String,
+    pub household: Household,
+    /// 本设备对应的成员身份
+    pub member: Member,
+    /// 最近一次同步到的服务器版本号
+    #[serde(default)]
+    pub revision: u64,
+    /// 由家庭口令派生的端到端加密密钥，只保存在本机
+    #[serde(default)]
+    pub encryption: Option<DerivedKey>,
+}
+
+impl HouseholdSession {
+    /// 生成扫码加入用的链接，例如 https://ukeep.app/join/AB3D5F
+    pub fn join_url(&self, origin: &str) -> String {
+        format!("{}/join/{}", origin.trim_end_matches('/'), self.household.invite_code)
+    }
+
+    /// 由家庭口令派生同步密钥；以家庭 ID 作为盐，所有成员输入相同口令即可得到相同密钥
+    pub fn derive_key(&self, passphrase: &str) -> Result<DerivedKey, String> {
+        DerivedKey::derive(passphrase, *self.household.id.as_bytes()).map_err(|e| e.to_string())
+    }
+}
+
+/// 规范化用户输入的邀请码：去掉空格和连字符并转为大写，不合法时返回 None
+pub fn normalize_invite_code(input: &str) -> Option<String> {
+    let code: String = input
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    let valid = code.len() == INVITE_CODE_LEN && code.bytes().all(|b| INVITE_ALPHABET.contains(&b));
+    valid.then_some(code)
+}
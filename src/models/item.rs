@@ -1,45 +1,143 @@
-use chrono::Local;
+use super::{Batch, ItemStatus, Location, Price, Quantity, Thresholds, Unit};
+use chrono::Duration;
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-fn default_quantity() -> u32 {
-    1
+fn default_quantity() -> Quantity {
+    Quantity::pieces(1.0)
 }
 
+// 剩余数量小于该值时视为用完
+const EPSILON: f64 = 1e-6;
+
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(from = "ItemRepr")]
 pub struct Item {
     id: Uuid,
     name: String,
     emoji: String,
-    expiry_date: NaiveDate,
+    /// 所有批次共用的单位
+    unit: Unit,
+    /// 按过期日期从早到晚排列，至少有一批
+    batches: Vec<Batch>,
+    location: Location,
+    /// 分类标识，见 `Category`
+    category: Option<String>,
+    added_on: NaiveDate,
+    /// 每单位（按 `unit`）的价格，未填写时为 None
+    unit_price: Option<Price>,
+    /// 开封日期，未开封时为 None
+    opened_on: Option<NaiveDate>,
+}
+
+// 反序列化用的中间结构：旧数据只有一个 expiry_date 和 quantity，迁移为单个批次
+#[derive(Deserialize)]
+struct ItemRepr {
+    id: Uuid,
+    name: String,
+    emoji: String,
+    #[serde(default)]
+    unit: Option<Unit>,
+    #[serde(default)]
+    batches: Vec<Batch>,
+    #[serde(default)]
+    expiry_date: Option<NaiveDate>,
+    /// 旧数据中的整数数量会按“个”迁移
     #[serde(default = "default_quantity")]
-    quantity: u32,
+    quantity: Quantity,
+    #[serde(default)]
+    location: Location,
+    #[serde(default)]
+    category: Option<String>,
+    /// 旧数据没有入库日期，先记为 `NaiveDate::default()`，加载后由 `settle_added_on` 按当天补上
+    #[serde(default)]
+    added_on: NaiveDate,
+    #[serde(default)]
+    unit_price: Option<Price>,
+    #[serde(default)]
+    opened_on: Option<NaiveDate>,
+}
+
+impl From<ItemRepr> for Item {
+    fn from(repr: ItemRepr) -> Self {
+        let mut batches = repr.batches;
+        if batches.is_empty() {
+            let expiry = repr.expiry_date.unwrap_or(repr.added_on);
+            batches.push(Batch::new(repr.quantity.amount(), expiry, repr.added_on));
+        }
+        for batch in batches.iter_mut().filter(|b| b.added_on() == NaiveDate::default()) {
+            batch.set_added_on(repr.added_on);
+        }
+        batches.sort_by_key(|b| b.expiry_date());
+        Self {
+            id: repr.id,
+            name: repr.name,
+            emoji: repr.emoji,
+            unit: repr.unit.unwrap_or(repr.quantity.unit()),
+            batches,
+            location: repr.location,
+            category: repr.category,
+            added_on: repr.added_on,
+            unit_price: repr.unit_price,
+            opened_on: repr.opened_on,
+        }
+    }
 }
 
 impl Item {
-    /// 创建新的 Item 实例
-    pub fn new(name: String, expiry_date: NaiveDate) -> Self {
-        Self::new_with_quantity(name, expiry_date, 1)
+    /// 创建新的 Item 实例，`today` 是入库日期，通常取 `Clock::today`
+    pub fn new(name: String, expiry_date: NaiveDate, today: NaiveDate) -> Self {
+        Self::new_with_quantity(name, expiry_date, 1, today)
     }
 
-    /// 创建带数量的 Item 实例
+    /// 创建带数量的 Item 实例（单位为“个”）
     pub fn new_with_quantity(
         name: String,
         expiry_date: NaiveDate,
         quantity: u32,
+        today: NaiveDate,
     ) -> Self {
+        Self::new_with_amount(name, expiry_date, Quantity::pieces(quantity.max(1) as f64), today)
+    }
+
+    /// 创建带单位数量的 Item 实例，例如 1.5 L 牛奶、500 g 面粉
+    pub fn new_with_amount(name: String, expiry_date: NaiveDate, quantity: Quantity, today: NaiveDate) -> Self {
         let mut name = name;
         let emoji: String = Item::extract_emoji(&mut name);
         Self {
             id: Uuid::new_v4(),
             name,
             emoji,
-            expiry_date,
-            quantity: quantity.max(1),
+            unit: quantity.unit(),
+            batches: vec![Batch::new(quantity.amount(), expiry_date, today)],
+            location: Location::default(),
+            category: None,
+            added_on: today,
+            unit_price: None,
+            opened_on: None,
         }
     }
 
+    /// 设置存放位置
+    pub fn with_location(mut self, location: Location) -> Self {
+        self.location = location;
+        self
+    }
+
+    /// 设置分类
+    pub fn with_category(mut self, category: Option<String>) -> Self {
+        self.category = category;
+        self
+    }
+
+    /// 设置购买时的总价，按当前数量折算为单价
+    pub fn with_total_price(mut self, price: Option<Price>) -> Self {
+        let total = self.quantity().amount();
+        self.unit_price = price.filter(|_| total > EPSILON).map(|p| p.times(1.0 / total));
+        self
+    }
+
     /// 获取 ID
     pub fn id(&self) -> Uuid {
         self.id
@@ -55,51 +153,204 @@ impl Item {
         &self.emoji
     }
 
-    /// 获取过期日期
+    /// 获取最近的过期日期（最早过期的一批）
     pub fn expiry_date(&self) -> NaiveDate {
-        self.expiry_date
+        self.batches.first().map(|b| b.expiry_date()).unwrap_or(self.added_on)
     }
 
-    /// 获取数量
-    pub fn quantity(&self) -> u32 {
-        self.quantity
+    /// 获取所有批次的总数量
+    pub fn quantity(&self) -> Quantity {
+        Quantity::new(self.batches.iter().map(|b| b.amount()).sum(), self.unit)
     }
 
-    pub fn consume_n(&mut self, count: u32) -> bool {
-        let count = count.max(1);
-        if count >= self.quantity {
-            true
-        } else {
-            self.quantity -= count;
-            false
+    /// 获取所有批次，按过期日期从早到晚排列
+    pub fn batches(&self) -> &[Batch] {
+        &self.batches
+    }
+
+    /// 获取单价（按物品自身的单位）
+    pub fn unit_price(&self) -> Option<Price> {
+        self.unit_price
+    }
+
+    /// 指定数量（按物品自身的单位）的价值，没有价格时返回 None
+    pub fn value_of(&self, amount: f64) -> Option<Price> {
+        self.unit_price.map(|p| p.times(amount))
+    }
+
+    /// 剩余部分的价值
+    pub fn value(&self) -> Option<Price> {
+        self.value_of(self.quantity().amount())
+    }
+
+    /// 是否与另一个物品是同一种东西：名称、表情相同，单位可换算，且放在同一个位置、属于同一分类；
+    /// 位置或分类不同时（例如一盒冷藏、一盒冷冻）分开记录，不会丢掉录入时的选择
+    pub fn is_same_product(&self, other: &Item) -> bool {
+        self.emoji == other.emoji
+            && self.name.trim() == other.name.trim()
+            && self.unit.is_compatible(other.unit)
+            && self.location == other.location
+            && self.category == other.category
+    }
+
+    /// 新增一批，数量会换算为本物品的单位；单位无法换算时返回 false
+    pub fn add_batch(&mut self, quantity: Quantity, expiry_date: NaiveDate, added_on: NaiveDate) -> bool {
+        let Some(quantity) = quantity.to(self.unit) else {
+            return false;
+        };
+        self.batches.push(Batch::new(quantity.amount(), expiry_date, added_on));
+        self.batches.sort_by_key(|b| b.expiry_date());
+        true
+    }
+
+    /// 把在 `from` 这天过期的批次改到 `to` 过期，没有这样的批次时返回 false
+    pub fn reschedule(&mut self, from: NaiveDate, to: NaiveDate) -> bool {
+        let mut changed = false;
+        for batch in self.batches.iter_mut().filter(|b| b.expiry_date() == from) {
+            batch.set_expiry_date(to);
+            changed = true;
         }
+        self.batches.sort_by_key(|b| b.expiry_date());
+        changed
     }
 
-    pub fn consume_one(&mut self) -> bool {
-        self.consume_n(1)
+    /// 所有批次的过期日期一起提前（负数）或推后若干天
+    pub fn shift_expiry(&mut self, days: i64) {
+        for batch in self.batches.iter_mut() {
+            batch.set_expiry_date(batch.expiry_date() + Duration::days(days));
+        }
     }
 
-    /// 计算剩余天数：负数表示已过期
-    pub fn days_remaining(&self) -> i64 {
-        let today = Local::now().date_naive();
-        (self.expiry_date - today).num_days()
+    /// 把同一种东西的另一个物品合并为新的批次，不是同一种东西时原样返回；
+    /// 价格只记在整个物品上，一边有价格一边没有、或者货币不同时也分开记录，免得算错已有库存的价值
+    // 原样交还未合并的物品是常见路径，装箱反而多一次分配
+    #[allow(clippy::result_large_err)]
+    pub fn absorb(&mut self, other: Item) -> Result<(), Item> {
+        if !self.is_same_product(&other) || !self.price_compatible(&other) {
+            return Err(other);
+        }
+        self.merge_price(&other);
+        for batch in other.batches() {
+            self.add_batch(Quantity::new(batch.amount(), other.unit), batch.expiry_date(), batch.added_on());
+        }
+        Ok(())
     }
 
-    /// 获取状态颜色类名 (Tailwind CSS Class)
-    pub fn status_class(&self) -> &'static str {
-        let days = self.days_remaining();
-        if days <= 1 {
-            "bg-red-50 border-l-4 border-red-500 shadow-sm" // 🔴 过期 或 剩1天
-        } else if days <= 3 {
-            "bg-amber-50 border-l-4 border-amber-500 shadow-sm" // 🟡 3天内
-        } else {
-            "bg-white border border-gray-100 border-l-4 border-l-green-500 shadow-sm" // 🟢 安全
+    // 两边都没有价格，或者都用同一种货币
+    fn price_compatible(&self, other: &Item) -> bool {
+        match (self.unit_price, other.unit_price) {
+            (None, None) => true,
+            (Some(ours), Some(theirs)) => ours.currency() == theirs.currency(),
+            _ => false,
         }
     }
 
+    // 合并批次前更新单价：按数量加权平均，调用前已确认货币相同
+    fn merge_price(&mut self, other: &Item) {
+        let (Some(ours), Some(their_value), Some(their_quantity)) =
+            (self.unit_price, other.value(), other.quantity().to(self.unit))
+        else {
+            return;
+        };
+        let our_amount = self.quantity().amount();
+        let total = our_amount + their_quantity.amount();
+        if total <= EPSILON {
+            return;
+        }
+        let value = ours.amount() * our_amount + their_value.amount();
+        self.unit_price = Some(Price::new(value / total, ours.currency()));
+    }
+
+    /// 获取存放位置
+    pub fn location(&self) -> Location {
+        self.location
+    }
+
+    /// 移到另一个存放位置
+    pub fn set_location(&mut self, location: Location) {
+        self.location = location;
+    }
+
+    /// 获取开封日期
+    pub fn opened_on(&self) -> Option<NaiveDate> {
+        self.opened_on
+    }
+
+    /// 标记为已开封，开封后的保质期已知时，过期日期不会晚于开封日加上这个天数
+    pub fn mark_opened(&mut self, today: NaiveDate, opened_days: Option<i64>) {
+        self.opened_on.get_or_insert(today);
+        if let Some(days) = opened_days {
+            let limit = today + Duration::days(days);
+            for batch in self.batches.iter_mut().filter(|b| b.expiry_date() > limit) {
+                batch.set_expiry_date(limit);
+            }
+            self.batches.sort_by_key(|b| b.expiry_date());
+        }
+    }
+
+    /// 放进冷冻室，冷冻保质期已知时，过期日期至少延长到今天加上这个天数
+    pub fn freeze(&mut self, today: NaiveDate, freezer_days: Option<i64>) {
+        self.location = Location::Freezer;
+        if let Some(days) = freezer_days {
+            let until = today + Duration::days(days);
+            for batch in self.batches.iter_mut().filter(|b| b.expiry_date() < until) {
+                batch.set_expiry_date(until);
+            }
+            self.batches.sort_by_key(|b| b.expiry_date());
+        }
+    }
+
+    /// 获取分类标识
+    pub fn category(&self) -> Option<&str> {
+        self.category.as_deref()
+    }
+
+    /// 获取入库日期
+    pub fn added_on(&self) -> NaiveDate {
+        self.added_on
+    }
+
+    /// 旧数据没有入库日期时，按 `today`（通常取 `Clock::today`）补上物品和各批次的入库日期
+    pub fn settle_added_on(&mut self, today: NaiveDate) {
+        if self.added_on == NaiveDate::default() {
+            self.added_on = today;
+        }
+        for batch in self.batches.iter_mut().filter(|b| b.added_on() == NaiveDate::default()) {
+            batch.set_added_on(self.added_on);
+        }
+    }
+
+    /// 按物品自身的单位消耗一部分，先用最早过期的批次（FEFO），
+    /// 返回 true 表示已经用完、应从库存移除
+    pub fn consume(&mut self, amount: f64) -> bool {
+        let mut rest = amount.max(0.0);
+        for batch in self.batches.iter_mut() {
+            if rest <= EPSILON {
+                break;
+            }
+            rest -= batch.take(rest);
+        }
+        self.batches.retain(|b| b.amount() > EPSILON);
+        self.batches.is_empty()
+    }
+
+    pub fn consume_one(&mut self) -> bool {
+        self.consume(1.0)
+    }
+
+    /// 到 `today` 为止的剩余天数：负数表示已过期，`today` 取自 `Clock`
+    pub fn days_remaining(&self, today: NaiveDate) -> i64 {
+        (self.expiry_date() - today).num_days()
+    }
+
+    /// 按分类的临期阈值判断在 `today` 的新鲜程度
+    pub fn status(&self, thresholds: &Thresholds, today: NaiveDate) -> ItemStatus {
+        ItemStatus::of(self.days_remaining(today), thresholds)
+    }
+
     /// 获取用于展示的时间文本
-    pub fn display_deadline(&self) -> String {
-        let days = self.days_remaining();
+    pub fn display_deadline(&self, today: NaiveDate) -> String {
+        let days = self.days_remaining(today);
         if days < 0 {
             format!("已过期 {} 天", days.abs())
         } else if days == 0 {
@@ -111,17 +362,25 @@ impl Item {
 
     /// 从物品名称中提取 emoji，如果名称以 emoji 开头则返回该 emoji，否则返回默认值
     pub fn extract_emoji(name: &mut String) -> String {
-        // 获取第一个字符
-        if let Some(first_char) = name.chars().next() {
-            // 检查是否是 emoji（简单判断：非 ASCII 字符）
-            // 更准确的判断可以检查 Unicode 范围，但这里简化处理
-            if !first_char.is_ascii() {
-                let char_str = first_char.to_string();
-                name.drain(..first_char.len_utf8());
-                return char_str;
+        if let Some(first_char) = name.chars().next().filter(|c| is_emoji(*c)) {
+            name.drain(..first_char.len_utf8());
+            // 去掉紧跟的变体选择符，例如 "🌶️辣椒"
+            if name.starts_with('\u{FE0F}') {
+                name.drain(..'\u{FE0F}'.len_utf8());
             }
+            return first_char.to_string();
         }
         // 默认使用纸箱 emoji
         "📦".to_string()
     }
+
+    /// 名称是否以 emoji 开头
+    pub fn starts_with_emoji(name: &str) -> bool {
+        name.chars().next().is_some_and(is_emoji)
+    }
+}
+
+// 粗略判断字符是否为 emoji：常见的符号和图形区段，不包括汉字等文字
+fn is_emoji(c: char) -> bool {
+    matches!(c as u32, 0x1F000..=0x1FAFF | 0x2600..=0x27BF | 0x2B00..=0x2BFF | 0x2300..=0x23FF)
 }
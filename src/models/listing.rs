@@ -0,0 +1,158 @@
+use super::query::{matches_text, pinyin_keys, search_label};
+use super::{Category, InventoryQuery, Item, SortOrder};
+use chrono::NaiveDate;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+// 一次同步中变化的物品超过列表的这个比例时，直接整体重新排序
+const REBUILD_RATIO: usize = 4;
+
+// 排序键：同一个列表只会出现其中一种，排序相同的再按 ID 排列
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+enum SortKey {
+    Expiry(NaiveDate),
+    Name(String),
+    // 数量多的在前；数量都不是负数，按位比较与按大小比较一致
+    Quantity(Reverse<u64>),
+    Added(Reverse<NaiveDate>),
+}
+
+/// 按查询条件筛选并排序后的库存，只保存物品 ID 和排序键。
+/// 库存变化后调用 `sync`：只有排序键或筛选结果变化的物品会移动位置，不需要每次整体排序，
+/// 拼音和搜索匹配结果按名称缓存，几千件物品时也能在一帧内完成
+#[derive(Clone, Debug)]
+pub struct SortedInventory {
+    query: InventoryQuery,
+    // 按 (排序键, ID) 排好序的行
+    rows: Vec<(SortKey, Uuid)>,
+    // 在列表中的物品当前的排序键
+    keys: HashMap<Uuid, SortKey>,
+    // 最近一次同步时物品在库存中的下标
+    positions: HashMap<Uuid, usize>,
+    // 名称对应的拼音全拼，用于按名称排序
+    pinyin: HashMap<String, String>,
+    // 表情加名称是否匹配当前的搜索文本
+    text_matches: HashMap<String, bool>,
+}
+
+impl SortedInventory {
+    /// 创建空列表，随后用 `sync` 填充
+    pub fn new(query: InventoryQuery) -> Self {
+        Self {
+            query,
+            rows: Vec::new(),
+            keys: HashMap::new(),
+            positions: HashMap::new(),
+            pinyin: HashMap::new(),
+            text_matches: HashMap::new(),
+        }
+    }
+
+    /// 查询条件，条件变化时应重新创建
+    pub fn query(&self) -> &InventoryQuery {
+        &self.query
+    }
+
+    /// 列表中的物品个数
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// 列表是否为空
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// 按顺序排列的物品 ID
+    pub fn ids(&self) -> impl Iterator<Item = Uuid> + '_ {
+        self.rows.iter().map(|(_, id)| *id)
+    }
+
+    /// 第 `index` 行的物品 ID
+    pub fn id_at(&self, index: usize) -> Option<Uuid> {
+        self.rows.get(index).map(|(_, id)| *id)
+    }
+
+    /// 物品在列表中的行号
+    pub fn index_of(&self, id: Uuid) -> Option<usize> {
+        let key = self.keys.get(&id)?;
+        self.rows.binary_search_by(|(k, i)| (k, i).cmp(&(key, &id))).ok()
+    }
+
+    /// 物品在最近一次同步的库存中的下标，用于取出物品本身
+    pub fn position(&self, id: Uuid) -> Option<usize> {
+        self.positions.get(&id).copied()
+    }
+
+    /// 与库存同步：重新计算每个物品是否满足条件和排序键，只移动有变化的行
+    pub fn sync(&mut self, items: &[Item], categories: &[Category], today: NaiveDate) {
+        self.positions.clear();
+        let mut changes = Vec::new();
+        for (index, item) in items.iter().enumerate() {
+            let id = item.id();
+            self.positions.insert(id, index);
+            let key = self.included(item, categories, today).then(|| self.key_of(item));
+            if self.keys.get(&id) != key.as_ref() {
+                changes.push((id, key));
+            }
+        }
+        // 已经从库存中移除的物品
+        let removed: Vec<Uuid> = self.keys.keys().filter(|id| !self.positions.contains_key(id)).copied().collect();
+        changes.extend(removed.into_iter().map(|id| (id, None)));
+        if changes.is_empty() {
+            return;
+        }
+
+        if changes.len() * REBUILD_RATIO > self.rows.len() {
+            for (id, key) in changes {
+                match key {
+                    Some(key) => self.keys.insert(id, key),
+                    None => self.keys.remove(&id),
+                };
+            }
+            self.rows = self.keys.iter().map(|(id, key)| (key.clone(), *id)).collect();
+            self.rows.sort_unstable();
+            return;
+        }
+
+        for (id, key) in changes {
+            if let Some(old) = self.keys.remove(&id) {
+                if let Ok(index) = self.rows.binary_search(&(old, id)) {
+                    self.rows.remove(index);
+                }
+            }
+            if let Some(key) = key {
+                let row = (key.clone(), id);
+                let index = self.rows.binary_search(&row).unwrap_or_else(|index| index);
+                self.rows.insert(index, row);
+                self.keys.insert(id, key);
+            }
+        }
+    }
+
+    // 是否满足查询条件，搜索文本的匹配结果按名称缓存
+    fn included(&mut self, item: &Item, categories: &[Category], today: NaiveDate) -> bool {
+        if !self.query.matches_filters(item, categories, today) {
+            return false;
+        }
+        if self.query.text.trim().is_empty() {
+            return true;
+        }
+        let label = search_label(item);
+        let text = &self.query.text;
+        *self.text_matches.entry(label).or_insert_with_key(|label| matches_text(label, text))
+    }
+
+    fn key_of(&mut self, item: &Item) -> SortKey {
+        match self.query.sort {
+            SortOrder::Expiry => SortKey::Expiry(item.expiry_date()),
+            SortOrder::Name => {
+                let full = self.pinyin.entry(item.name().to_string()).or_insert_with_key(|name| pinyin_keys(name).0);
+                SortKey::Name(full.clone())
+            }
+            SortOrder::Quantity => SortKey::Quantity(Reverse(item.quantity().amount().max(0.0).to_bits())),
+            SortOrder::Added => SortKey::Added(Reverse(item.added_on())),
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+/// 物品的存放位置
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Serialize, Deserialize)]
+pub enum Location {
+    /// 冷藏
+    #[default]
+    Fridge,
+    /// 冷冻
+    Freezer,
+    /// 常温储藏
+    Pantry,
+}
+
+impl Location {
+    /// 所有位置，用于筛选和录入
+    pub const ALL: [Location; 3] = [Location::Fridge, Location::Freezer, Location::Pantry];
+
+    /// 用于展示的文本
+    pub fn label(&self) -> &'static str {
+        match self {
+            Location::Fridge => "冷藏",
+            Location::Freezer => "冷冻",
+            Location::Pantry => "常温",
+        }
+    }
+
+    /// Material Symbols 图标名
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Location::Fridge => "kitchen",
+            Location::Freezer => "ac_unit",
+            Location::Pantry => "shelves",
+        }
+    }
+
+    /// 用于 URL 查询参数的标识
+    pub fn key(&self) -> &'static str {
+        match self {
+            Location::Fridge => "fridge",
+            Location::Freezer => "freezer",
+            Location::Pantry => "pantry",
+        }
+    }
+
+    /// 从 URL 查询参数解析
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|l| l.key() == key)
+    }
+}
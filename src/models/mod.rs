@@ -1,3 +1,47 @@
+mod action;
+mod appearance;
+mod backup;
+mod batch;
+mod bulk;
+mod category;
+mod food;
+mod history;
+mod household;
 mod item;
+mod listing;
+mod location;
+mod plan;
+mod price;
+mod quantity;
+mod query;
+mod recipe;
+mod report;
+mod shopping;
+mod snapshot;
+mod status;
 
+pub use action::{CardAction, SwipeSettings};
+pub use appearance::{Appearance, StatusPalette, Theme};
+pub use backup::{BackupSchedule, WebDavSettings};
+pub use batch::Batch;
+pub use bulk::{BulkAction, BulkOperation};
+pub use category::{category_of, find_category, guess_category, thresholds_of, Category, ShelfLife, Thresholds};
+pub use food::{FoodDatabase, FoodEntry};
+pub use history::{usual_amount, EventKind, HistoryEvent, WasteReason};
+pub use household::{normalize_invite_code, Household, HouseholdSession, Member, INVITE_CODE_LEN};
 pub use item::Item;
+pub use listing::SortedInventory;
+pub use location::Location;
+pub use plan::{complete_meal, reservation_shortfall, reserved_amount, Meal, MealSlot, Reservation};
+pub use price::{Currency, Price};
+pub use quantity::{format_amount, Quantity, Unit};
+pub use query::{matches_text, InventoryQuery, SortOrder, StatusFilter};
+pub use recipe::{
+    bundled_recipes, cook, parse_recipes, suggest_recipes, Consumption, Ingredient, IngredientMatch, Recipe, RecipeSuggestion,
+};
+pub use report::{monthly_reports, MonthlyReport};
+pub use shopping::{known_shelf_life, shelf_life_of, ShoppingEntry, DEFAULT_SHELF_LIFE_DAYS, SHELF_LIFE_PRESETS};
+pub use snapshot::{
+    apply_retention, needs_auto_snapshot, record_snapshot, Snapshot, SnapshotDiff, SnapshotKind, SNAPSHOT_BUDGET_BYTES,
+};
+pub use status::ItemStatus;
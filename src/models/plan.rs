@@ -0,0 +1,178 @@
+use super::{Batch, Consumption, Item, Quantity};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// 一天中的哪一餐
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Default, Serialize, Deserialize)]
+pub enum MealSlot {
+    /// 早餐
+    Breakfast,
+    /// 午餐
+    Lunch,
+    /// 晚餐
+    #[default]
+    Dinner,
+}
+
+impl MealSlot {
+    /// 所有餐次，按时间先后排列
+    pub const ALL: [MealSlot; 3] = [MealSlot::Breakfast, MealSlot::Lunch, MealSlot::Dinner];
+
+    /// 用于展示的文本
+    pub fn label(&self) -> &'static str {
+        match self {
+            MealSlot::Breakfast => "早餐",
+            MealSlot::Lunch => "午餐",
+            MealSlot::Dinner => "晚餐",
+        }
+    }
+
+    /// 用于表单选项的标识
+    pub fn key(&self) -> &'static str {
+        match self {
+            MealSlot::Breakfast => "breakfast",
+            MealSlot::Lunch => "lunch",
+            MealSlot::Dinner => "dinner",
+        }
+    }
+
+    /// 从标识解析
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.key() == key)
+    }
+}
+
+/// 为某一餐预留的物品数量（按物品自身的单位）
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Reservation {
+    pub item_id: Uuid,
+    pub amount: f64,
+}
+
+/// 计划中的一餐
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Meal {
+    id: Uuid,
+    date: NaiveDate,
+    slot: MealSlot,
+    title: String,
+    reservations: Vec<Reservation>,
+}
+
+impl Meal {
+    /// 创建一餐
+    pub fn new(date: NaiveDate, slot: MealSlot, title: String, reservations: Vec<Reservation>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            date,
+            slot,
+            title,
+            reservations,
+        }
+    }
+
+    /// 获取 ID
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// 获取日期
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+
+    /// 获取餐次
+    pub fn slot(&self) -> MealSlot {
+        self.slot
+    }
+
+    /// 获取标题
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// 获取预留的物品
+    pub fn reservations(&self) -> &[Reservation] {
+        &self.reservations
+    }
+
+    /// 预留的物品里会在用餐日期之前过期的：按先到期先用的顺序，排在前面的饭菜先用掉最早到期的批次，
+    /// 检查这一餐实际会用到的那几批；`meals` 是全部计划，用来确定前面的饭菜已经占用了多少
+    pub fn expiring_before<'a>(&self, items: &'a [Item], meals: &[Meal]) -> Vec<&'a Item> {
+        self.reservations
+            .iter()
+            .filter_map(|r| items.iter().find(|i| i.id() == r.item_id).map(|item| (r, item)))
+            .filter(|(r, item)| {
+                let taken_before: f64 = meals
+                    .iter()
+                    .filter(|m| m.id != self.id && (m.date, m.slot) < (self.date, self.slot))
+                    .flat_map(|m| m.reservations.iter())
+                    .filter(|other| other.item_id == item.id())
+                    .map(|other| other.amount)
+                    .sum();
+                covered_batches(item, taken_before, r.amount).any(|b| self.date > b.expiry_date())
+            })
+            .map(|(_, item)| item)
+            .collect()
+    }
+}
+
+// 跳过前面已占用的 `skip` 后，取 `amount` 会用到的批次（批次已按过期日期排列）
+fn covered_batches(item: &Item, skip: f64, amount: f64) -> impl Iterator<Item = &Batch> {
+    let end = skip + amount;
+    let mut start = 0.0;
+    item.batches().iter().filter(move |batch| {
+        let from = start;
+        start += batch.amount();
+        amount > 0.0 && from < end && start > skip
+    })
+}
+
+/// 某个物品在所有计划中被预留的总量，可以排除正在编辑的那一餐
+pub fn reserved_amount(meals: &[Meal], item_id: Uuid, except: Option<Uuid>) -> f64 {
+    meals
+        .iter()
+        .filter(|m| Some(m.id) != except)
+        .flat_map(|m| m.reservations.iter())
+        .filter(|r| r.item_id == item_id)
+        .map(|r| r.amount)
+        .sum()
+}
+
+/// 从物品中用掉 `amount` 后，会挤占计划预留数量的部分；不影响计划时为 0。
+/// 吃掉、扔掉或做菜时用来提醒“这是为某顿饭留的”
+pub fn reservation_shortfall(meals: &[Meal], item: &Item, amount: f64) -> f64 {
+    let reserved = reserved_amount(meals, item.id(), None);
+    let left = (item.quantity().amount() - amount).max(0.0);
+    let shortfall = reserved.min(item.quantity().amount()) - left;
+    // 换算误差不算挤占
+    if shortfall > 1e-6 {
+        shortfall
+    } else {
+        0.0
+    }
+}
+
+/// 完成一餐：扣减预留的数量（不超过现有数量），已不在库存里的物品会被跳过
+pub fn complete_meal(meal: &Meal, items: &mut Vec<Item>) -> Vec<Consumption> {
+    let mut consumed = Vec::new();
+    for reservation in &meal.reservations {
+        let Some(index) = items.iter().position(|i| i.id() == reservation.item_id) else {
+            continue;
+        };
+        let before = items[index].clone();
+        let total = before.quantity();
+        let amount = reservation.amount.min(total.amount());
+        let removed = items[index].consume(amount);
+        if removed {
+            items.remove(index);
+        }
+        consumed.push(Consumption {
+            amount: Quantity::new(amount, total.unit()),
+            item: before,
+            removed,
+        });
+    }
+    consumed
+}
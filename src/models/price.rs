@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// 货币
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default, Serialize, Deserialize)]
+pub enum Currency {
+    /// 人民币
+    #[default]
+    Cny,
+    /// 美元
+    Usd,
+    /// 欧元
+    Eur,
+    /// 英镑
+    Gbp,
+    /// 日元
+    Jpy,
+    /// 港币
+    Hkd,
+}
+
+impl Currency {
+    /// 所有货币，用于录入页选择
+    pub const ALL: [Currency; 6] = [
+        Currency::Cny,
+        Currency::Usd,
+        Currency::Eur,
+        Currency::Gbp,
+        Currency::Jpy,
+        Currency::Hkd,
+    ];
+
+    /// ISO 4217 代码
+    pub fn code(&self) -> &'static str {
+        match self {
+            Currency::Cny => "CNY",
+            Currency::Usd => "USD",
+            Currency::Eur => "EUR",
+            Currency::Gbp => "GBP",
+            Currency::Jpy => "JPY",
+            Currency::Hkd => "HKD",
+        }
+    }
+
+    /// 用于展示的货币符号
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Currency::Cny => "¥",
+            Currency::Usd => "$",
+            Currency::Eur => "€",
+            Currency::Gbp => "£",
+            Currency::Jpy => "JP¥",
+            Currency::Hkd => "HK$",
+        }
+    }
+
+    /// 从代码或符号解析，忽略大小写，例如 "cny"、"¥"、"RMB"
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        if text.eq_ignore_ascii_case("RMB") || text == "元" {
+            return Some(Currency::Cny);
+        }
+        Self::ALL
+            .into_iter()
+            .find(|c| c.code().eq_ignore_ascii_case(text) || c.symbol() == text)
+    }
+}
+
+/// 带货币的金额
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Price {
+    amount: f64,
+    currency: Currency,
+}
+
+impl Price {
+    /// 创建金额，负数和非法值按 0 处理
+    pub fn new(amount: f64, currency: Currency) -> Self {
+        let amount = if amount.is_finite() { amount.max(0.0) } else { 0.0 };
+        Self { amount, currency }
+    }
+
+    /// 获取数值
+    pub fn amount(&self) -> f64 {
+        self.amount
+    }
+
+    /// 获取货币
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// 乘以数量，例如单价 × 剩余数量
+    pub fn times(&self, factor: f64) -> Price {
+        Price::new(self.amount * factor, self.currency)
+    }
+}
+
+impl fmt::Display for Price {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{:.2}", self.currency.symbol(), self.amount)
+    }
+}
@@ -0,0 +1,196 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// 浮点数比较的容差，避免 0.1 + 0.2 这类误差导致“还剩 0.0000001 个”
+const EPSILON: f64 = 1e-6;
+
+/// 计量单位
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Serialize, Deserialize)]
+pub enum Unit {
+    /// 个 / 件
+    #[default]
+    Piece,
+    /// 克
+    Gram,
+    /// 千克
+    Kilogram,
+    /// 毫升
+    Milliliter,
+    /// 升
+    Liter,
+    /// 包 / 袋 / 盒
+    Pack,
+}
+
+// 单位的量纲：只有同一量纲的单位之间可以换算
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Dimension {
+    Piece,
+    Pack,
+    Mass,
+    Volume,
+}
+
+impl Unit {
+    /// 所有单位，用于录入页选择
+    pub const ALL: [Unit; 6] = [Unit::Piece, Unit::Pack, Unit::Gram, Unit::Kilogram, Unit::Milliliter, Unit::Liter];
+
+    /// 用于展示的单位符号
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Unit::Piece => "个",
+            Unit::Gram => "g",
+            Unit::Kilogram => "kg",
+            Unit::Milliliter => "ml",
+            Unit::Liter => "L",
+            Unit::Pack => "包",
+        }
+    }
+
+    /// 从单位符号解析，忽略大小写，例如 "kg"、"L"、"个"
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        Self::ALL.into_iter().find(|u| u.symbol().eq_ignore_ascii_case(text))
+    }
+
+    /// 是否为可以任意细分的单位（重量、体积）
+    pub fn is_continuous(&self) -> bool {
+        matches!(self.dimension(), Dimension::Mass | Dimension::Volume)
+    }
+
+    fn dimension(&self) -> Dimension {
+        match self {
+            Unit::Piece => Dimension::Piece,
+            Unit::Pack => Dimension::Pack,
+            Unit::Gram | Unit::Kilogram => Dimension::Mass,
+            Unit::Milliliter | Unit::Liter => Dimension::Volume,
+        }
+    }
+
+    // 换算到该量纲基本单位（g、ml）的倍数
+    fn factor(&self) -> f64 {
+        match self {
+            Unit::Kilogram | Unit::Liter => 1000.0,
+            _ => 1.0,
+        }
+    }
+
+    /// 与另一个单位是否可以互相换算
+    pub fn is_compatible(&self, other: Unit) -> bool {
+        self.dimension() == other.dimension()
+    }
+}
+
+/// 带单位的数量，例如 1.5 L、500 g、2 个
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(from = "QuantityRepr")]
+pub struct Quantity {
+    amount: f64,
+    unit: Unit,
+}
+
+// 旧数据中数量是一个整数（个），新数据是 { amount, unit }
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum QuantityRepr {
+    Legacy(f64),
+    Full {
+        amount: f64,
+        #[serde(default)]
+        unit: Unit,
+    },
+}
+
+impl From<QuantityRepr> for Quantity {
+    fn from(repr: QuantityRepr) -> Self {
+        match repr {
+            QuantityRepr::Legacy(amount) => Quantity::new(amount, Unit::Piece),
+            QuantityRepr::Full { amount, unit } => Quantity::new(amount, unit),
+        }
+    }
+}
+
+impl Default for Quantity {
+    fn default() -> Self {
+        Self::pieces(1.0)
+    }
+}
+
+impl Quantity {
+    /// 创建数量，负数和非法值按 0 处理
+    pub fn new(amount: f64, unit: Unit) -> Self {
+        let amount = if amount.is_finite() { amount.max(0.0) } else { 0.0 };
+        Self { amount, unit }
+    }
+
+    /// 以“个”为单位的数量
+    pub fn pieces(amount: f64) -> Self {
+        Self::new(amount, Unit::Piece)
+    }
+
+    /// 获取数值
+    pub fn amount(&self) -> f64 {
+        self.amount
+    }
+
+    /// 获取单位
+    pub fn unit(&self) -> Unit {
+        self.unit
+    }
+
+    /// 是否已经用完
+    pub fn is_empty(&self) -> bool {
+        self.amount <= EPSILON
+    }
+
+    /// 换算到另一个单位，量纲不同时返回 None
+    pub fn to(&self, unit: Unit) -> Option<Quantity> {
+        self.unit
+            .is_compatible(unit)
+            .then(|| Quantity::new(self.amount * self.unit.factor() / unit.factor(), unit))
+    }
+
+    /// 减去另一个数量（会先换算单位），不够减时结果为 0，单位不兼容时返回 None
+    pub fn checked_sub(&self, other: Quantity) -> Option<Quantity> {
+        let other = other.to(self.unit)?;
+        let rest = self.amount - other.amount;
+        Some(Quantity::new(if rest <= EPSILON { 0.0 } else { rest }, self.unit))
+    }
+}
+
+impl fmt::Display for Quantity {
+    // 个数显示为 x2、x½；重量和体积不足 1 kg / 1 L 时换成 g / ml 显示
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let shown = match self.unit {
+            Unit::Kilogram if self.amount < 1.0 => self.to(Unit::Gram).unwrap_or(*self),
+            Unit::Liter if self.amount < 1.0 => self.to(Unit::Milliliter).unwrap_or(*self),
+            _ => *self,
+        };
+        match shown.unit {
+            Unit::Piece => write!(f, "x{}", format_amount(shown.amount)),
+            Unit::Pack => write!(f, "{}{}", format_amount(shown.amount), shown.unit.symbol()),
+            _ => write!(f, "{} {}", format_decimal(shown.amount), shown.unit.symbol()),
+        }
+    }
+}
+
+/// 格式化个数：常见分数显示为 ¼ ½ ¾，其余最多保留两位小数
+pub fn format_amount(amount: f64) -> String {
+    let whole = amount.trunc();
+    let frac = amount - whole;
+    let glyph = [(0.25, "¼"), (0.5, "½"), (0.75, "¾")]
+        .into_iter()
+        .find(|(value, _)| (frac - value).abs() < EPSILON)
+        .map(|(_, glyph)| glyph);
+    match glyph {
+        Some(glyph) if whole == 0.0 => glyph.to_string(),
+        Some(glyph) => format!("{}{}", whole, glyph),
+        None => format_decimal(amount),
+    }
+}
+
+// 最多保留两位小数，并去掉末尾的 0
+fn format_decimal(amount: f64) -> String {
+    let text = format!("{:.2}", amount);
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
@@ -0,0 +1,240 @@
+use super::{thresholds_of, Category, Item, Location, SortedInventory, Thresholds};
+use crate::utils::percent_decode;
+use chrono::NaiveDate;
+use pinyin::ToPinyin;
+use std::fmt;
+
+/// 按保质状态筛选
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StatusFilter {
+    /// 已过期
+    Expired,
+    /// 临期（不超过分类的黄色阈值）
+    Urgent,
+    /// 还很新鲜
+    Safe,
+}
+
+impl StatusFilter {
+    /// 所有状态，用于筛选条
+    pub const ALL: [StatusFilter; 3] = [StatusFilter::Expired, StatusFilter::Urgent, StatusFilter::Safe];
+
+    /// 用于展示的文本
+    pub fn label(&self) -> &'static str {
+        match self {
+            StatusFilter::Expired => "已过期",
+            StatusFilter::Urgent => "临期",
+            StatusFilter::Safe => "新鲜",
+        }
+    }
+
+    /// 用于 URL 查询参数的标识
+    pub fn key(&self) -> &'static str {
+        match self {
+            StatusFilter::Expired => "expired",
+            StatusFilter::Urgent => "urgent",
+            StatusFilter::Safe => "safe",
+        }
+    }
+
+    /// 从 URL 查询参数解析
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.key() == key)
+    }
+
+    /// 按临期阈值判断剩余天数是否属于该状态
+    pub fn matches(&self, days_remaining: i64, thresholds: &Thresholds) -> bool {
+        match self {
+            StatusFilter::Expired => days_remaining < 0,
+            StatusFilter::Urgent => (0..=thresholds.warning_days).contains(&days_remaining),
+            StatusFilter::Safe => days_remaining > thresholds.warning_days,
+        }
+    }
+}
+
+/// 列表排序方式
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum SortOrder {
+    /// 快过期的在前
+    #[default]
+    Expiry,
+    /// 按名称（拼音）排序
+    Name,
+    /// 数量多的在前
+    Quantity,
+    /// 最近入库的在前
+    Added,
+}
+
+impl SortOrder {
+    /// 所有排序方式，用于排序选择
+    pub const ALL: [SortOrder; 4] = [SortOrder::Expiry, SortOrder::Name, SortOrder::Quantity, SortOrder::Added];
+
+    /// 用于展示的文本
+    pub fn label(&self) -> &'static str {
+        match self {
+            SortOrder::Expiry => "按到期",
+            SortOrder::Name => "按名称",
+            SortOrder::Quantity => "按数量",
+            SortOrder::Added => "按入库",
+        }
+    }
+
+    /// 用于 URL 查询参数的标识
+    pub fn key(&self) -> &'static str {
+        match self {
+            SortOrder::Expiry => "expiry",
+            SortOrder::Name => "name",
+            SortOrder::Quantity => "quantity",
+            SortOrder::Added => "added",
+        }
+    }
+
+    /// 从 URL 查询参数解析
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.key() == key)
+    }
+}
+
+/// 首页的搜索、筛选和排序条件，保存在 URL 查询字符串中，
+/// 例如 `/?q=nn&status=urgent&location=fridge&sort=name`、`/?expires=2026-10-20`
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct InventoryQuery {
+    /// 搜索文本，支持子串、全拼和拼音首字母
+    pub text: String,
+    pub status: Option<StatusFilter>,
+    pub location: Option<Location>,
+    /// 分类标识
+    pub category: Option<String>,
+    /// 只看有批次在这一天过期的物品，由到期日历点选
+    pub expires: Option<NaiveDate>,
+    pub sort: SortOrder,
+}
+
+impl InventoryQuery {
+    /// 是否有任何搜索或筛选条件（不含排序）
+    pub fn is_filtered(&self) -> bool {
+        !self.text.trim().is_empty()
+            || self.status.is_some()
+            || self.location.is_some()
+            || self.category.is_some()
+            || self.expires.is_some()
+    }
+
+    /// 物品在 `today` 是否满足所有条件，`categories` 用于查找物品分类的临期阈值
+    pub fn matches(&self, item: &Item, categories: &[Category], today: NaiveDate) -> bool {
+        self.matches_filters(item, categories, today) && matches_text(&search_label(item), &self.text)
+    }
+
+    // 除搜索文本以外的条件
+    pub(super) fn matches_filters(&self, item: &Item, categories: &[Category], today: NaiveDate) -> bool {
+        self.status
+            .is_none_or(|s| s.matches(item.days_remaining(today), &thresholds_of(categories, item)))
+            && self.location.is_none_or(|l| item.location() == l)
+            && self.category.as_deref().is_none_or(|c| item.category() == Some(c))
+            && self.expires.is_none_or(|d| item.batches().iter().any(|b| b.expiry_date() == d))
+    }
+
+    /// 筛选并排序，排序相同的按物品 ID 排列；需要反复更新时使用 `SortedInventory`
+    pub fn apply(&self, items: &[Item], categories: &[Category], today: NaiveDate) -> Vec<Item> {
+        let mut view = SortedInventory::new(self.clone());
+        view.sync(items, categories, today);
+        view.ids().filter_map(|id| view.position(id)).map(|index| items[index].clone()).collect()
+    }
+}
+
+// 搜索文本匹配的对象：表情加名称
+pub(super) fn search_label(item: &Item) -> String {
+    format!("{}{}", item.emoji(), item.name())
+}
+
+impl fmt::Display for InventoryQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut pairs = Vec::new();
+        if !self.text.is_empty() {
+            pairs.push(format!("q={}", escape_value(&self.text)));
+        }
+        if let Some(status) = self.status {
+            pairs.push(format!("status={}", status.key()));
+        }
+        if let Some(location) = self.location {
+            pairs.push(format!("location={}", location.key()));
+        }
+        if let Some(category) = &self.category {
+            pairs.push(format!("category={}", escape_value(category)));
+        }
+        if let Some(expires) = self.expires {
+            pairs.push(format!("expires={}", expires.format("%Y-%m-%d")));
+        }
+        if self.sort != SortOrder::default() {
+            pairs.push(format!("sort={}", self.sort.key()));
+        }
+        write!(f, "{}", pairs.join("&"))
+    }
+}
+
+// 路由在拆分参数之前已经整体解码过一次，无法识别的参数直接忽略
+impl From<&str> for InventoryQuery {
+    fn from(query: &str) -> Self {
+        let mut result = Self::default();
+        for (key, value) in query.split('&').filter_map(|pair| pair.split_once('=')) {
+            let value = percent_decode(value);
+            match key {
+                "q" => result.text = value,
+                "status" => result.status = StatusFilter::from_key(&value),
+                "location" => result.location = Location::from_key(&value),
+                "category" => result.category = Some(value).filter(|c| !c.is_empty()),
+                "expires" => result.expires = NaiveDate::parse_from_str(&value, "%Y-%m-%d").ok(),
+                "sort" => result.sort = SortOrder::from_key(&value).unwrap_or_default(),
+                _ => {}
+            }
+        }
+        result
+    }
+}
+
+// 路由只转义空格等少数字符，并在拆分前整体解码，
+// 所以值里的 % & = 需要转义两次才能原样保留
+fn escape_value(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '%' | '&' | '=' | '+' | '#' => out.push_str(&format!("%25{:02X}", c as u32)),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+// 名称的拼音全拼和首字母，非汉字原样保留（小写）
+pub(super) fn pinyin_keys(name: &str) -> (String, String) {
+    let mut full = String::new();
+    let mut initials = String::new();
+    for c in name.chars() {
+        match c.to_pinyin() {
+            Some(p) => {
+                full.push_str(p.plain());
+                initials.push_str(p.first_letter());
+            }
+            None if c.is_alphanumeric() => {
+                full.extend(c.to_lowercase());
+                initials.extend(c.to_lowercase());
+            }
+            None => {}
+        }
+    }
+    (full, initials)
+}
+
+/// 名称是否匹配搜索文本：子串、拼音全拼或首字母，例如 "nn"、"niunai" 都能匹配“牛奶”
+pub fn matches_text(name: &str, text: &str) -> bool {
+    let needle: String = text.split_whitespace().collect::<String>().to_lowercase();
+    if needle.is_empty() {
+        return true;
+    }
+    if name.to_lowercase().contains(&needle) {
+        return true;
+    }
+    let (full, initials) = pinyin_keys(name);
+    full.contains(&needle) || initials.contains(&needle)
+}
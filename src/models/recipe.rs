@@ -0,0 +1,276 @@
+use super::food::contains_term;
+use super::{thresholds_of, Category, Item, Quantity};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+use uuid::Uuid;
+
+// 随应用打包的家常菜谱
+const BUNDLED_RECIPES: &str = include_str!("recipes.json");
+
+/// 菜谱中的一种食材
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Ingredient {
+    name: String,
+    /// 用于匹配库存物品名称的关键词，缺省时使用 `name`
+    #[serde(default)]
+    keywords: Vec<String>,
+    /// 需要的数量，缺省时按一个单位计算
+    #[serde(default)]
+    quantity: Option<Quantity>,
+    /// 可选食材缺少时不影响推荐
+    #[serde(default)]
+    optional: bool,
+}
+
+impl Ingredient {
+    /// 获取名称
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// 获取需要的数量
+    pub fn quantity(&self) -> Option<Quantity> {
+        self.quantity
+    }
+
+    /// 是否为可选食材
+    pub fn optional(&self) -> bool {
+        self.optional
+    }
+
+    /// 库存物品是否可以用作这种食材
+    pub fn matches(&self, item: &Item) -> bool {
+        let name = item.name().trim().to_lowercase();
+        std::iter::once(&self.name)
+            .chain(self.keywords.iter())
+            .map(|k| k.trim().to_lowercase())
+            .any(|k| !k.is_empty() && contains_term(&name, &k))
+    }
+
+    /// 做一次菜要从该物品中消耗的数量（按物品自身的单位）：
+    /// 单位可以换算时按菜谱用量，否则个数类单位消耗 1，重量体积类整份用掉
+    pub fn amount_for(&self, item: &Item) -> f64 {
+        let total = item.quantity();
+        let amount = match self.quantity.and_then(|q| q.to(total.unit())) {
+            Some(q) => q.amount(),
+            None if total.unit().is_continuous() => total.amount(),
+            None => 1.0,
+        };
+        amount.min(total.amount())
+    }
+}
+
+/// 一道菜
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Recipe {
+    id: String,
+    name: String,
+    #[serde(default = "default_recipe_emoji")]
+    emoji: String,
+    /// 大约需要的分钟数
+    #[serde(default)]
+    minutes: Option<u32>,
+    ingredients: Vec<Ingredient>,
+    #[serde(default)]
+    steps: Vec<String>,
+}
+
+fn default_recipe_emoji() -> String {
+    "🍽️".to_string()
+}
+
+impl Recipe {
+    /// 获取标识
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// 获取名称
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// 获取表情符号
+    pub fn emoji(&self) -> &str {
+        &self.emoji
+    }
+
+    /// 获取大约需要的分钟数
+    pub fn minutes(&self) -> Option<u32> {
+        self.minutes
+    }
+
+    /// 获取食材
+    pub fn ingredients(&self) -> &[Ingredient] {
+        &self.ingredients
+    }
+
+    /// 获取步骤
+    pub fn steps(&self) -> &[String] {
+        &self.steps
+    }
+}
+
+// 菜谱文件可以是 { version, recipes } 也可以直接是菜谱列表
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RecipeFile {
+    Book { recipes: Vec<Recipe> },
+    List(Vec<Recipe>),
+}
+
+impl From<RecipeFile> for Vec<Recipe> {
+    fn from(file: RecipeFile) -> Self {
+        match file {
+            RecipeFile::Book { recipes } | RecipeFile::List(recipes) => recipes,
+        }
+    }
+}
+
+/// 随应用打包的菜谱
+pub fn bundled_recipes() -> &'static [Recipe] {
+    static RECIPES: OnceLock<Vec<Recipe>> = OnceLock::new();
+    RECIPES.get_or_init(|| parse_recipes(BUNDLED_RECIPES, false).expect("bundled recipes are valid"))
+}
+
+/// 解析用户导入的菜谱文件（JSON 或 YAML），没有标识的菜谱会自动生成一个
+pub fn parse_recipes(text: &str, yaml: bool) -> Result<Vec<Recipe>, String> {
+    let file: RecipeFile = if yaml {
+        serde_yaml::from_str(text).map_err(|e| format!("菜谱格式错误: {}", e))?
+    } else {
+        serde_json::from_str(text).map_err(|e| format!("菜谱格式错误: {}", e))?
+    };
+    let mut recipes: Vec<Recipe> = file.into();
+    for recipe in recipes.iter_mut() {
+        if recipe.id.trim().is_empty() {
+            recipe.id = Uuid::new_v4().to_string();
+        }
+        if recipe.ingredients.is_empty() {
+            return Err(format!("菜谱“{}”没有食材", recipe.name));
+        }
+    }
+    Ok(recipes)
+}
+
+/// 某种食材在库存中找到的物品
+#[derive(Clone, PartialEq, Debug)]
+pub struct IngredientMatch {
+    /// 对应 `Recipe::ingredients` 中的位置
+    pub ingredient: usize,
+    pub item_id: Uuid,
+    pub days_remaining: i64,
+    /// 是否已到该物品分类的临期阈值
+    pub urgent: bool,
+}
+
+/// 一条菜谱推荐
+#[derive(Clone, PartialEq, Debug)]
+pub struct RecipeSuggestion {
+    pub recipe: Recipe,
+    pub matches: Vec<IngredientMatch>,
+    /// 库存里没有的必需食材
+    pub missing: Vec<String>,
+    /// 排序分数：每个用到的临期物品贡献 1 / (1 + 剩余天数)，越快过期权重越高
+    pub score: f64,
+}
+
+impl RecipeSuggestion {
+    /// 用到的临期物品个数
+    pub fn urgent_count(&self) -> usize {
+        self.matches.iter().filter(|m| m.urgent).count()
+    }
+}
+
+/// 按用到的临期物品推荐菜谱：只返回至少用到一个临期物品的菜谱，
+/// 分数高的在前，同分时缺少食材少的在前；剩余天数按 `today` 计算
+pub fn suggest_recipes(
+    recipes: &[Recipe],
+    items: &[Item],
+    categories: &[Category],
+    today: NaiveDate,
+) -> Vec<RecipeSuggestion> {
+    let mut suggestions: Vec<RecipeSuggestion> = recipes
+        .iter()
+        .map(|recipe| {
+            let mut matches: Vec<IngredientMatch> = Vec::new();
+            let mut missing = Vec::new();
+            for (index, ingredient) in recipe.ingredients.iter().enumerate() {
+                // 同一种食材有多个物品时优先用最快过期的；已被前面食材用上的物品不再重复匹配，
+                // 例如“鸡蛋”和“蛋”不会都指向同一盒鸡蛋
+                let best = items
+                    .iter()
+                    .filter(|item| ingredient.matches(item))
+                    .filter(|item| matches.iter().all(|m| m.item_id != item.id()))
+                    .min_by_key(|item| item.days_remaining(today));
+                match best {
+                    Some(item) => {
+                        let days = item.days_remaining(today);
+                        matches.push(IngredientMatch {
+                            ingredient: index,
+                            item_id: item.id(),
+                            days_remaining: days,
+                            urgent: days <= thresholds_of(categories, item).warning_days,
+                        });
+                    }
+                    None if !ingredient.optional => missing.push(ingredient.name.clone()),
+                    None => {}
+                }
+            }
+            let score = matches
+                .iter()
+                .filter(|m| m.urgent)
+                .map(|m| 1.0 / (1.0 + m.days_remaining.max(0) as f64))
+                .sum();
+            RecipeSuggestion {
+                recipe: recipe.clone(),
+                matches,
+                missing,
+                score,
+            }
+        })
+        .filter(|s| s.urgent_count() > 0)
+        .collect();
+    suggestions.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then(a.missing.len().cmp(&b.missing.len()))
+            .then_with(|| a.recipe.name.cmp(&b.recipe.name))
+    });
+    suggestions
+}
+
+/// 做菜时从一个物品中消耗的部分
+#[derive(Clone, PartialEq, Debug)]
+pub struct Consumption {
+    /// 消耗前的物品
+    pub item: Item,
+    pub amount: Quantity,
+    /// 是否因此用完并移出库存
+    pub removed: bool,
+}
+
+/// 按菜谱一次性消耗所有匹配到的食材，返回每个物品的消耗情况；每个物品最多消耗一次
+pub fn cook(suggestion: &RecipeSuggestion, items: &mut Vec<Item>) -> Vec<Consumption> {
+    let mut consumed: Vec<Consumption> = Vec::new();
+    for m in &suggestion.matches {
+        if consumed.iter().any(|c| c.item.id() == m.item_id) {
+            continue;
+        }
+        let Some(index) = items.iter().position(|i| i.id() == m.item_id) else {
+            continue;
+        };
+        let before = items[index].clone();
+        let amount = suggestion.recipe.ingredients[m.ingredient].amount_for(&before);
+        let removed = items[index].consume(amount);
+        if removed {
+            items.remove(index);
+        }
+        consumed.push(Consumption {
+            amount: Quantity::new(amount, before.quantity().unit()),
+            item: before,
+            removed,
+        });
+    }
+    consumed
+}
@@ -0,0 +1,87 @@
+use super::{Currency, EventKind, HistoryEvent};
+use chrono::Datelike;
+use std::collections::{BTreeMap, HashSet};
+use uuid::Uuid;
+
+/// 一个月内吃掉和扔掉的金额，按货币分别统计
+#[derive(Clone, PartialEq, Debug)]
+pub struct MonthlyReport {
+    year: i32,
+    month: u32,
+    consumed: BTreeMap<Currency, f64>,
+    wasted: BTreeMap<Currency, f64>,
+}
+
+impl MonthlyReport {
+    fn new(year: i32, month: u32) -> Self {
+        Self {
+            year,
+            month,
+            consumed: BTreeMap::new(),
+            wasted: BTreeMap::new(),
+        }
+    }
+
+    /// 获取年份
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// 获取月份（1-12）
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    /// 用于展示的月份，例如“2026年10月”
+    pub fn label(&self) -> String {
+        format!("{}年{}月", self.year, self.month)
+    }
+
+    /// 本月出现过的所有货币
+    pub fn currencies(&self) -> Vec<Currency> {
+        let mut currencies: Vec<Currency> = self.consumed.keys().chain(self.wasted.keys()).copied().collect();
+        currencies.sort();
+        currencies.dedup();
+        currencies
+    }
+
+    /// 吃掉的金额
+    pub fn consumed(&self, currency: Currency) -> f64 {
+        self.consumed.get(&currency).copied().unwrap_or(0.0)
+    }
+
+    /// 扔掉的金额
+    pub fn wasted(&self, currency: Currency) -> f64 {
+        self.wasted.get(&currency).copied().unwrap_or(0.0)
+    }
+
+    /// 扔掉的金额占比（0.0 - 1.0），没有任何金额时为 0
+    pub fn waste_ratio(&self, currency: Currency) -> f64 {
+        let total = self.consumed(currency) + self.wasted(currency);
+        if total > 0.0 {
+            self.wasted(currency) / total
+        } else {
+            0.0
+        }
+    }
+}
+
+/// 按月汇总记录了价值的吃掉/扔掉操作，最近的月份在前
+pub fn monthly_reports(events: &[HistoryEvent]) -> Vec<MonthlyReport> {
+    // 撤销后被抵消的记录不计入
+    let reverted: HashSet<Uuid> = events.iter().filter_map(|e| e.reverts()).collect();
+    let mut months: BTreeMap<(i32, u32), MonthlyReport> = BTreeMap::new();
+    for event in events.iter().filter(|e| !reverted.contains(&e.id())) {
+        let Some(value) = event.value().filter(|_| matches!(event.kind(), EventKind::Consumed | EventKind::Wasted)) else {
+            continue;
+        };
+        let key = (event.at().year(), event.at().month());
+        let report = months.entry(key).or_insert_with(|| MonthlyReport::new(key.0, key.1));
+        let bucket = match event.kind() {
+            EventKind::Wasted => &mut report.wasted,
+            _ => &mut report.consumed,
+        };
+        *bucket.entry(value.currency()).or_insert(0.0) += value.amount();
+    }
+    months.into_values().rev().collect()
+}
@@ -0,0 +1,150 @@
+use super::{EventKind, HistoryEvent, Item};
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// 常见食品的默认保质期（表情、名称、天数），同时用作录入页的快捷选项
+pub const SHELF_LIFE_PRESETS: [(&str, &str, i64); 5] = [
+    ("🥛", "牛奶", 7),
+    ("🥬", "蔬菜", 5),
+    ("🍞", "面包", 3),
+    ("🥚", "鸡蛋", 15),
+    ("🥩", "生肉", 2),
+];
+
+/// 无法推断保质期时使用的默认天数
+pub const DEFAULT_SHELF_LIFE_DAYS: i64 = 7;
+
+/// 购物清单中的一项
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct ShoppingEntry {
+    id: Uuid,
+    name: String,
+    emoji: String,
+    quantity: u32,
+    /// 已知的保质期（天），买回入库时用于预填过期日期
+    #[serde(default)]
+    shelf_life_days: Option<i64>,
+    #[serde(default)]
+    checked: bool,
+    added_at: NaiveDateTime,
+}
+
+impl ShoppingEntry {
+    /// 手动添加一项，名称开头的表情会被单独提取；`now` 记为加入清单的时间
+    pub fn new(name: String, quantity: u32, shelf_life_days: Option<i64>, now: NaiveDateTime) -> Self {
+        let mut name = name.trim().to_string();
+        let emoji = Item::extract_emoji(&mut name);
+        Self {
+            id: Uuid::new_v4(),
+            name: name.trim().to_string(),
+            emoji,
+            quantity: quantity.max(1),
+            shelf_life_days,
+            checked: false,
+            added_at: now,
+        }
+    }
+
+    /// 根据用完的物品创建一项，沿用其名称和表情；`now` 记为加入清单的时间
+    pub fn from_item(item: &Item, shelf_life_days: Option<i64>, now: NaiveDateTime) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name: item.name().trim().to_string(),
+            emoji: item.emoji().to_string(),
+            quantity: 1,
+            shelf_life_days,
+            checked: false,
+            added_at: now,
+        }
+    }
+
+    /// 获取 ID
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// 获取名称
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// 获取表情符号
+    pub fn emoji(&self) -> &str {
+        &self.emoji
+    }
+
+    /// 获取数量
+    pub fn quantity(&self) -> u32 {
+        self.quantity
+    }
+
+    /// 获取已知的保质期（天）
+    pub fn shelf_life_days(&self) -> Option<i64> {
+        self.shelf_life_days
+    }
+
+    /// 是否已勾选（已买到）
+    pub fn checked(&self) -> bool {
+        self.checked
+    }
+
+    /// 获取加入清单的时间
+    pub fn added_at(&self) -> NaiveDateTime {
+        self.added_at
+    }
+
+    /// 增加数量
+    pub fn add_quantity(&mut self, count: u32) {
+        self.quantity += count.max(1);
+    }
+
+    /// 切换勾选状态
+    pub fn toggle(&mut self) {
+        self.checked = !self.checked;
+    }
+
+    /// 是否与某个物品是同一种东西（按表情和名称比较）
+    pub fn matches(&self, item: &Item) -> bool {
+        self.emoji == item.emoji() && self.name == item.name().trim()
+    }
+
+    /// 设置保质期（天）
+    pub fn set_shelf_life_days(&mut self, days: Option<i64>) {
+        self.shelf_life_days = days;
+    }
+
+    /// 以 `today` 为入库日期推算的过期日期
+    pub fn expiry_from(&self, today: NaiveDate) -> NaiveDate {
+        today + Duration::days(self.shelf_life_days.unwrap_or(DEFAULT_SHELF_LIFE_DAYS))
+    }
+
+    /// 买回后转换为库存物品，`today` 为入库日期
+    pub fn to_item(&self, expiry_date: NaiveDate, today: NaiveDate) -> Item {
+        Item::new_with_quantity(format!("{}{}", self.emoji, self.name), expiry_date, self.quantity, today)
+    }
+}
+
+/// 根据入库记录计算某个物品的保质期：过期日期 - 入库日期
+pub fn shelf_life_of(item: &Item, history: &[HistoryEvent]) -> Option<i64> {
+    history
+        .iter()
+        .find(|e| e.item_id() == item.id() && e.kind() == EventKind::Added)
+        .map(|e| (item.expiry_date() - e.at().date()).num_days())
+        .filter(|days| *days > 0)
+}
+
+/// 推断清单项的保质期：优先使用库存中同名物品的入库记录，其次使用常见食品的默认值
+pub fn known_shelf_life(entry: &ShoppingEntry, items: &[Item], history: &[HistoryEvent]) -> Option<i64> {
+    let label = format!("{}{}", entry.emoji, entry.name);
+    items
+        .iter()
+        .filter(|item| entry.matches(item))
+        .find_map(|item| shelf_life_of(item, history))
+        .or_else(|| {
+            SHELF_LIFE_PRESETS
+                .iter()
+                .find(|(_, preset, _)| label.contains(preset))
+                .map(|(_, _, days)| *days)
+        })
+}
@@ -0,0 +1,203 @@
+use super::Item;
+use chrono::{Datelike, Duration, NaiveDateTime, Timelike};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+// 自动快照的保留策略：最近 24 小时每小时一份、最近 7 天每天一份、最近 4 周每周一份
+const HOURLY_WINDOW: i64 = 24;
+const DAILY_WINDOW: i64 = 7;
+const WEEKLY_WINDOW: i64 = 4;
+// 导入、恢复、批量操作前的快照不参与上面的分段，只保留最近的几份
+const MAX_MANUAL_SNAPSHOTS: usize = 5;
+
+/// 所有快照序列化后的总大小上限（字节），超出时先删最旧的自动快照；
+/// 浏览器的 localStorage 通常只有 5 MB 左右，还要留给库存和记录
+pub const SNAPSHOT_BUDGET_BYTES: usize = 2 * 1024 * 1024;
+
+/// 快照的来源
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum SnapshotKind {
+    /// 库存变化时自动创建
+    #[default]
+    Auto,
+    /// 导入数据之前
+    BeforeImport,
+    /// 从快照或备份恢复之前
+    BeforeRestore,
+    /// 批量操作之前
+    BeforeBulk,
+}
+
+impl SnapshotKind {
+    /// 用于展示的文本
+    pub fn label(&self) -> &'static str {
+        match self {
+            SnapshotKind::Auto => "自动",
+            SnapshotKind::BeforeImport => "导入前",
+            SnapshotKind::BeforeRestore => "恢复前",
+            SnapshotKind::BeforeBulk => "批量操作前",
+        }
+    }
+}
+
+/// 某一时刻的完整库存副本
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Snapshot {
+    id: Uuid,
+    taken_at: NaiveDateTime,
+    #[serde(default)]
+    kind: SnapshotKind,
+    items: Vec<Item>,
+}
+
+/// 快照与当前库存的差异
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct SnapshotDiff {
+    /// 恢复后会重新出现的物品数（当前已没有）
+    pub restored: usize,
+    /// 恢复后会消失的物品数（快照之后新增）
+    pub dropped: usize,
+    /// 两边都有但数量或日期不同的物品数
+    pub changed: usize,
+}
+
+impl SnapshotDiff {
+    /// 快照与当前库存是否完全一致
+    pub fn is_empty(&self) -> bool {
+        self.restored == 0 && self.dropped == 0 && self.changed == 0
+    }
+}
+
+impl Snapshot {
+    /// 以指定时间创建快照
+    pub fn new_at(items: &[Item], kind: SnapshotKind, taken_at: NaiveDateTime) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            taken_at,
+            kind,
+            items: items.to_vec(),
+        }
+    }
+
+    /// 获取 ID
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// 获取创建时间
+    pub fn taken_at(&self) -> NaiveDateTime {
+        self.taken_at
+    }
+
+    /// 获取来源
+    pub fn kind(&self) -> SnapshotKind {
+        self.kind
+    }
+
+    /// 获取快照中的物品
+    pub fn items(&self) -> &[Item] {
+        &self.items
+    }
+
+    /// 与当前库存比较，按物品 ID 统计差异
+    pub fn diff(&self, current: &[Item]) -> SnapshotDiff {
+        let now: HashMap<Uuid, &Item> = current.iter().map(|i| (i.id(), i)).collect();
+        let then: HashSet<Uuid> = self.items.iter().map(|i| i.id()).collect();
+
+        let mut diff = SnapshotDiff::default();
+        for item in &self.items {
+            match now.get(&item.id()) {
+                None => diff.restored += 1,
+                Some(existing) if *existing != item => diff.changed += 1,
+                Some(_) => {}
+            }
+        }
+        diff.dropped = current.iter().filter(|i| !then.contains(&i.id())).count();
+        diff
+    }
+}
+
+/// 库存从 `previous` 变为 `current` 后，是否需要把变化前的 `previous` 存为自动快照：
+/// 本小时内还没有自动快照，且变化前的内容与最近一份不同。
+/// 存的是变化前的状态，这样每小时第一次改动即使是误操作（例如误滑删除），也能恢复到改动之前
+pub fn needs_auto_snapshot(snapshots: &[Snapshot], previous: &[Item], current: &[Item], now: NaiveDateTime) -> bool {
+    if previous == current || previous.is_empty() {
+        return false;
+    }
+    let auto = snapshots.iter().filter(|s| s.kind == SnapshotKind::Auto);
+    match auto.max_by_key(|s| s.taken_at) {
+        None => true,
+        Some(latest) => hour_key(latest.taken_at) != hour_key(now) && latest.items != previous,
+    }
+}
+
+/// 追加一份快照并按保留策略清理旧快照
+pub fn record_snapshot(snapshots: &mut Vec<Snapshot>, snapshot: Snapshot, now: NaiveDateTime) {
+    snapshots.push(snapshot);
+    apply_retention(snapshots, now, SNAPSHOT_BUDGET_BYTES);
+}
+
+/// 清理快照，结果按时间从新到旧排列：
+/// - 自动快照按“每小时 / 每天 / 每周”滚动保留，每个时间段只保留最新的一份，超出所有窗口的删除
+/// - 导入、恢复、批量操作前的快照保留最近 5 份
+/// - 总大小超过 `max_bytes` 时，从最旧的自动快照开始删除，最新的一份总会保留
+pub fn apply_retention(snapshots: &mut Vec<Snapshot>, now: NaiveDateTime, max_bytes: usize) {
+    snapshots.sort_by_key(|s| std::cmp::Reverse(s.taken_at));
+
+    let mut hours = HashSet::new();
+    let mut days = HashSet::new();
+    let mut weeks = HashSet::new();
+    let mut manual = 0;
+    snapshots.retain(|s| {
+        if s.kind != SnapshotKind::Auto {
+            manual += 1;
+            return manual <= MAX_MANUAL_SNAPSHOTS;
+        }
+        let age = now - s.taken_at;
+        let mut keep = false;
+        if age < Duration::hours(HOURLY_WINDOW) && hours.insert(hour_key(s.taken_at)) {
+            keep = true;
+        }
+        if age < Duration::days(DAILY_WINDOW) && days.insert(s.taken_at.date()) {
+            keep = true;
+        }
+        if age < Duration::weeks(WEEKLY_WINDOW) {
+            let week = s.taken_at.iso_week();
+            if weeks.insert((week.year(), week.week())) {
+                keep = true;
+            }
+        }
+        keep
+    });
+
+    enforce_budget(snapshots, max_bytes);
+}
+
+// 按序列化后的大小删除快照，直到总大小不超过上限；快照已按时间从新到旧排列
+fn enforce_budget(snapshots: &mut Vec<Snapshot>, max_bytes: usize) {
+    let sizes: Vec<usize> = snapshots
+        .iter()
+        .map(|s| serde_json::to_vec(s).map(|json| json.len()).unwrap_or(0))
+        .collect();
+    let mut total: usize = sizes.iter().sum();
+    let mut keep = vec![true; snapshots.len()];
+    // 先删自动快照，再删其它快照，都从最旧的开始
+    for auto_pass in [true, false] {
+        for i in (1..snapshots.len()).rev() {
+            if total <= max_bytes {
+                break;
+            }
+            if keep[i] && (snapshots[i].kind == SnapshotKind::Auto) == auto_pass {
+                keep[i] = false;
+                total -= sizes[i];
+            }
+        }
+    }
+    let mut keep = keep.into_iter();
+    snapshots.retain(|_| keep.next().unwrap_or(true));
+}
+
+fn hour_key(at: NaiveDateTime) -> (chrono::NaiveDate, u32) {
+    (at.date(), at.hour())
+}
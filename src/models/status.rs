@@ -0,0 +1,39 @@
+use super::Thresholds;
+
+/// 物品的新鲜程度，颜色等展示方式由界面按主题决定
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ItemStatus {
+    /// 已过期
+    Expired,
+    /// 即将到期（不超过分类的红色阈值）
+    Critical,
+    /// 临期（不超过分类的黄色阈值）
+    Warning,
+    /// 还很新鲜
+    Safe,
+}
+
+impl ItemStatus {
+    /// 按剩余天数和分类的临期阈值判断
+    pub fn of(days_remaining: i64, thresholds: &Thresholds) -> Self {
+        if days_remaining < 0 {
+            ItemStatus::Expired
+        } else if days_remaining <= thresholds.critical_days {
+            ItemStatus::Critical
+        } else if days_remaining <= thresholds.warning_days {
+            ItemStatus::Warning
+        } else {
+            ItemStatus::Safe
+        }
+    }
+
+    /// 用于展示的文本
+    pub fn label(&self) -> &'static str {
+        match self {
+            ItemStatus::Expired => "已过期",
+            ItemStatus::Critical => "即将到期",
+            ItemStatus::Warning => "临期",
+            ItemStatus::Safe => "新鲜",
+        }
+    }
+}
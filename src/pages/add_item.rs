@@ -1,28 +1,44 @@
-use crate::models::Item;
+use crate::models::{find_category, guess_category, Currency, EventKind, FoodDatabase, Price, HistoryEvent, Item, Location, Quantity, Unit, SHELF_LIFE_PRESETS};
 use crate::router::Route;
-use crate::state::InventoryState;
-use chrono::{Duration, Local, NaiveDate};
+use crate::state::{CategoryState, ClockState, HistoryState, HouseholdState, InventoryState};
+use chrono::{Duration, NaiveDate};
 use dioxus::prelude::*;
 
 #[component]
 pub fn AddItem() -> Element {
-    let mut inventory = use_context::<InventoryState>().0;
+    let mut inventory = use_context::<InventoryState>();
+    let mut history = use_context::<HistoryState>().0;
+    let household = use_context::<HouseholdState>();
+    let category_state = use_context::<CategoryState>();
+    let clock = use_context::<ClockState>();
     let navigator = use_navigator();
 
     // Form State
     let mut name = use_signal(|| "".to_string());
-    let mut production_date_str = use_signal(|| Local::now().format("%Y-%m-%d").to_string());
+    let mut production_date_str = use_signal(move || clock.today().format("%Y-%m-%d").to_string());
     let mut quantity_str = use_signal(|| "1".to_string());
-    let mut expiry_date_str = use_signal(|| Local::now().format("%Y-%m-%d").to_string());
+    let mut unit = use_signal(Unit::default);
+    // 购买时的总价，可不填
+    let mut price_str = use_signal(String::new);
+    let mut currency = use_signal(Currency::default);
+    let mut expiry_date_str = use_signal(move || clock.today().format("%Y-%m-%d").to_string());
+    let mut location = use_signal(Location::default);
+    // 已开封时按开封后的保质期推算，日期即为开封日期
+    let mut opened = use_signal(|| false);
+    let mut category = use_signal(|| Option::<String>::None);
+    // 用户手动选过分类或改过过期日期后，不再根据名称自动覆盖
+    let mut category_touched = use_signal(|| false);
+    let mut expiry_touched = use_signal(|| false);
+    // 当前过期日期是否来自分类默认保质期，用于提示
+    let mut prefilled_days = use_signal(|| Option::<i64>::None);
+
+    let categories = category_state.all();
 
     // Quick Chips Data
-    let quick_options = vec![
-        ("🥛 牛奶", 7, "🥛"),
-        ("🥬 蔬菜", 5, "🥬"),
-        ("🍞 面包", 3, "🍞"),
-        ("🥚 鸡蛋", 15, "🥚"),
-        ("🥩 生肉", 2, "🥩"),
-    ];
+    let quick_options: Vec<(String, i64, &str)> = SHELF_LIFE_PRESETS
+        .iter()
+        .map(|(emoji, name, days)| (format!("{} {}", emoji, name), *days, *emoji))
+        .collect();
 
     let submit = move |_| {
         if name.read().is_empty() {
@@ -30,26 +46,89 @@ pub fn AddItem() -> Element {
         }
 
         if let Ok(parsed_date) = NaiveDate::parse_from_str(&expiry_date_str.read(), "%Y-%m-%d") {
-            let item_name = name.read().clone();
-            let quantity = quantity_str
+            let mut item_name = name.read().clone();
+            // 名称里没有表情时使用分类的默认表情
+            if !Item::starts_with_emoji(&item_name) {
+                let all = category_state.all();
+                if let Some(c) = category.read().as_deref().and_then(|id| find_category(&all, id)) {
+                    item_name = format!("{}{}", c.emoji(), item_name);
+                }
+            }
+            // 支持小数，例如 1.5 L；无效或非正数时按 1 处理
+            let amount = quantity_str
                 .read()
-                .parse::<u32>()
+                .trim()
+                .parse::<f64>()
                 .ok()
-                .filter(|q| *q >= 1)
-                .unwrap_or(1);
-
-            inventory.write().push(Item::new_with_quantity(
-                item_name,
-                parsed_date,
-                quantity,
-            ));
+                .filter(|q| q.is_finite() && *q > 0.0)
+                .unwrap_or(1.0);
+            let quantity = Quantity::new(amount, unit());
+            let price = price_str
+                .read()
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|p| p.is_finite() && *p >= 0.0)
+                .map(|p| Price::new(p, currency()));
+
+            let item = Item::new_with_amount(item_name, parsed_date, quantity, clock.today())
+                .with_location(location())
+                .with_category(category())
+                .with_total_price(price);
+            let stocked = inventory.stock(item);
+            history
+                .write()
+                .push(HistoryEvent::new(&stocked, EventKind::Added, quantity, false, household.member()));
             navigator.go_back();
         }
     };
 
+    // Helper: 预填过期日期，优先使用食物知识库，其次使用分类和存放位置的默认保质期
+    let mut prefill_expiry = move || {
+        if expiry_touched() {
+            return;
+        }
+        let Ok(prod_date) = NaiveDate::parse_from_str(&production_date_str.read(), "%Y-%m-%d") else {
+            return;
+        };
+        if let Some(expiry) = FoodDatabase::bundled()
+            .lookup(&name.read())
+            .and_then(|f| f.expiry_from(prod_date, location(), opened()))
+        {
+            expiry_date_str.set(expiry.format("%Y-%m-%d").to_string());
+            prefilled_days.set(None);
+            return;
+        }
+        let all = category_state.all();
+        let days = category
+            .read()
+            .as_deref()
+            .and_then(|id| find_category(&all, id))
+            .and_then(|c| c.shelf_life.for_location(location()));
+        if let Some(days) = days {
+            expiry_date_str.set((prod_date + Duration::days(days)).format("%Y-%m-%d").to_string());
+            prefilled_days.set(Some(days));
+        }
+    };
+
+    // Helper: 输入名称时自动识别分类，知识库里有记录时使用记录的分类
+    let mut set_name = move |value: String| {
+        if !category_touched() {
+            let all = category_state.all();
+            let known = FoodDatabase::bundled()
+                .lookup(&value)
+                .and_then(|f| find_category(&all, f.category()));
+            category.set(known.or_else(|| guess_category(&all, &value)).map(|c| c.id().to_string()));
+        }
+        name.set(value);
+        prefill_expiry();
+    };
+
     // Helper: 点击 Chip 自动填入名称和过期日期
     let mut apply_chip = move |n: &str, days: i64, _e: &str| {
-        name.set(n.to_string());
+        set_name(n.to_string());
+        expiry_touched.set(true);
+        prefilled_days.set(None);
         // 基于生产日期计算过期日期
         if let Ok(prod_date) = NaiveDate::parse_from_str(&production_date_str.read(), "%Y-%m-%d") {
             let exp_date = prod_date + Duration::days(days);
@@ -59,18 +138,29 @@ pub fn AddItem() -> Element {
 
     // Helper: 快速设置过期日期（基于生产日期 + X天）
     let mut add_days = move |days: i64| {
+        expiry_touched.set(true);
+        prefilled_days.set(None);
         if let Ok(prod_date) = NaiveDate::parse_from_str(&production_date_str.read(), "%Y-%m-%d") {
             let exp_date = prod_date + Duration::days(days);
             expiry_date_str.set(exp_date.format("%Y-%m-%d").to_string());
         }
     };
 
+    // 知识库给出的建议过期日期，与当前日期不同时提供“采用”按钮
+    let suggestion = NaiveDate::parse_from_str(&production_date_str.read(), "%Y-%m-%d")
+        .ok()
+        .and_then(|prod_date| {
+            let food = FoodDatabase::bundled().lookup(&name.read())?;
+            let days = food.days(location(), opened())?;
+            Some((food.clone(), days, food.expiry_from(prod_date, location(), opened())?))
+        });
+
     rsx! {
         div { class: "flex flex-col p-6 max-w-2xl mx-auto min-h-screen bg-white",
 
             // Top Bar
             div { class: "flex items-center mb-6",
-                Link { to: Route::Home {}, class: "material-symbols-outlined text-gray-600 p-2 -ml-2 rounded-full hover:bg-gray-100 transition-colors", "arrow_back" }
+                Link { to: Route::Home { query: Default::default() }, class: "material-symbols-outlined text-gray-600 p-2 -ml-2 rounded-full hover:bg-gray-100 transition-colors", "arrow_back" }
                 h2 { class: "flex-1 text-center text-lg font-semibold text-gray-900 pr-8", "录入新物品" } // pr-8 balances the back button width visually
             }
 
@@ -82,7 +172,7 @@ pub fn AddItem() -> Element {
                         r#type: "text",
                         class: "flex-1 bg-gray-50 border border-gray-200 rounded-xl px-4 py-3 text-base focus:outline-none focus:ring-2 focus:ring-blue-500 focus:bg-white transition-all",
                         value: "{name}",
-                        oninput: move |evt| name.set(evt.value()),
+                        oninput: move |evt| set_name(evt.value()),
                         placeholder: "例如：全麦面包"
                     }
                     button {
@@ -98,37 +188,145 @@ pub fn AddItem() -> Element {
                 for (n, d, e) in quick_options {
                     button {
                         class: "px-4 py-2 bg-gray-100 hover:bg-gray-200 text-gray-700 rounded-full text-sm font-medium transition-colors cursor-pointer active:scale-95 border-none",
-                        onclick: move |_| apply_chip(n, d, e),
+                        onclick: move |_| apply_chip(&n, d, e),
                         "{n}"
                     }
                 }
             }
 
-            // 3. Production Date / Entry Date
+            // 3. 存放位置与分类
+            div { class: "flex gap-3 mb-6",
+                div { class: "flex flex-col",
+                    label { class: "block text-sm font-medium text-gray-700 mb-2", "存放位置" }
+                    div { class: "flex gap-2",
+                        for option in Location::ALL {
+                            button {
+                                class: if location() == option {
+                                    "flex items-center gap-1 px-3 py-2 rounded-lg text-sm font-medium bg-blue-600 text-white"
+                                } else {
+                                    "flex items-center gap-1 px-3 py-2 rounded-lg text-sm font-medium bg-gray-100 text-gray-700 hover:bg-gray-200"
+                                },
+                                onclick: move |_| {
+                                    location.set(option);
+                                    prefill_expiry();
+                                },
+                                span { class: "material-symbols-outlined text-base", "{option.icon()}" }
+                                "{option.label()}"
+                            }
+                        }
+                    }
+                }
+                div { class: "flex flex-col flex-1",
+                    div { class: "flex items-center justify-between mb-2",
+                        label { class: "block text-sm font-medium text-gray-700", "分类" }
+                        Link { to: Route::Categories {}, class: "text-xs text-blue-600 hover:underline", "管理" }
+                    }
+                    select {
+                        class: "w-full bg-gray-50 border border-gray-200 rounded-xl px-3 py-2 text-base focus:outline-none focus:ring-2 focus:ring-blue-500",
+                        onchange: move |evt| {
+                            category.set(Some(evt.value()).filter(|v| !v.is_empty()));
+                            category_touched.set(true);
+                            prefill_expiry();
+                        },
+                        option { value: "", selected: category.read().is_none(), "未分类" }
+                        for c in categories {
+                            option {
+                                value: "{c.id()}",
+                                selected: category.read().as_deref() == Some(c.id()),
+                                "{c.emoji()} {c.name()}"
+                            }
+                        }
+                    }
+                }
+            }
+
+            // 4. Production Date / Entry Date
             div { class: "flex flex-col mb-6",
-                label { class: "block text-sm font-medium text-gray-700 mb-2", "生产日期 / 入库日期" }
+                div { class: "flex items-center justify-between mb-2",
+                    label { class: "block text-sm font-medium text-gray-700",
+                        if opened() { "开封日期" } else { "生产日期 / 入库日期" }
+                    }
+                    label { class: "flex items-center gap-1 text-sm text-gray-600 cursor-pointer",
+                        input {
+                            r#type: "checkbox",
+                            checked: opened(),
+                            onchange: move |evt| {
+                                opened.set(evt.checked());
+                                prefill_expiry();
+                            }
+                        }
+                        "已开封"
+                    }
+                }
                 input {
                     r#type: "date",
                     class: "w-full bg-gray-50 border border-gray-200 rounded-xl px-4 py-3 text-base focus:outline-none focus:ring-2 focus:ring-blue-500 focus:bg-white transition-all",
                     value: "{production_date_str}",
-                    oninput: move |evt| production_date_str.set(evt.value())
+                    oninput: move |evt| {
+                        production_date_str.set(evt.value());
+                        prefill_expiry();
+                    }
                 }
             }
 
-            // 4. Quantity
+            // 5. Quantity
             div { class: "flex flex-col mb-6",
                 label { class: "block text-sm font-medium text-gray-700 mb-2", "数量" }
-                input {
-                    r#type: "number",
-                    min: "1",
-                    step: "1",
-                    class: "w-full bg-gray-50 border border-gray-200 rounded-xl px-4 py-3 text-base focus:outline-none focus:ring-2 focus:ring-blue-500 focus:bg-white transition-all",
-                    value: "{quantity_str}",
-                    oninput: move |evt| quantity_str.set(evt.value())
+                div { class: "flex gap-3",
+                    input {
+                        r#type: "number",
+                        min: "0",
+                        step: "any",
+                        inputmode: "decimal",
+                        class: "flex-1 min-w-0 bg-gray-50 border border-gray-200 rounded-xl px-4 py-3 text-base focus:outline-none focus:ring-2 focus:ring-blue-500 focus:bg-white transition-all",
+                        value: "{quantity_str}",
+                        oninput: move |evt| quantity_str.set(evt.value())
+                    }
+                    select {
+                        class: "w-24 bg-gray-50 border border-gray-200 rounded-xl px-3 py-3 text-base focus:outline-none focus:ring-2 focus:ring-blue-500",
+                        "aria-label": "单位",
+                        onchange: move |evt| {
+                            if let Some(u) = Unit::ALL.into_iter().find(|u| u.symbol() == evt.value()) {
+                                unit.set(u);
+                            }
+                        },
+                        for u in Unit::ALL {
+                            option { value: u.symbol(), selected: unit() == u, "{u.symbol()}" }
+                        }
+                    }
                 }
             }
 
-            // 5. Expiry Date
+            // 6. Price
+            div { class: "flex flex-col mb-6",
+                label { class: "block text-sm font-medium text-gray-700 mb-2", "总价（可选）" }
+                div { class: "flex gap-3",
+                    input {
+                        r#type: "number",
+                        min: "0",
+                        step: "0.01",
+                        inputmode: "decimal",
+                        class: "flex-1 min-w-0 bg-gray-50 border border-gray-200 rounded-xl px-4 py-3 text-base focus:outline-none focus:ring-2 focus:ring-blue-500 focus:bg-white transition-all",
+                        value: "{price_str}",
+                        oninput: move |evt| price_str.set(evt.value()),
+                        placeholder: "用于统计浪费的金额"
+                    }
+                    select {
+                        class: "w-24 bg-gray-50 border border-gray-200 rounded-xl px-3 py-3 text-base focus:outline-none focus:ring-2 focus:ring-blue-500",
+                        "aria-label": "货币",
+                        onchange: move |evt| {
+                            if let Some(c) = Currency::parse(&evt.value()) {
+                                currency.set(c);
+                            }
+                        },
+                        for c in Currency::ALL {
+                            option { value: c.code(), selected: currency() == c, "{c.symbol()} {c.code()}" }
+                        }
+                    }
+                }
+            }
+
+            // 7. Expiry Date
             div { class: "flex flex-col mb-8",
                 label { class: "block text-sm font-medium text-gray-700 mb-2", "过期日期" }
                 // Duration Presets
@@ -143,13 +341,41 @@ pub fn AddItem() -> Element {
                     r#type: "date",
                     class: "w-full bg-gray-50 border border-gray-200 rounded-xl px-4 py-3 text-base focus:outline-none focus:ring-2 focus:ring-blue-500 focus:bg-white transition-all",
                     value: "{expiry_date_str}",
-                    oninput: move |evt| expiry_date_str.set(evt.value())
+                    oninput: move |evt| {
+                        expiry_date_str.set(evt.value());
+                        expiry_touched.set(true);
+                        prefilled_days.set(None);
+                    }
+                }
+                if let Some((food, days, expiry)) = suggestion {
+                    div { class: "flex items-center gap-2 text-xs text-gray-500 mt-2",
+                        span { class: "material-symbols-outlined text-base text-amber-500", "lightbulb" }
+                        span { class: "flex-1",
+                            "{food.emoji()}{food.name_zh()} {location().label()}"
+                            if opened() { "开封后" } else { "未开封" }
+                            "建议 {days} 天（{expiry}）"
+                        }
+                        if expiry_date_str.read().as_str() != expiry.format("%Y-%m-%d").to_string() {
+                            button {
+                                class: "px-2 py-1 bg-amber-50 text-amber-700 rounded-lg font-medium hover:bg-amber-100 transition-colors",
+                                onclick: move |_| {
+                                    expiry_date_str.set(expiry.format("%Y-%m-%d").to_string());
+                                    prefilled_days.set(None);
+                                },
+                                "采用"
+                            }
+                        }
+                    }
+                } else if let Some(days) = prefilled_days() {
+                    p { class: "text-xs text-gray-400 mt-2",
+                        "已按分类默认保质期（{location().label()} {days} 天）预填"
+                    }
                 }
             }
 
             div { class: "flex-1" } // Push button to bottom
 
-            // 6. Submit
+            // 8. Submit
             button {
                 class: "w-full bg-blue-600 hover:bg-blue-700 text-white font-medium py-4 rounded-xl shadow-lg shadow-blue-600/20 transition-all active:scale-95 text-lg mt-4",
                 onclick: submit,
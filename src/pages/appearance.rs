@@ -0,0 +1,84 @@
+use crate::components::{status_card_class, status_icon};
+use crate::models::{ItemStatus, StatusPalette, Theme};
+use crate::router::Route;
+use crate::state::AppearanceState;
+use dioxus::prelude::*;
+
+// 预览配色时展示的状态和示例文本
+const PREVIEW: [(ItemStatus, &str); 4] = [
+    (ItemStatus::Expired, "🥛 牛奶 · 已过期 1 天"),
+    (ItemStatus::Critical, "🍞 面包 · 今天到期"),
+    (ItemStatus::Warning, "🍓 草莓 · 还剩 3 天"),
+    (ItemStatus::Safe, "🍎 苹果 · 还剩 10 天"),
+];
+
+/// 外观设置：浅色、深色或跟随系统，以及表示新鲜程度的配色
+#[component]
+pub fn AppearanceSettings() -> Element {
+    let mut appearance = use_context::<AppearanceState>().0;
+    let current = appearance();
+
+    rsx! {
+        div { class: "flex flex-col p-6 max-w-2xl mx-auto min-h-screen bg-white",
+            // Top Bar
+            div { class: "flex items-center mb-6",
+                Link { to: Route::Home { query: Default::default() }, class: "material-symbols-outlined text-gray-600 p-2 -ml-2 rounded-full hover:bg-gray-100 transition-colors", "arrow_back" }
+                h2 { class: "flex-1 text-center text-lg font-semibold text-gray-900 pr-8", "外观" }
+            }
+
+            // 主题
+            h3 { class: "text-sm font-medium text-gray-500 mb-2", "主题" }
+            div { class: "grid grid-cols-3 gap-2 mb-8", role: "radiogroup", "aria-label": "主题",
+                for theme in Theme::ALL {
+                    button {
+                        class: if current.theme == theme {
+                            "flex flex-col items-center gap-1 py-3 rounded-xl border-2 border-blue-500 bg-blue-50 text-blue-700 dark:bg-blue-950 dark:text-blue-300"
+                        } else {
+                            "flex flex-col items-center gap-1 py-3 rounded-xl border-2 border-gray-200 text-gray-600 hover:bg-gray-50"
+                        },
+                        role: "radio",
+                        "aria-checked": "{current.theme == theme}",
+                        onclick: move |_| appearance.write().theme = theme,
+                        span { class: "material-symbols-outlined", "{theme.icon()}" }
+                        span { class: "text-sm", "{theme.label()}" }
+                    }
+                }
+            }
+
+            // 新鲜程度配色
+            h3 { class: "text-sm font-medium text-gray-500 mb-2", "新鲜程度配色" }
+            div { class: "flex flex-col gap-2 mb-6", role: "radiogroup", "aria-label": "新鲜程度配色",
+                for palette in StatusPalette::ALL {
+                    button {
+                        class: if current.palette == palette {
+                            "flex flex-col items-start px-4 py-3 rounded-xl border-2 border-blue-500 bg-blue-50 dark:bg-blue-950 text-left"
+                        } else {
+                            "flex flex-col items-start px-4 py-3 rounded-xl border-2 border-gray-200 hover:bg-gray-50 text-left"
+                        },
+                        role: "radio",
+                        "aria-checked": "{current.palette == palette}",
+                        onclick: move |_| appearance.write().palette = palette,
+                        span { class: "text-sm font-medium text-gray-900", "{palette.label()}" }
+                        span { class: "text-xs text-gray-500", "{palette.description()}" }
+                    }
+                }
+            }
+
+            // 预览
+            h3 { class: "text-sm font-medium text-gray-500 mb-2", "预览" }
+            div { class: "flex flex-col",
+                for (status, text) in PREVIEW {
+                    div { class: "flex items-center justify-between rounded-xl mb-3 px-4 py-3 {status_card_class(status, current.palette)}",
+                        span { class: "text-gray-900", "{text}" }
+                        span { class: "flex items-center gap-1 text-sm font-bold text-gray-700",
+                            if current.palette != StatusPalette::Standard {
+                                span { class: "material-symbols-outlined text-base", "aria-hidden": "true", "{status_icon(status)}" }
+                            }
+                            "{status.label()}"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,358 @@
+use crate::components::{describe_error, ErrorPanel, PassphraseDialog};
+use crate::crypto::{CryptoError, DerivedKey};
+use crate::error::UkeepError;
+use crate::models::{BackupSchedule, Item, SnapshotKind};
+use crate::router::Route;
+use crate::state::{BackupState, ClockState, InventoryState, SnapshotState};
+use crate::storage::import_data_from_bytes;
+use crate::sync::{run_backup, webdav_client, RemoteSnapshot};
+use chrono::Local;
+use dioxus::prelude::*;
+
+/// WebDAV 备份设置页：配置、手动备份、从远程快照恢复
+#[component]
+pub fn BackupSettings() -> Element {
+    let mut inventory = use_context::<InventoryState>().0;
+    let mut settings = use_context::<BackupState>().0;
+    let mut local_snapshots = use_context::<SnapshotState>();
+    let clock = use_context::<ClockState>();
+
+    // 表单状态（保存前不影响全局设置）
+    let initial = settings.peek().clone();
+    let mut url = use_signal(|| initial.url.clone());
+    let mut username = use_signal(|| initial.username.clone());
+    let mut password = use_signal(|| initial.password.clone());
+    let mut keep_str = use_signal(|| initial.keep.to_string());
+    let mut schedule = use_signal(|| initial.schedule);
+    let mut passphrase = use_signal(String::new);
+
+    let mut busy = use_signal(|| false);
+    let mut message = use_signal(|| Option::<String>::None);
+    let mut error_message = use_signal(|| Option::<String>::None);
+    // 与服务器通信或恢复数据时的错误，以及出错的操作
+    let mut backup_error = use_signal(|| Option::<(&'static str, UkeepError)>::None);
+    let mut snapshots = use_signal(Vec::<RemoteSnapshot>::new);
+
+    // 等待输入口令的加密快照内容，以及解密失败提示
+    let mut pending_restore = use_signal(|| Option::<Vec<u8>>::None);
+    let mut restore_error = use_signal(|| Option::<String>::None);
+
+    let mut save_settings = move || {
+        let keep = keep_str.read().parse::<u32>().ok().filter(|k| *k >= 1).unwrap_or(1);
+        let mut current = settings.write();
+        current.url = url.read().trim().to_string();
+        current.username = username.read().trim().to_string();
+        current.password = password.read().clone();
+        current.keep = keep;
+        current.schedule = schedule();
+        keep_str.set(keep.to_string());
+    };
+
+    // 刷新远程快照列表
+    let refresh = move || {
+        let current = settings.peek().clone();
+        if !current.is_configured() {
+            return;
+        }
+        spawn(async move {
+            match webdav_client(&current).list().await {
+                Ok(list) => snapshots.set(list),
+                Err(e) => backup_error.set(Some(("读取备份列表失败", e))),
+            }
+        });
+    };
+
+    // 进入页面时加载一次快照列表
+    use_hook(refresh);
+
+    let handle_save = move |_| {
+        save_settings();
+        message.set(Some("设置已保存".to_string()));
+        error_message.set(None);
+        backup_error.set(None);
+        refresh();
+    };
+
+    let handle_backup_now = move |_| {
+        save_settings();
+        let current = settings.peek().clone();
+        if !current.is_configured() {
+            error_message.set(Some("请先填写 WebDAV 地址".to_string()));
+            return;
+        }
+        let items = inventory.peek().clone();
+        busy.set(true);
+        spawn(async move {
+            let now = Local::now().naive_local();
+            match run_backup(&current, &items, now).await {
+                Ok(name) => {
+                    settings.write().last_backup = Some(now);
+                    message.set(Some(format!("已备份为 {}", name)));
+                    error_message.set(None);
+                    backup_error.set(None);
+                    refresh();
+                }
+                Err(e) => backup_error.set(Some(("备份失败", e))),
+            }
+            busy.set(false);
+        });
+    };
+
+    // 设置备份口令：派生一次密钥并保存，之后的备份都会加密
+    let handle_set_passphrase = move |_| {
+        let value = passphrase.read().clone();
+        if value.is_empty() {
+            error_message.set(Some("请输入备份口令".to_string()));
+            return;
+        }
+        match DerivedKey::derive_random(&value) {
+            Ok(key) => {
+                settings.write().encryption = Some(key);
+                passphrase.set(String::new());
+                message.set(Some("之后的备份将使用口令加密".to_string()));
+                error_message.set(None);
+            }
+            Err(e) => backup_error.set(Some(("设置口令失败", e.into()))),
+        }
+    };
+
+    let mut finish_restore = move |result: Result<Vec<Item>, UkeepError>| match result {
+        Ok(items) => {
+            let count = items.len();
+            local_snapshots.capture(&inventory.peek(), SnapshotKind::BeforeRestore, clock.now());
+            inventory.set(items);
+            message.set(Some(format!("已恢复 {} 个物品", count)));
+            error_message.set(None);
+            backup_error.set(None);
+        }
+        Err(e) => backup_error.set(Some(("恢复失败", e))),
+    };
+
+    let mut restore = move |snapshot: RemoteSnapshot| {
+        let confirmed = web_sys::window()
+            .and_then(|w| w.confirm_with_message("恢复后当前库存将被替换，确定继续吗？").ok())
+            .unwrap_or(false);
+        if !confirmed {
+            return;
+        }
+        let current = settings.peek().clone();
+        busy.set(true);
+        spawn(async move {
+            match webdav_client(&current).download(&snapshot.name).await {
+                Ok(bytes) if snapshot.is_encrypted() => match current.encryption.as_ref().map(|key| key.decrypt(&bytes)) {
+                    Some(Ok(plain)) => finish_restore(import_data_from_bytes(&plain, None, clock.today())),
+                    // 口令已更换或未设置：询问当时使用的口令
+                    Some(Err(CryptoError::WrongPassphrase)) | None => pending_restore.set(Some(bytes)),
+                    Some(Err(e)) => finish_restore(Err(e.into())),
+                },
+                Ok(bytes) => finish_restore(import_data_from_bytes(&bytes, None, clock.today())),
+                Err(e) => finish_restore(Err(e)),
+            }
+            busy.set(false);
+        });
+    };
+
+    let confirm_restore = move |value: String| {
+        let Some(bytes) = pending_restore.read().clone() else {
+            return;
+        };
+        match import_data_from_bytes(&bytes, Some(&value), clock.today()) {
+            Ok(items) => {
+                pending_restore.set(None);
+                restore_error.set(None);
+                finish_restore(Ok(items));
+            }
+            Err(e) => restore_error.set(Some(describe_error(&e))),
+        }
+    };
+
+    let current = settings();
+    let last_backup = current
+        .last_backup
+        .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| "从未备份".to_string());
+    let input_class = "w-full bg-gray-50 border border-gray-200 rounded-xl px-4 py-3 text-base focus:outline-none focus:ring-2 focus:ring-blue-500 focus:bg-white transition-all";
+
+    rsx! {
+        div { class: "flex flex-col p-6 max-w-2xl mx-auto min-h-screen bg-white",
+            // Top Bar
+            div { class: "flex items-center mb-6",
+                Link { to: Route::Home { query: Default::default() }, class: "material-symbols-outlined text-gray-600 p-2 -ml-2 rounded-full hover:bg-gray-100 transition-colors", "arrow_back" }
+                h2 { class: "flex-1 text-center text-lg font-semibold text-gray-900 pr-8", "WebDAV 备份" }
+            }
+
+            p { class: "text-sm text-gray-500 mb-6",
+                "把库存定期备份到 Nextcloud 等 WebDAV 网盘，并只保留最近几份快照。上次备份：{last_backup}"
+            }
+
+            // 1. 连接信息
+            div { class: "flex flex-col mb-4",
+                label { class: "block text-sm font-medium text-gray-700 mb-2", "备份目录地址" }
+                input {
+                    r#type: "url",
+                    class: input_class,
+                    value: "{url}",
+                    oninput: move |evt| url.set(evt.value()),
+                    placeholder: "https://cloud.example.com/remote.php/dav/files/me/ukeep"
+                }
+            }
+            div { class: "flex gap-3 mb-4",
+                div { class: "flex flex-col flex-1",
+                    label { class: "block text-sm font-medium text-gray-700 mb-2", "用户名" }
+                    input {
+                        r#type: "text",
+                        class: input_class,
+                        value: "{username}",
+                        oninput: move |evt| username.set(evt.value())
+                    }
+                }
+                div { class: "flex flex-col flex-1",
+                    label { class: "block text-sm font-medium text-gray-700 mb-2", "应用密码" }
+                    input {
+                        r#type: "password",
+                        class: input_class,
+                        value: "{password}",
+                        oninput: move |evt| password.set(evt.value())
+                    }
+                }
+            }
+
+            // 2. 频率与轮换
+            div { class: "flex gap-3 mb-6",
+                div { class: "flex flex-col flex-1",
+                    label { class: "block text-sm font-medium text-gray-700 mb-2", "自动备份" }
+                    div { class: "flex gap-2",
+                        for option in BackupSchedule::ALL {
+                            button {
+                                class: if schedule() == option {
+                                    "px-3 py-2 rounded-lg text-sm font-medium bg-blue-600 text-white"
+                                } else {
+                                    "px-3 py-2 rounded-lg text-sm font-medium bg-gray-100 text-gray-700 hover:bg-gray-200"
+                                },
+                                onclick: move |_| schedule.set(option),
+                                "{option.label()}"
+                            }
+                        }
+                    }
+                }
+                div { class: "flex flex-col w-28",
+                    label { class: "block text-sm font-medium text-gray-700 mb-2", "保留份数" }
+                    input {
+                        r#type: "number",
+                        min: "1",
+                        step: "1",
+                        class: input_class,
+                        value: "{keep_str}",
+                        oninput: move |evt| keep_str.set(evt.value())
+                    }
+                }
+            }
+
+            // 3. 备份加密
+            div { class: "flex flex-col mb-6 p-4 rounded-xl border border-gray-100 bg-gray-50",
+                div { class: "flex items-center gap-2 mb-2",
+                    span { class: "material-symbols-outlined text-gray-500", if current.encryption.is_some() { "lock" } else { "lock_open" } }
+                    span { class: "text-sm font-medium text-gray-700",
+                        if current.encryption.is_some() { "备份已加密" } else { "备份未加密" }
+                    }
+                }
+                div { class: "flex items-center gap-3",
+                    input {
+                        r#type: "password",
+                        class: "flex-1 bg-white border border-gray-200 rounded-xl px-4 py-3 text-base focus:outline-none focus:ring-2 focus:ring-blue-500",
+                        value: "{passphrase}",
+                        oninput: move |evt| passphrase.set(evt.value()),
+                        placeholder: "备份口令"
+                    }
+                    button {
+                        class: "px-4 py-3 bg-gray-900 hover:bg-gray-700 text-white rounded-xl font-medium transition-colors",
+                        onclick: handle_set_passphrase,
+                        "设置"
+                    }
+                    if current.encryption.is_some() {
+                        button {
+                            class: "px-4 py-3 bg-gray-100 hover:bg-gray-200 text-gray-700 rounded-xl font-medium transition-colors",
+                            onclick: move |_| settings.write().encryption = None,
+                            "清除"
+                        }
+                    }
+                }
+            }
+
+            div { class: "flex gap-3 mb-6",
+                button {
+                    class: "flex-1 py-3 rounded-xl bg-gray-100 hover:bg-gray-200 text-gray-700 font-medium transition-colors",
+                    onclick: handle_save,
+                    "保存设置"
+                }
+                button {
+                    class: "flex-1 py-3 rounded-xl bg-blue-600 hover:bg-blue-700 text-white font-medium transition-colors disabled:opacity-50",
+                    disabled: busy(),
+                    onclick: handle_backup_now,
+                    "立即备份"
+                }
+            }
+
+            if let Some(msg) = message() {
+                div { class: "bg-green-50 text-green-700 p-4 rounded-xl mb-4 shadow-sm", "{msg}" }
+            }
+            if let Some(err) = error_message() {
+                div { class: "bg-red-50 text-red-700 p-4 rounded-xl mb-4 shadow-sm", "{err}" }
+            }
+            if let Some((title, error)) = backup_error() {
+                ErrorPanel {
+                    title: title.to_string(),
+                    error,
+                    on_dismiss: move |_| backup_error.set(None),
+                }
+            }
+
+            // 4. 远程快照
+            div { class: "flex flex-col mb-8",
+                div { class: "flex items-center justify-between mb-2",
+                    label { class: "text-sm font-medium text-gray-700", "远程快照" }
+                    button {
+                        class: "material-symbols-outlined p-1 rounded-full text-gray-500 hover:bg-gray-100",
+                        onclick: move |_| refresh(),
+                        "refresh"
+                    }
+                }
+                if snapshots.read().is_empty() {
+                    span { class: "text-sm text-gray-400", "暂无备份" }
+                }
+                for snapshot in snapshots() {
+                    div { key: "{snapshot.name}", class: "flex items-center justify-between py-3 border-b border-gray-100 last:border-b-0",
+                        div { class: "flex items-center gap-2",
+                            span { class: "material-symbols-outlined text-gray-400 text-xl",
+                                if snapshot.is_encrypted() { "lock" } else { "description" }
+                            }
+                            span { class: "text-sm text-gray-900",
+                                {snapshot.taken_at().map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string()).unwrap_or_else(|| snapshot.name.clone())}
+                            }
+                        }
+                        button {
+                            class: "px-3 py-1.5 bg-blue-50 text-blue-600 rounded-lg text-xs font-medium hover:bg-blue-100 transition-colors disabled:opacity-50",
+                            disabled: busy(),
+                            onclick: move |_| restore(snapshot.clone()),
+                            "恢复"
+                        }
+                    }
+                }
+            }
+        }
+
+        if pending_restore.read().is_some() {
+            PassphraseDialog {
+                title: "解密备份",
+                description: "这份快照使用了不同的口令加密，请输入当时的备份口令。",
+                confirm_label: "解密并恢复",
+                error: restore_error(),
+                on_submit: confirm_restore,
+                on_cancel: move |_| {
+                    pending_restore.set(None);
+                    restore_error.set(None);
+                },
+            }
+        }
+    }
+}
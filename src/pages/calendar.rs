@@ -0,0 +1,247 @@
+use crate::components::status_dot_class;
+use crate::models::{thresholds_of, InventoryQuery, Item, ItemStatus};
+use crate::router::Route;
+use crate::state::{AppearanceState, CategoryState, ClockState, InventoryState};
+use chrono::{Datelike, Duration, Months, NaiveDate};
+use dioxus::prelude::*;
+use uuid::Uuid;
+
+const WEEKDAYS: [&str; 7] = ["一", "二", "三", "四", "五", "六", "日"];
+
+// 月视图里每天最多显示的表情个数，其余折叠为 +N
+const MONTH_CELL_LIMIT: usize = 4;
+
+#[derive(Clone, Copy, PartialEq)]
+enum CalendarView {
+    Month,
+    Week,
+}
+
+/// 到期日历：按月或按周查看每天过期的物品，点日期筛选列表，拖动物品改过期日期
+#[component]
+pub fn Calendar() -> Element {
+    let mut inventory = use_context::<InventoryState>().0;
+    let categories = use_context::<CategoryState>().all();
+    let navigator = use_navigator();
+
+    let today = use_context::<ClockState>().today();
+    let palette = use_context::<AppearanceState>().0.read().palette;
+    let mut view = use_signal(|| CalendarView::Month);
+    // 当前显示的月或周中的任意一天
+    let mut anchor = use_signal(|| today);
+    // 正在拖动的物品及其原来的过期日期
+    let mut dragging = use_signal(|| Option::<(Uuid, NaiveDate)>::None);
+    let mut drop_target = use_signal(|| Option::<NaiveDate>::None);
+    let mut message = use_signal(|| Option::<String>::None);
+
+    let (start, end) = visible_range(view(), anchor());
+    let days: Vec<NaiveDate> = start.iter_days().take_while(|d| *d <= end).collect();
+    let title = match view() {
+        CalendarView::Month => format!("{}年{}月", anchor().year(), anchor().month()),
+        CalendarView::Week => format!("{} – {}", start.format("%m月%d日"), end.format("%m月%d日")),
+    };
+
+    // 每个物品在每个过期日期上只出现一次，多批同一天过期的合在一起
+    let items = inventory.read().clone();
+    let entries_on = |day: NaiveDate| -> Vec<(Item, &'static str)> {
+        items
+            .iter()
+            .filter(|item| item.batches().iter().any(|b| b.expiry_date() == day))
+            .map(|item| {
+                let days = (day - today).num_days();
+                // 按那天离今天的天数和分类阈值给表情上色
+                let status = ItemStatus::of(days, &thresholds_of(&categories, item));
+                (item.clone(), status_dot_class(status, palette))
+            })
+            .collect()
+    };
+
+    let mut step = move |forward: bool| {
+        let current = anchor();
+        let next = match view() {
+            CalendarView::Month if forward => current.checked_add_months(Months::new(1)),
+            CalendarView::Month => current.checked_sub_months(Months::new(1)),
+            CalendarView::Week if forward => Some(current + Duration::days(7)),
+            CalendarView::Week => Some(current - Duration::days(7)),
+        };
+        if let Some(next) = next {
+            anchor.set(next);
+        }
+    };
+
+    // 把拖动中的物品改到放下的那一天过期
+    let mut handle_drop = move |day: NaiveDate| {
+        drop_target.set(None);
+        let Some((id, from)) = dragging.take() else {
+            return;
+        };
+        if from == day {
+            return;
+        }
+        let mut items = inventory.write();
+        if let Some(item) = items.iter_mut().find(|i| i.id() == id) {
+            if item.reschedule(from, day) {
+                message.set(Some(format!("已把 {} 改为 {} 过期", item.name(), day.format("%m月%d日"))));
+            }
+        }
+    };
+
+    rsx! {
+        div { class: "flex flex-col p-6 max-w-2xl mx-auto min-h-screen bg-white",
+            // Top Bar
+            div { class: "flex items-center mb-6",
+                Link { to: Route::Home { query: Default::default() }, class: "material-symbols-outlined text-gray-600 p-2 -ml-2 rounded-full hover:bg-gray-100 transition-colors", "arrow_back" }
+                h2 { class: "flex-1 text-center text-lg font-semibold text-gray-900 pr-8", "到期日历" }
+            }
+
+            if let Some(msg) = message() {
+                div { class: "bg-green-50 text-green-700 p-4 rounded-xl mb-4 shadow-sm", "{msg}" }
+            }
+
+            // 1. 切换月份 / 周
+            div { class: "flex items-center gap-2 mb-4",
+                button {
+                    class: "material-symbols-outlined p-2 rounded-full text-gray-600 hover:bg-gray-100",
+                    onclick: move |_| step(false),
+                    "chevron_left"
+                }
+                span { class: "flex-1 text-center font-medium text-gray-900", "{title}" }
+                button {
+                    class: "material-symbols-outlined p-2 rounded-full text-gray-600 hover:bg-gray-100",
+                    onclick: move |_| step(true),
+                    "chevron_right"
+                }
+                button {
+                    class: "px-3 py-1 rounded-full text-sm text-blue-600 hover:bg-blue-50",
+                    onclick: move |_| anchor.set(today),
+                    "今天"
+                }
+                div { class: "flex rounded-full bg-gray-100 p-0.5 text-sm",
+                    for (mode, label) in [(CalendarView::Month, "月"), (CalendarView::Week, "周")] {
+                        button {
+                            class: if view() == mode {
+                                "px-3 py-1 rounded-full bg-white text-gray-900 shadow-sm"
+                            } else {
+                                "px-3 py-1 rounded-full text-gray-500"
+                            },
+                            onclick: move |_| view.set(mode),
+                            "{label}"
+                        }
+                    }
+                }
+            }
+
+            // 2. 日历格子
+            div { class: "grid grid-cols-7 gap-1 text-xs text-center text-gray-400 mb-1",
+                for weekday in WEEKDAYS {
+                    span { "{weekday}" }
+                }
+            }
+            div { class: "grid grid-cols-7 gap-1",
+                for day in days {
+                    div {
+                        key: "{day}",
+                        class: {
+                            let base = if view() == CalendarView::Month { "min-h-16" } else { "min-h-40" };
+                            let tone = if drop_target() == Some(day) {
+                                "bg-blue-50 ring-2 ring-blue-400"
+                            } else if view() == CalendarView::Month && day.month() != anchor().month() {
+                                "bg-gray-50 text-gray-300"
+                            } else {
+                                "bg-white border border-gray-100 hover:bg-gray-50"
+                            };
+                            format!("{base} {tone} flex flex-col gap-1 p-1 rounded-lg cursor-pointer transition-colors")
+                        },
+                        onclick: move |_| {
+                            navigator.push(Route::Home { query: InventoryQuery { expires: Some(day), ..Default::default() } });
+                        },
+                        ondragover: move |evt| {
+                            // 允许放下
+                            evt.prevent_default();
+                            if drop_target() != Some(day) {
+                                drop_target.set(Some(day));
+                            }
+                        },
+                        ondragleave: move |_| {
+                            if drop_target() == Some(day) {
+                                drop_target.set(None);
+                            }
+                        },
+                        ondrop: move |evt| {
+                            evt.prevent_default();
+                            handle_drop(day);
+                        },
+                        span {
+                            class: if day == today {
+                                "self-center w-6 h-6 leading-6 rounded-full bg-blue-600 text-white text-xs"
+                            } else {
+                                "self-center w-6 h-6 leading-6 text-xs"
+                            },
+                            "{day.day()}"
+                        }
+                        {
+                            let entries = entries_on(day);
+                            let limit = if view() == CalendarView::Month { MONTH_CELL_LIMIT } else { entries.len() };
+                            let hidden = entries.len().saturating_sub(limit);
+                            rsx! {
+                                div { class: "flex flex-wrap justify-center gap-0.5",
+                                    for (item, color) in entries.into_iter().take(limit) {
+                                        span {
+                                            key: "{item.id()}",
+                                            class: if view() == CalendarView::Month {
+                                                format!("{color} rounded px-0.5 cursor-grab")
+                                            } else {
+                                                format!("{color} w-full truncate rounded px-1 text-xs text-gray-700 cursor-grab")
+                                            },
+                                            title: "{item.name()}",
+                                            draggable: "true",
+                                            ondragstart: {
+                                                let id = item.id();
+                                                move |evt: DragEvent| {
+                                                    // 部分浏览器要求写入数据才会开始拖动
+                                                    let _ = evt.data_transfer().set_data("text/plain", &id.to_string());
+                                                    dragging.set(Some((id, day)));
+                                                }
+                                            },
+                                            ondragend: move |_| {
+                                                dragging.set(None);
+                                                drop_target.set(None);
+                                            },
+                                            "{item.emoji()}"
+                                            if view() == CalendarView::Week {
+                                                " {item.name()}"
+                                            }
+                                        }
+                                    }
+                                    if hidden > 0 {
+                                        span { class: "text-xs text-gray-400", "+{hidden}" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            p { class: "mt-4 text-xs text-gray-400", "点日期查看当天过期的物品，把物品拖到另一天可以修改过期日期" }
+        }
+    }
+}
+
+// 月视图从包含 1 号的那周一到包含月末的那周日，周视图为所在的一周
+fn visible_range(view: CalendarView, anchor: NaiveDate) -> (NaiveDate, NaiveDate) {
+    let (first, last) = match view {
+        CalendarView::Month => {
+            let first = anchor.with_day(1).unwrap_or(anchor);
+            let last = first
+                .checked_add_months(Months::new(1))
+                .map(|next| next - Duration::days(1))
+                .unwrap_or(first);
+            (first, last)
+        }
+        CalendarView::Week => (anchor, anchor),
+    };
+    let start = first - Duration::days(first.weekday().num_days_from_monday() as i64);
+    let end = last + Duration::days(6 - last.weekday().num_days_from_monday() as i64);
+    (start, end)
+}
@@ -0,0 +1,240 @@
+use crate::models::{Category, Location, ShelfLife, Thresholds};
+use crate::router::Route;
+use crate::state::CategoryState;
+use dioxus::prelude::*;
+
+// 空白或非正数表示不建议这样存放
+fn parse_days(value: &str) -> Option<i64> {
+    value.trim().parse::<i64>().ok().filter(|d| *d > 0)
+}
+
+fn days_text(days: Option<i64>) -> String {
+    days.map(|d| d.to_string()).unwrap_or_default()
+}
+
+/// 分类管理页：查看内置分类，新增、编辑和删除自定义分类
+#[component]
+pub fn Categories() -> Element {
+    let mut custom = use_context::<CategoryState>().0;
+
+    // 正在编辑的自定义分类，None 表示新增
+    let mut editing = use_signal(|| Option::<String>::None);
+    let mut name = use_signal(String::new);
+    let mut emoji = use_signal(|| "📦".to_string());
+    let mut keywords = use_signal(String::new);
+    // 冷藏 / 冷冻 / 常温的保质期输入
+    let mut shelf_days = use_signal(|| [String::new(), String::new(), String::new()]);
+    let mut warning_str = use_signal(|| Thresholds::default().warning_days.to_string());
+    let mut critical_str = use_signal(|| Thresholds::default().critical_days.to_string());
+    let mut error = use_signal(|| Option::<String>::None);
+
+    let mut reset_form = move || {
+        editing.set(None);
+        name.set(String::new());
+        emoji.set("📦".to_string());
+        keywords.set(String::new());
+        shelf_days.set([String::new(), String::new(), String::new()]);
+        warning_str.set(Thresholds::default().warning_days.to_string());
+        critical_str.set(Thresholds::default().critical_days.to_string());
+        error.set(None);
+    };
+
+    let mut start_edit = move |category: Category| {
+        editing.set(Some(category.id().to_string()));
+        name.set(category.name.clone());
+        emoji.set(category.emoji.clone());
+        keywords.set(category.keywords.join("，"));
+        shelf_days.set(Location::ALL.map(|l| days_text(category.shelf_life.for_location(l))));
+        warning_str.set(category.thresholds.warning_days.to_string());
+        critical_str.set(category.thresholds.critical_days.to_string());
+        error.set(None);
+    };
+
+    let handle_save = move |_| {
+        let title = name.read().trim().to_string();
+        if title.is_empty() {
+            error.set(Some("请输入分类名称".to_string()));
+            return;
+        }
+        let defaults = Thresholds::default();
+        let warning_days = warning_str.read().trim().parse::<i64>().ok().filter(|d| *d >= 0).unwrap_or(defaults.warning_days);
+        let critical_days = critical_str
+            .read()
+            .trim()
+            .parse::<i64>()
+            .ok()
+            .filter(|d| *d >= 0)
+            .unwrap_or(defaults.critical_days)
+            .min(warning_days);
+        let [fridge, freezer, pantry] = shelf_days.read().clone().map(|d| parse_days(&d));
+        let words: Vec<String> = keywords
+            .read()
+            .split([',', '，', ' '])
+            .map(|w| w.trim().to_string())
+            .filter(|w| !w.is_empty())
+            .collect();
+        let icon = Some(emoji.read().trim().to_string()).filter(|e| !e.is_empty()).unwrap_or_else(|| "📦".to_string());
+
+        let mut categories = custom.write();
+        let id = editing.read().clone();
+        let category = match id.and_then(|id| categories.iter().position(|c| c.id() == id)) {
+            Some(index) => &mut categories[index],
+            None => {
+                categories.push(Category::custom(title.clone(), icon.clone()));
+                categories.last_mut().expect("just pushed")
+            }
+        };
+        category.name = title;
+        category.emoji = icon;
+        category.keywords = words;
+        category.shelf_life = ShelfLife { fridge, freezer, pantry };
+        category.thresholds = Thresholds { warning_days, critical_days };
+        drop(categories);
+        reset_form();
+    };
+
+    let all = Category::all(&custom.read());
+    let input_class = "bg-gray-50 border border-gray-200 rounded-xl px-3 py-2 text-base focus:outline-none focus:ring-2 focus:ring-blue-500 focus:bg-white transition-all";
+
+    rsx! {
+        div { class: "flex flex-col p-6 max-w-2xl mx-auto min-h-screen bg-white",
+            // Top Bar
+            div { class: "flex items-center mb-6",
+                Link { to: Route::Home { query: Default::default() }, class: "material-symbols-outlined text-gray-600 p-2 -ml-2 rounded-full hover:bg-gray-100 transition-colors", "arrow_back" }
+                h2 { class: "flex-1 text-center text-lg font-semibold text-gray-900 pr-8", "分类" }
+            }
+
+            // 1. 分类列表
+            for category in all {
+                div { key: "{category.id()}", class: "flex items-center gap-3 py-3 border-b border-gray-100 last:border-b-0",
+                    span { class: "text-2xl", "{category.emoji()}" }
+                    div { class: "flex-1 flex flex-col",
+                        span { class: "text-gray-900", "{category.name()}" }
+                        span { class: "text-xs text-gray-400",
+                            for location in Location::ALL {
+                                span { class: "mr-2",
+                                    "{location.label()} "
+                                    match category.shelf_life.for_location(location) {
+                                        Some(days) => rsx! { "{days} 天" },
+                                        None => rsx! { "—" },
+                                    }
+                                }
+                            }
+                            "· 临期 {category.thresholds.warning_days} 天 / 紧急 {category.thresholds.critical_days} 天"
+                        }
+                    }
+                    if category.is_builtin() {
+                        span { class: "text-xs text-gray-300", "内置" }
+                    } else {
+                        button {
+                            class: "material-symbols-outlined p-1 rounded-full text-gray-400 hover:bg-gray-100",
+                            onclick: {
+                                let category = category.clone();
+                                move |_| start_edit(category.clone())
+                            },
+                            "edit"
+                        }
+                        button {
+                            class: "material-symbols-outlined p-1 rounded-full text-gray-400 hover:bg-gray-100",
+                            onclick: {
+                                let id = category.id().to_string();
+                                move |_| {
+                                    custom.write().retain(|c| c.id() != id);
+                                    if editing.read().as_deref() == Some(id.as_str()) {
+                                        reset_form();
+                                    }
+                                }
+                            },
+                            "delete"
+                        }
+                    }
+                }
+            }
+
+            // 2. 新增 / 编辑自定义分类
+            div { class: "flex flex-col gap-3 mt-8",
+                h3 { class: "text-sm font-medium text-gray-700",
+                    if editing.read().is_some() { "编辑分类" } else { "新增分类" }
+                }
+                div { class: "flex gap-3",
+                    input {
+                        r#type: "text",
+                        class: "w-16 text-center {input_class}",
+                        "aria-label": "默认表情",
+                        value: "{emoji}",
+                        oninput: move |evt| emoji.set(evt.value())
+                    }
+                    input {
+                        r#type: "text",
+                        class: "flex-1 min-w-0 {input_class}",
+                        value: "{name}",
+                        oninput: move |evt| name.set(evt.value()),
+                        placeholder: "分类名称，例如：冻品"
+                    }
+                }
+                input {
+                    r#type: "text",
+                    class: "{input_class}",
+                    value: "{keywords}",
+                    oninput: move |evt| keywords.set(evt.value()),
+                    placeholder: "关键词，用逗号分隔，例如：饺子，汤圆"
+                }
+                label { class: "text-xs text-gray-500", "默认保质期（天，留空表示不适合）" }
+                div { class: "flex gap-3",
+                    for (index, location) in Location::ALL.into_iter().enumerate() {
+                        div { class: "flex flex-col flex-1 min-w-0",
+                            span { class: "text-xs text-gray-400 mb-1", "{location.label()}" }
+                            input {
+                                r#type: "number",
+                                min: "1",
+                                class: "w-full {input_class}",
+                                value: "{shelf_days.read()[index]}",
+                                oninput: move |evt| shelf_days.write()[index] = evt.value()
+                            }
+                        }
+                    }
+                }
+                label { class: "text-xs text-gray-500", "临期提醒（剩余天数不超过时变色）" }
+                div { class: "flex gap-3",
+                    div { class: "flex flex-col flex-1 min-w-0",
+                        span { class: "text-xs text-amber-500 mb-1", "黄色" }
+                        input {
+                            r#type: "number",
+                            min: "0",
+                            class: "w-full {input_class}",
+                            value: "{warning_str}",
+                            oninput: move |evt| warning_str.set(evt.value())
+                        }
+                    }
+                    div { class: "flex flex-col flex-1 min-w-0",
+                        span { class: "text-xs text-red-500 mb-1", "红色" }
+                        input {
+                            r#type: "number",
+                            min: "0",
+                            class: "w-full {input_class}",
+                            value: "{critical_str}",
+                            oninput: move |evt| critical_str.set(evt.value())
+                        }
+                    }
+                }
+                if let Some(msg) = error() {
+                    div { class: "bg-red-50 text-red-600 p-3 rounded-xl text-sm", "{msg}" }
+                }
+                div { class: "flex gap-3",
+                    if editing.read().is_some() {
+                        button {
+                            class: "flex-1 bg-gray-100 hover:bg-gray-200 text-gray-700 font-medium py-3 rounded-xl transition-colors",
+                            onclick: move |_| reset_form(),
+                            "取消"
+                        }
+                    }
+                    button {
+                        class: "flex-1 bg-blue-600 hover:bg-blue-700 text-white font-medium py-3 rounded-xl shadow-lg shadow-blue-600/20 transition-all active:scale-95",
+                        onclick: handle_save,
+                        "保存"
+                    }
+                }
+            }
+        }
+    }
+}
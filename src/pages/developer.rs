@@ -0,0 +1,64 @@
+use crate::router::Route;
+use crate::state::ClockState;
+use chrono::{Duration, NaiveDate};
+use dioxus::prelude::*;
+
+/// 开发者设置：模拟某一天，预览到那天时哪些物品临期、过期
+#[component]
+pub fn DeveloperSettings() -> Element {
+    let mut clock = use_context::<ClockState>();
+    let simulated = clock.simulated_date();
+    let today = clock.today();
+
+    rsx! {
+        div { class: "flex flex-col p-6 max-w-2xl mx-auto min-h-screen bg-white",
+            // Top Bar
+            div { class: "flex items-center mb-6",
+                Link { to: Route::Home { query: Default::default() }, class: "material-symbols-outlined text-gray-600 p-2 -ml-2 rounded-full hover:bg-gray-100 transition-colors", "arrow_back" }
+                h2 { class: "flex-1 text-center text-lg font-semibold text-gray-900 pr-8", "开发者选项" }
+            }
+
+            div { class: "flex flex-col gap-4",
+                div { class: "flex items-center gap-3",
+                    span { class: "material-symbols-outlined text-gray-400", "event" }
+                    label { class: "flex-1 text-sm text-gray-700", r#for: "simulated-date", "模拟日期" }
+                    input {
+                        id: "simulated-date",
+                        r#type: "date",
+                        class: "w-44 bg-gray-50 border border-gray-200 rounded-xl px-3 py-2 text-base focus:outline-none focus:ring-2 focus:ring-blue-500",
+                        value: "{today.format(\"%Y-%m-%d\")}",
+                        onchange: move |evt| {
+                            if let Ok(date) = NaiveDate::parse_from_str(&evt.value(), "%Y-%m-%d") {
+                                clock.simulate(Some(date));
+                            }
+                        },
+                    }
+                }
+                div { class: "flex flex-wrap gap-2",
+                    for days in [1, 3, 7, 30] {
+                        button {
+                            class: "px-3 py-1 rounded-full text-sm bg-gray-100 hover:bg-gray-200 text-gray-700 transition-colors",
+                            onclick: move |_| {
+                                let today = clock.today();
+                                clock.simulate(Some(today + Duration::days(days)));
+                            },
+                            "+{days} 天"
+                        }
+                    }
+                }
+            }
+
+            if simulated.is_some() {
+                button {
+                    class: "mt-6 self-start text-sm text-blue-600 hover:underline",
+                    onclick: move |_| clock.simulate(None),
+                    "恢复真实日期"
+                }
+            }
+
+            p { class: "mt-4 text-xs text-gray-400",
+                "模拟日期只影响剩余天数、临期提醒和默认日期，操作记录和备份仍使用真实时间。"
+            }
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use crate::models::{CardAction, SwipeSettings};
+use crate::router::Route;
+use crate::state::SwipeState;
+use dioxus::prelude::*;
+
+/// 滑动操作设置：为左右滑动分别选择要执行的操作
+#[component]
+pub fn GestureSettings() -> Element {
+    let mut swipe = use_context::<SwipeState>().0;
+    let settings = swipe();
+
+    rsx! {
+        div { class: "flex flex-col p-6 max-w-2xl mx-auto min-h-screen bg-white",
+            // Top Bar
+            div { class: "flex items-center mb-6",
+                Link { to: Route::Home { query: Default::default() }, class: "material-symbols-outlined text-gray-600 p-2 -ml-2 rounded-full hover:bg-gray-100 transition-colors", "arrow_back" }
+                h2 { class: "flex-1 text-center text-lg font-semibold text-gray-900 pr-8", "滑动操作" }
+            }
+
+            div { class: "flex flex-col gap-4",
+                for (label, icon, current, is_right) in [
+                    ("向右滑", "east", settings.right, true),
+                    ("向左滑", "west", settings.left, false),
+                ] {
+                    div { class: "flex items-center gap-3",
+                        span { class: "material-symbols-outlined text-gray-400", "{icon}" }
+                        label { class: "flex-1 text-sm text-gray-700", r#for: "swipe-{icon}", "{label}" }
+                        select {
+                            id: "swipe-{icon}",
+                            class: "w-44 bg-gray-50 border border-gray-200 rounded-xl px-3 py-2 text-base focus:outline-none focus:ring-2 focus:ring-blue-500",
+                            onchange: move |evt| {
+                                if let Some(action) = CardAction::from_key(&evt.value()) {
+                                    let mut settings = swipe.write();
+                                    if is_right {
+                                        settings.right = action;
+                                    } else {
+                                        settings.left = action;
+                                    }
+                                }
+                            },
+                            for action in CardAction::ALL {
+                                option { value: action.key(), selected: current == action, "{action.label()}" }
+                            }
+                        }
+                    }
+                }
+            }
+
+            if settings != SwipeSettings::default() {
+                button {
+                    class: "mt-6 self-start text-sm text-blue-600 hover:underline",
+                    onclick: move |_| swipe.set(SwipeSettings::default()),
+                    "恢复默认（右滑吃掉，左滑扔掉）"
+                }
+            }
+
+            p { class: "mt-4 text-xs text-gray-400",
+                "右滑设为“吃掉了”时可以在弹出的菜单中选择数量。长按物品或点右侧的 ⋮ 可以打开包含所有操作的菜单。"
+            }
+        }
+    }
+}
@@ -1,18 +1,67 @@
-use crate::components::ItemCard;
+use crate::components::{
+    describe_error, focus_card, scroll_row_into_view, use_visible_rows, ErrorPanel, ItemCard, PassphraseDialog, WasteDialog,
+};
+use crate::error::{FileProblem, UkeepError};
+use crate::crypto;
+use crate::keyboard::KeyCommand;
+use crate::models::{
+    known_shelf_life, shelf_life_of, thresholds_of, usual_amount, BulkAction, BulkOperation, CardAction, Category, EventKind, HistoryEvent, InventoryQuery,
+    Item, Location, Quantity, ShoppingEntry, SnapshotKind, SortOrder, SortedInventory, StatusFilter, WasteReason,
+};
 use crate::router::Route;
-use crate::state::InventoryState;
-use crate::storage::{export_data, import_data_from_json};
-use chrono::{Local, Timelike};
+use crate::state::{
+    CategoryState, ClockState, HistoryState, PlanState, HouseholdState, InventoryState, ShoppingState, SnapshotState, SwipeState,
+};
+use crate::storage::{
+    enter_demo_mode, exit_demo_mode, export_data, export_history, import_data_from_bytes, import_items_from_csv, is_demo_mode,
+};
+use crate::utils::generate_mock_data;
+use crate::virtual_list::padding;
+use chrono::Timelike;
 use dioxus::prelude::*;
-use gloo_file::callbacks::{read_as_text, FileReader};
+use gloo_file::callbacks::{read_as_bytes, FileReader};
 use gloo_file::File;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::rc::Rc;
 use uuid::Uuid;
 use wasm_bindgen::JsCast;
 use web_sys::HtmlInputElement;
 
+// 库存列表元素的 id，用于计算可见行
+const LIST_ID: &str = "inventory-list";
+
 #[component]
-pub fn Home() -> Element {
+pub fn Home(query: InventoryQuery) -> Element {
     let mut inventory = use_context::<InventoryState>().0;
+    let mut history = use_context::<HistoryState>().0;
+    let household = use_context::<HouseholdState>();
+    let mut snapshots = use_context::<SnapshotState>();
+    let mut shopping = use_context::<ShoppingState>();
+    let category_state = use_context::<CategoryState>();
+    let plan = use_context::<PlanState>();
+    let swipe = use_context::<SwipeState>().0;
+    let clock = use_context::<ClockState>();
+    let all_categories = category_state.all();
+    // 演示模式只能通过重新加载页面进入或退出，页面存在期间不会变化
+    let demo = use_hook(is_demo_mode);
+
+    // 最后一件用完后，询问是否加入购物清单
+    let mut restock_offer = use_signal(|| Option::<ShoppingEntry>::None);
+
+    // 卡片操作完成后的提示
+    let mut notice = use_signal(|| Option::<String>::None);
+
+    // 读屏软件朗读的最近一次操作结果（aria-live）
+    let mut announcement = use_signal(String::new);
+
+    // 键盘焦点所在的卡片，列表中只有这一张可以用 Tab 聚焦
+    let mut active_card = use_signal(|| Option::<Uuid>::None);
+    // 键盘移动到还没有渲染的卡片时，等它挂载后再聚焦
+    let mut focus_request = use_signal(|| Option::<Uuid>::None);
+
+    // 左滑后等待选择扔掉原因的物品
+    let mut wasting = use_signal(|| Option::<Item>::None);
 
     // 保持 FileReader 存活，防止异步导入回调被取消
     let file_reader_slot = use_signal(|| Option::<FileReader>::None);
@@ -23,41 +72,261 @@ pub fn Home() -> Element {
     // 错误提示信息
     let mut error_message = use_signal(|| Option::<String>::None);
 
-    // 排序逻辑：按剩余天数升序 (快过期的在前面)
-    let mut sorted_items = inventory.read().clone();
-    sorted_items.sort_by_key(|item| item.days_remaining());
+    // 导出时的加密口令弹窗
+    let mut show_export_dialog = use_signal(|| false);
+
+    // 等待输入口令的加密备份内容，以及解密失败的提示
+    let mut pending_import = use_signal(|| Option::<Vec<u8>>::None);
+    let mut import_error = use_signal(|| Option::<String>::None);
+    // 导入导出失败：出错的操作和错误，详细信息可以展开查看
+    let mut file_error = use_signal(|| Option::<(&'static str, UkeepError)>::None);
+
+    // 多选模式：选中的物品、批量改期的天数，以及最近一次可撤销的批量操作
+    let mut selecting = use_signal(|| false);
+    let mut selected = use_signal(Vec::<Uuid>::new);
+    let mut shift_days = use_signal(|| "1".to_string());
+    let mut last_bulk = use_signal(|| Option::<BulkOperation>::None);
+
+    // 筛选排序后的列表：库存或条件变化时增量更新，不必每次渲染都复制和排序整个库存
+    let listing = use_hook(|| Rc::new(RefCell::new(SortedInventory::new(query.clone()))));
+    let visible_ids = use_memo(use_reactive((&query,), {
+        let listing = listing.clone();
+        move |(query,)| {
+            let mut listing = listing.borrow_mut();
+            if *listing.query() != query {
+                *listing = SortedInventory::new(query);
+            }
+            listing.sync(&inventory.read(), &category_state.all(), clock.today());
+            listing.ids().collect::<Vec<Uuid>>()
+        }
+    }));
+    let count = visible_ids.read().len();
+
+    // 只渲染视口附近的卡片，上下用空白撑开
+    let visible_rows = use_visible_rows(LIST_ID);
+    let range = visible_rows.range(count);
+    let (pad_top, pad_bottom) = padding(&range, count);
+    let window_items: Vec<(usize, Item)> = {
+        let items = inventory.read();
+        let listing = listing.borrow();
+        range
+            .filter_map(|index| {
+                let position = listing.position(listing.id_at(index)?)?;
+                Some((index, items.get(position)?.clone()))
+            })
+            .collect()
+    };
+
+    // 多选工具栏的“全选”只选当前筛选出来的物品
+    let all_selected = {
+        let chosen: HashSet<Uuid> = selected.read().iter().copied().collect();
+        count > 0 && visible_ids.read().iter().all(|id| chosen.contains(id))
+    };
+    // 焦点所在的卡片被移除或筛掉后，由第一张卡片接替
+    let tab_stop = active_card()
+        .filter(|id| listing.borrow().index_of(*id).is_some())
+        .or(visible_ids.read().first().copied());
+
+    // 在卡片上按方向键：按当前列表顺序移动焦点，目标不在渲染范围内时先滚动过去
+    let navigate = Callback::new(move |(id, command): (Uuid, KeyCommand)| {
+        let ids = visible_ids.read();
+        let Some(index) = ids.iter().position(|i| *i == id) else {
+            return;
+        };
+        if let Some(target) = command.move_focus(index, ids.len()) {
+            let target_id = ids[target];
+            active_card.set(Some(target_id));
+            focus_request.set(Some(target_id));
+            scroll_row_into_view(LIST_ID, target);
+            focus_card(target_id);
+        }
+    });
+
+    // 搜索和筛选条件保存在 URL 中，修改时替换当前历史记录
+    let navigator = use_navigator();
+    let set_query = move |query: InventoryQuery| {
+        navigator.replace(Route::Home { query });
+    };
+
+    // 只显示库存里实际用到的分类；统计多少个即将过期（按各自分类的临期阈值）
+    let (categories, urgent_count) = {
+        let items = inventory.read();
+        let used: HashSet<&str> = items.iter().filter_map(|item| item.category()).collect();
+        let categories: Vec<Category> = all_categories.iter().filter(|c| used.contains(c.id())).cloned().collect();
+        let today = clock.today();
+        let urgent_count = items
+            .iter()
+            .filter(|i| i.days_remaining(today) <= thresholds_of(&all_categories, i).warning_days)
+            .count();
+        (categories, urgent_count)
+    };
 
-    // 统计：多少个即将过期 (<=3天)
-    let urgent_count = sorted_items
-        .iter()
-        .filter(|i| i.days_remaining() <= 3)
-        .count();
+    // 物品被移除后，如果库存里已经没有同类物品，提示加入购物清单
+    let mut offer_restock = move |item: &Item, remaining: &[Item]| {
+        let mut entry = ShoppingEntry::from_item(item, None, clock.now());
+        if remaining.iter().any(|i| entry.matches(i)) {
+            return;
+        }
+        let events = history.peek();
+        let shelf_life = shelf_life_of(item, &events).or_else(|| known_shelf_life(&entry, remaining, &events));
+        entry.set_shelf_life_days(shelf_life);
+        restock_offer.set(Some(entry));
+    };
 
     // Handler: 模拟 "吃掉了"
-    let consume_item = move |(id, count): (Uuid, u32)| {
+    let consume_item = move |(id, amount): (Uuid, f64)| {
         let mut items = inventory.write();
         if let Some(index) = items.iter().position(|i| i.id() == id) {
-            if items[index].consume_n(count) {
+            let total = items[index].quantity();
+            let count = Quantity::new(amount.min(total.amount()), total.unit());
+            let before = items[index].clone();
+            let shortfall = plan.shortfall(&before, count.amount());
+            let removed = items[index].consume(count.amount());
+            announcement.set(removal_message("吃掉", &before, count, (!removed).then(|| items[index].quantity())));
+            if shortfall > 0.0 {
+                notice.set(Some(reservation_warning(&before, Quantity::new(shortfall, total.unit()))));
+            }
+            if removed {
                 items.remove(index);
+                offer_restock(&before, &items);
             }
+            // 记录吃掉部分的价值
+            let value = before.value_of(count.amount());
+            history.write().push(
+                HistoryEvent::new(&before, EventKind::Consumed, count, removed, household.member()).with_value(value),
+            );
         }
     };
 
-    // Handler: 模拟 "扔掉了"
+    // Handler: 模拟 "扔掉了"，先询问原因和数量
     let waste_item = move |id: Uuid| {
-        inventory.write().retain(|i| i.id() != id);
+        wasting.set(inventory.read().iter().find(|i| i.id() == id).cloned());
+    };
+
+    let confirm_waste = move |(amount, reason, note): (f64, Option<WasteReason>, Option<String>)| {
+        let Some(id) = wasting.take().map(|i| i.id()) else {
+            return;
+        };
+        let mut items = inventory.write();
+        if let Some(index) = items.iter().position(|i| i.id() == id) {
+            let total = items[index].quantity();
+            let count = Quantity::new(amount.min(total.amount()), total.unit());
+            let before = items[index].clone();
+            let shortfall = plan.shortfall(&before, count.amount());
+            let removed = items[index].consume(count.amount());
+            announcement.set(removal_message("扔掉", &before, count, (!removed).then(|| items[index].quantity())));
+            if shortfall > 0.0 {
+                notice.set(Some(reservation_warning(&before, Quantity::new(shortfall, total.unit()))));
+            }
+            if removed {
+                items.remove(index);
+                offer_restock(&before, &items);
+            }
+            focus_card(id);
+            // 只计算扔掉部分的价值，已经吃掉的部分不算浪费
+            history.write().push(
+                HistoryEvent::new(&before, EventKind::Wasted, count, removed, household.member())
+                    .with_value(before.value_of(count.amount()))
+                    .with_reason(reason, note),
+            );
+        }
+    };
+
+    // 卡片上的其它操作：加入购物清单，或冷冻、开封、移动等只改物品本身的操作
+    let run_action = move |(id, action): (Uuid, CardAction)| {
+        let Some(item) = inventory.peek().iter().find(|i| i.id() == id).cloned() else {
+            return;
+        };
+        if action == CardAction::AddToShopping {
+            let mut entry = ShoppingEntry::from_item(&item, None, clock.now());
+            entry.set_shelf_life_days(shelf_life_of(&item, &history.peek()));
+            shopping.add(entry);
+            let message = format!("已把 {} 加入购物清单", item.name());
+            announcement.set(message.clone());
+            notice.set(Some(message));
+            return;
+        }
+        let today = clock.today();
+        let mut items = inventory.write();
+        if let Some(item) = items.iter_mut().find(|i| i.id() == id) {
+            if action.apply(item, &category_state.all(), today) {
+                let message = format!("{}：{}", action.label(), item.name());
+                announcement.set(message.clone());
+                notice.set(Some(message));
+            }
+        }
+    };
+
+    // 操作菜单中选“多选”或多选模式下点击：切换选中，并进入多选模式
+    let toggle_select = move |id: Uuid| {
+        selecting.set(true);
+        let mut ids = selected.write();
+        match ids.iter().position(|s| *s == id) {
+            Some(index) => {
+                ids.remove(index);
+            }
+            None => ids.push(id),
+        }
     };
 
-    // Handler: 导出数据
+    let mut exit_selecting = move || {
+        selecting.set(false);
+        selected.write().clear();
+    };
+
+    // 对选中的物品执行批量操作：先保存一份快照，整个操作只占一条撤销记录
+    let mut run_bulk = move |action: BulkAction| {
+        let ids = selected.peek().clone();
+        if ids.is_empty() {
+            return;
+        }
+        snapshots.capture(&inventory.peek(), SnapshotKind::BeforeBulk, clock.now());
+        let operation = BulkOperation::apply(action, &ids, &mut inventory.write(), household.member());
+        history.write().extend(operation.events().iter().cloned());
+        restock_offer.set(None);
+        announcement.set(operation.describe());
+        last_bulk.set(Some(operation));
+        exit_selecting();
+    };
+
+    let mut shift_selected = move |sign: i64| match shift_days.read().trim().parse::<i64>() {
+        Ok(days) if days > 0 => run_bulk(BulkAction::ShiftExpiry(sign * days)),
+        _ => error_message.set(Some("请输入有效的天数".to_string())),
+    };
+
+    // Handler: 导出数据（先询问是否加密）
     let handle_export = move |_| {
+        show_settings.set(false);
+        show_export_dialog.set(true);
+    };
+
+    // Handler: 确认导出，口令为空时导出明文 JSON
+    let confirm_export = move |passphrase: String| {
+        show_export_dialog.set(false);
         let items = inventory.read().clone();
-        match export_data(&items) {
-            Ok(_) => {
-                show_settings.set(false);
-            }
-            Err(e) => {
-                error_message.set(Some(format!("导出失败: {}", e)));
+        let passphrase = (!passphrase.is_empty()).then_some(passphrase);
+        match export_data(&items, passphrase.as_deref(), clock.now()) {
+            Ok(()) => file_error.set(None),
+            Err(e) => file_error.set(Some(("导出失败", e))),
+        }
+    };
+
+    // Handler: 输入口令后解密导入
+    let confirm_import = move |passphrase: String| {
+        let Some(bytes) = pending_import.read().clone() else {
+            return;
+        };
+        match import_data_from_bytes(&bytes, Some(&passphrase), clock.today()) {
+            Ok(items) => {
+                // 导入前先保存一份快照，便于撤回
+                snapshots.capture(&inventory.peek(), SnapshotKind::BeforeImport, clock.now());
+                inventory.set(items);
+                pending_import.set(None);
+                import_error.set(None);
+                file_error.set(None);
             }
+            // 保留弹窗，让用户重新输入口令
+            Err(e) => import_error.set(Some(describe_error(&e))),
         }
     };
 
@@ -65,7 +334,7 @@ pub fn Home() -> Element {
     let handle_import = move |_| {
         show_settings.set(false);
 
-        let mut reader_slot = file_reader_slot.clone();
+        let mut reader_slot = file_reader_slot;
 
         // 创建隐藏的文件输入元素
         if let Some(window) = web_sys::window() {
@@ -73,10 +342,11 @@ pub fn Home() -> Element {
                 if let Ok(input) = document.create_element("input") {
                     if let Ok(input) = input.dyn_into::<HtmlInputElement>() {
                         input.set_type("file");
-                        input.set_accept(".json");
+                        input.set_accept(".json,.ukeep,.csv");
 
-                        let inventory_clone = inventory.clone();
-                        let error_msg_clone = error_message.clone();
+                        let inventory_clone = inventory;
+                        let error_msg_clone = error_message;
+                        let file_error_clone = file_error;
 
                         let onchange = wasm_bindgen::closure::Closure::wrap(Box::new(move |event: web_sys::Event| {
                             if let Some(target) = event.target() {
@@ -84,20 +354,59 @@ pub fn Home() -> Element {
                                     if let Some(files) = input.files() {
                                         if let Some(file) = files.get(0) {
                                             let file = File::from(file);
-                                            let mut inventory_inner = inventory_clone.clone();
-                                            let mut error_inner = error_msg_clone.clone();
-                                            let mut reader_slot_inner = reader_slot.clone();
+                                            let is_csv = file.name().to_lowercase().ends_with(".csv");
+                                            let mut inventory_inner = inventory_clone;
+                                            let mut snapshots_inner = snapshots;
+                                            let mut error_inner = error_msg_clone;
+                                            let mut file_error_inner = file_error_clone;
+                                            let mut reader_slot_inner = reader_slot;
 
-                                            let reader = read_as_text(&file, move |result| {
+                                            let mut pending_inner = pending_import;
+
+                                            let reader = read_as_bytes(&file, move |result| {
                                                 match result {
-                                                    Ok(text) => {
-                                                        match import_data_from_json(&text) {
+                                                    // CSV：作为新物品入库，与现有库存合并
+                                                    Ok(bytes) if is_csv => {
+                                                        let parsed = String::from_utf8(bytes)
+                                                            .map_err(|_| UkeepError::InvalidFile(FileProblem::Encoding))
+                                                            .and_then(|text| import_items_from_csv(&text, &category_state.all(), clock.today()));
+                                                        match parsed {
+                                                            Ok(items) => {
+                                                                snapshots_inner.capture(&inventory_inner.peek(), SnapshotKind::BeforeImport, clock.now());
+                                                                let mut state = InventoryState(inventory_inner);
+                                                                for item in items {
+                                                                    let quantity = item.quantity();
+                                                                    let stocked = state.stock(item);
+                                                                    history.write().push(HistoryEvent::new(
+                                                                        &stocked,
+                                                                        EventKind::Added,
+                                                                        quantity,
+                                                                        false,
+                                                                        household.member(),
+                                                                    ));
+                                                                }
+                                                                error_inner.set(None);
+                                                                file_error_inner.set(None);
+                                                            }
+                                                            Err(e) => {
+                                                                file_error_inner.set(Some(("导入失败", e)));
+                                                            }
+                                                        }
+                                                    }
+                                                    // 加密备份：先弹窗询问口令
+                                                    Ok(bytes) if crypto::is_encrypted(&bytes) => {
+                                                        pending_inner.set(Some(bytes));
+                                                    }
+                                                    Ok(bytes) => {
+                                                        match import_data_from_bytes(&bytes, None, clock.today()) {
                                                             Ok(items) => {
+                                                                snapshots_inner.capture(&inventory_inner.peek(), SnapshotKind::BeforeImport, clock.now());
                                                                 inventory_inner.set(items);
                                                                 error_inner.set(None);
+                                                                file_error_inner.set(None);
                                                             }
                                                             Err(e) => {
-                                                                error_inner.set(Some(format!("导入失败: {}", e)));
+                                                                file_error_inner.set(Some(("导入失败", e)));
                                                             }
                                                         }
                                                     }
@@ -127,7 +436,7 @@ pub fn Home() -> Element {
     };
 
     let greeting = {
-        let now = Local::now();
+        let now = clock.now();
         let (hour, minute) = (now.hour(), now.minute());
         let total = hour * 60 + minute;
         match total {
@@ -149,6 +458,7 @@ pub fn Home() -> Element {
                     span { class: "text-sm text-gray-500 mt-1 block",
                         if urgent_count > 0 {
                             "⚠️ 有 {urgent_count} 个物品需要尽快处理"
+                            Link { to: Route::Recipes {}, class: "ml-2 text-blue-600 hover:underline", "看看能做什么" }
                         } else {
                             "👏 一切看起来都很新鲜"
                         }
@@ -193,11 +503,170 @@ pub fn Home() -> Element {
                                 span { class: "material-symbols-outlined text-green-500 text-xl", "upload" }
                                 span { "导入数据" }
                             }
+
+                            // 导出记录
+                            button {
+                                class: "w-full text-left px-4 py-3 hover:bg-gray-50 flex items-center gap-3 text-sm text-gray-700 transition-colors",
+                                onclick: move |_| {
+                                    show_settings.set(false);
+                                    match export_history(&history.read(), clock.now()) {
+                                        Ok(()) => file_error.set(None),
+                                        Err(e) => file_error.set(Some(("导出失败", e))),
+                                    }
+                                },
+                                span { class: "material-symbols-outlined text-gray-500 text-xl", "receipt_long" }
+                                span { "导出记录（CSV）" }
+                            }
+
+                            // 分隔线
+                            div { class: "h-px bg-gray-100" }
+
+                            // 购物清单
+                            Link {
+                                to: Route::Shopping {},
+                                class: "w-full text-left px-4 py-3 hover:bg-gray-50 flex items-center gap-3 text-sm text-gray-700 transition-colors",
+                                span { class: "material-symbols-outlined text-orange-500 text-xl", "shopping_cart" }
+                                span { "购物清单" }
+                            }
+
+                            // 临期菜谱
+                            Link {
+                                to: Route::Recipes {},
+                                class: "w-full text-left px-4 py-3 hover:bg-gray-50 flex items-center gap-3 text-sm text-gray-700 transition-colors",
+                                span { class: "material-symbols-outlined text-amber-500 text-xl", "restaurant" }
+                                span { "临期菜谱" }
+                            }
+
+                            // 到期日历
+                            Link {
+                                to: Route::Calendar {},
+                                class: "w-full text-left px-4 py-3 hover:bg-gray-50 flex items-center gap-3 text-sm text-gray-700 transition-colors",
+                                span { class: "material-symbols-outlined text-blue-500 text-xl", "event" }
+                                span { "到期日历" }
+                            }
+
+                            // 饭菜计划
+                            Link {
+                                to: Route::Plan {},
+                                class: "w-full text-left px-4 py-3 hover:bg-gray-50 flex items-center gap-3 text-sm text-gray-700 transition-colors",
+                                span { class: "material-symbols-outlined text-violet-500 text-xl", "calendar_month" }
+                                span { "饭菜计划" }
+                            }
+
+                            // 分类管理
+                            Link {
+                                to: Route::Categories {},
+                                class: "w-full text-left px-4 py-3 hover:bg-gray-50 flex items-center gap-3 text-sm text-gray-700 transition-colors",
+                                span { class: "material-symbols-outlined text-green-500 text-xl", "category" }
+                                span { "分类管理" }
+                            }
+
+                            // 滑动操作设置
+                            Link {
+                                to: Route::GestureSettings {},
+                                class: "w-full text-left px-4 py-3 hover:bg-gray-50 flex items-center gap-3 text-sm text-gray-700 transition-colors",
+                                span { class: "material-symbols-outlined text-teal-500 text-xl", "swipe" }
+                                span { "滑动操作" }
+                            }
+
+                            // 浪费统计
+                            Link {
+                                to: Route::Report {},
+                                class: "w-full text-left px-4 py-3 hover:bg-gray-50 flex items-center gap-3 text-sm text-gray-700 transition-colors",
+                                span { class: "material-symbols-outlined text-red-500 text-xl", "savings" }
+                                span { "浪费统计" }
+                            }
+
+                            // 分隔线
+                            div { class: "h-px bg-gray-100" }
+
+                            // 家庭共享
+                            Link {
+                                to: Route::HouseholdSettings {},
+                                class: "w-full text-left px-4 py-3 hover:bg-gray-50 flex items-center gap-3 text-sm text-gray-700 transition-colors",
+                                span { class: "material-symbols-outlined text-purple-500 text-xl", "family_restroom" }
+                                span { "家庭共享" }
+                            }
+
+                            // 分隔线
+                            div { class: "h-px bg-gray-100" }
+
+                            // WebDAV 备份
+                            Link {
+                                to: Route::BackupSettings {},
+                                class: "w-full text-left px-4 py-3 hover:bg-gray-50 flex items-center gap-3 text-sm text-gray-700 transition-colors",
+                                span { class: "material-symbols-outlined text-sky-500 text-xl", "cloud_upload" }
+                                span { "WebDAV 备份" }
+                            }
+
+                            // 本地历史快照
+                            Link {
+                                to: Route::Snapshots {},
+                                class: "w-full text-left px-4 py-3 hover:bg-gray-50 flex items-center gap-3 text-sm text-gray-700 transition-colors",
+                                span { class: "material-symbols-outlined text-amber-500 text-xl", "history" }
+                                span { "历史快照" }
+                            }
+
+                            // 外观：主题和配色
+                            Link {
+                                to: Route::AppearanceSettings {},
+                                class: "w-full text-left px-4 py-3 hover:bg-gray-50 flex items-center gap-3 text-sm text-gray-700 transition-colors",
+                                span { class: "material-symbols-outlined text-indigo-500 text-xl", "palette" }
+                                span { "外观" }
+                            }
+
+                            // 开发者选项：模拟日期
+                            Link {
+                                to: Route::DeveloperSettings {},
+                                class: "w-full text-left px-4 py-3 hover:bg-gray-50 flex items-center gap-3 text-sm text-gray-700 transition-colors",
+                                span { class: "material-symbols-outlined text-gray-500 text-xl", "developer_mode" }
+                                span { "开发者选项" }
+                            }
+
+                            // 演示模式：使用独立的模拟数据，不影响真实库存
+                            button {
+                                class: "w-full text-left px-4 py-3 hover:bg-gray-50 flex items-center gap-3 text-sm text-gray-700 transition-colors",
+                                onclick: move |_| {
+                                    if demo {
+                                        exit_demo_mode();
+                                    } else {
+                                        enter_demo_mode(&generate_mock_data(clock.0.read().as_ref()));
+                                    }
+                                },
+                                span { class: "material-symbols-outlined text-orange-500 text-xl", "science" }
+                                span { if demo { "退出演示" } else { "演示模式" } }
+                            }
                         }
                     }
                 }
             }
 
+            // 演示模式提示：数据都是模拟的，退出后全部丢弃
+            if demo {
+                div {
+                    class: "bg-orange-50 text-orange-800 px-4 py-3 rounded-xl mb-4 flex items-center gap-2 text-sm",
+                    role: "status",
+                    span { class: "material-symbols-outlined text-lg", "science" }
+                    span { class: "flex-1", "演示模式：这里的数据都是模拟的，不会影响你的真实库存，退出后全部丢弃" }
+                    button {
+                        class: "px-3 py-1 rounded-lg bg-orange-500 hover:bg-orange-600 text-white font-medium transition-colors",
+                        onclick: move |_| exit_demo_mode(),
+                        "退出"
+                    }
+                }
+            }
+
+            // 正在模拟日期时提示，避免误以为剩余天数有误
+            if let Some(date) = clock.simulated_date() {
+                Link {
+                    to: Route::DeveloperSettings {},
+                    class: "bg-violet-50 text-violet-700 px-4 py-2 rounded-xl mb-4 flex items-center gap-2 text-sm hover:bg-violet-100 transition-colors",
+                    span { class: "material-symbols-outlined text-lg", "event" }
+                    span { class: "flex-1", "正在模拟 {date.format(\"%Y-%m-%d\")}" }
+                    span { class: "text-xs", "修改" }
+                }
+            }
+
             // 错误提示
             if let Some(err) = error_message() {
                 div {
@@ -210,23 +679,398 @@ pub fn Home() -> Element {
                     }
                 }
             }
+            if let Some((title, error)) = file_error() {
+                ErrorPanel {
+                    title: title.to_string(),
+                    error,
+                    on_dismiss: move |_| file_error.set(None),
+                }
+            }
+
+            // --- 搜索、筛选与排序 ---
+            div { class: "flex flex-col gap-3 mb-4",
+                div { class: "flex items-center gap-2",
+                    div { class: "relative flex-1",
+                        span { class: "material-symbols-outlined absolute left-3 top-1/2 -translate-y-1/2 text-gray-400 text-xl pointer-events-none", "search" }
+                        input {
+                            r#type: "search",
+                            class: "w-full bg-gray-50 border border-gray-200 rounded-xl pl-10 pr-4 py-2.5 text-base focus:outline-none focus:ring-2 focus:ring-blue-500 focus:bg-white transition-all",
+                            value: "{query.text}",
+                            placeholder: "搜索，支持拼音首字母",
+                            oninput: {
+                                let query = query.clone();
+                                move |evt: FormEvent| set_query(InventoryQuery { text: evt.value(), ..query.clone() })
+                            }
+                        }
+                    }
+                    select {
+                        class: "bg-gray-50 border border-gray-200 rounded-xl px-3 py-2.5 text-sm text-gray-700 focus:outline-none focus:ring-2 focus:ring-blue-500",
+                        "aria-label": "排序",
+                        onchange: {
+                            let query = query.clone();
+                            move |evt: FormEvent| {
+                                let sort = SortOrder::from_key(&evt.value()).unwrap_or_default();
+                                set_query(InventoryQuery { sort, ..query.clone() })
+                            }
+                        },
+                        for order in SortOrder::ALL {
+                            option { value: order.key(), selected: query.sort == order, "{order.label()}" }
+                        }
+                    }
+                    button {
+                        class: if selecting() {
+                            "material-symbols-outlined p-2 rounded-xl bg-blue-600 text-white transition-colors"
+                        } else {
+                            "material-symbols-outlined p-2 rounded-xl bg-gray-50 border border-gray-200 text-gray-600 hover:bg-gray-100 transition-colors"
+                        },
+                        "aria-label": "多选",
+                        "aria-pressed": "{selecting}",
+                        onclick: move |_| {
+                            if selecting() {
+                                exit_selecting();
+                            } else {
+                                selecting.set(true);
+                            }
+                        },
+                        "checklist"
+                    }
+                }
+                div { class: "flex gap-2 overflow-x-auto pb-1",
+                    // 从到期日历点选的日期，点一下取消
+                    if let Some(date) = query.expires {
+                        button {
+                            class: chip_class(true),
+                            onclick: {
+                                let query = query.clone();
+                                move |_| set_query(InventoryQuery { expires: None, ..query.clone() })
+                            },
+                            "{date.format(\"%m月%d日\")}到期 ✕"
+                        }
+                        div { class: "w-px shrink-0 bg-gray-200" }
+                    }
+                    for status in StatusFilter::ALL {
+                        button {
+                            class: chip_class(query.status == Some(status)),
+                            onclick: {
+                                let query = query.clone();
+                                move |_| {
+                                    let status = (query.status != Some(status)).then_some(status);
+                                    set_query(InventoryQuery { status, ..query.clone() })
+                                }
+                            },
+                            "{status.label()}"
+                        }
+                    }
+                    div { class: "w-px shrink-0 bg-gray-200" }
+                    for location in Location::ALL {
+                        button {
+                            class: chip_class(query.location == Some(location)),
+                            onclick: {
+                                let query = query.clone();
+                                move |_| {
+                                    let location = (query.location != Some(location)).then_some(location);
+                                    set_query(InventoryQuery { location, ..query.clone() })
+                                }
+                            },
+                            "{location.label()}"
+                        }
+                    }
+                    if !categories.is_empty() {
+                        div { class: "w-px shrink-0 bg-gray-200" }
+                    }
+                    for category in categories {
+                        button {
+                            class: chip_class(query.category.as_deref() == Some(category.id())),
+                            onclick: {
+                                let query = query.clone();
+                                let id = category.id().to_string();
+                                move |_| {
+                                    let category = (query.category.as_deref() != Some(id.as_str())).then(|| id.clone());
+                                    set_query(InventoryQuery { category, ..query.clone() })
+                                }
+                            },
+                            "{category.emoji()} {category.name()}"
+                        }
+                    }
+                }
+            }
 
             // --- List View ---
             div { class: "flex flex-col pb-24",
-                for item in sorted_items {
-                    ItemCard {
-                        key: "{item.id()}",
-                        item: item.clone(),
-                        on_consume: consume_item,
-                        on_waste: waste_item
+                // 第一次使用：库存还是空的，可以先看看演示
+                if inventory.read().is_empty() && !demo {
+                    div { class: "flex flex-col items-center gap-3 py-12 text-gray-400",
+                        span { class: "material-symbols-outlined text-4xl", "kitchen" }
+                        span { class: "text-sm", "还没有物品，点右下角的 + 添加第一个" }
+                        button {
+                            class: "text-sm text-blue-600 hover:underline",
+                            onclick: move |_| enter_demo_mode(&generate_mock_data(clock.0.read().as_ref())),
+                            "先用模拟数据看看演示"
+                        }
+                    }
+                }
+                if count == 0 && query.is_filtered() {
+                    div { class: "flex flex-col items-center gap-2 py-12 text-gray-400",
+                        span { class: "material-symbols-outlined text-4xl", "search_off" }
+                        span { class: "text-sm", "没有符合条件的物品" }
+                        button {
+                            class: "text-sm text-blue-600 hover:underline",
+                            onclick: move |_| set_query(InventoryQuery::default()),
+                            "清除筛选"
+                        }
+                    }
+                }
+                // 键盘用户用方向键在卡片之间移动，整个列表只占一个 Tab 停靠点
+                p { id: "inventory-keys", class: "sr-only",
+                    "用上下方向键切换物品，E 吃掉，W 扔掉，Enter 打开操作菜单，数字键选择吃掉的数量"
+                }
+                div {
+                    id: LIST_ID,
+                    class: "flex flex-col",
+                    role: "list",
+                    "aria-label": "库存物品",
+                    "aria-describedby": "inventory-keys",
+                    div { style: "height: {pad_top}px;", "aria-hidden": "true" }
+                    for (index, item) in window_items {
+                        ItemCard {
+                            key: "{item.id()}",
+                            thresholds: thresholds_of(&all_categories, &item),
+                            reserved: plan.reserved(&item),
+                            usual: usual_amount(&item, &history.read()),
+                            selecting: selecting(),
+                            selected: selected.read().contains(&item.id()),
+                            item: item.clone(),
+                            on_consume: consume_item,
+                            on_waste: waste_item,
+                            swipe: swipe(),
+                            on_action: run_action,
+                            on_select: toggle_select,
+                            active: tab_stop == Some(item.id()),
+                            on_focus: move |id| {
+                                active_card.set(Some(id));
+                                if focus_request() == Some(id) {
+                                    focus_request.set(None);
+                                }
+                            },
+                            on_navigate: navigate,
+                            position: index + 1,
+                            total: count,
+                            autofocus: focus_request() == Some(item.id())
+                        }
+                    }
+                    div { style: "height: {pad_bottom}px;", "aria-hidden": "true" }
+                }
+            }
+        }
+
+        // --- 导出：可选加密 ---
+        if show_export_dialog() {
+            PassphraseDialog {
+                title: "导出数据",
+                description: "设置口令后将导出为端到端加密的 .ukeep 文件，导入时需要输入相同口令。留空则导出为明文 JSON。",
+                confirm_label: "导出",
+                confirm: true,
+                allow_empty: true,
+                on_submit: confirm_export,
+                on_cancel: move |_| show_export_dialog.set(false),
+            }
+        }
+
+        // --- 扔掉：选择原因和数量 ---
+        if let Some(item) = wasting() {
+            WasteDialog {
+                key: "{item.id()}",
+                item,
+                on_submit: confirm_waste,
+                on_cancel: move |_| {
+                    if let Some(item) = wasting.take() {
+                        focus_card(item.id());
+                    }
+                },
+            }
+        }
+
+        // --- 导入：解密加密备份 ---
+        if pending_import.read().is_some() {
+            PassphraseDialog {
+                title: "解密备份",
+                description: "这是一个加密备份，请输入导出时设置的口令。",
+                confirm_label: "解密并导入",
+                error: import_error(),
+                on_submit: confirm_import,
+                on_cancel: move |_| {
+                    pending_import.set(None);
+                    import_error.set(None);
+                },
+            }
+        }
+
+        // --- 补货提示 ---
+        if let Some(entry) = restock_offer() {
+            div { class: "fixed bottom-24 inset-x-4 max-w-md mx-auto z-40 bg-gray-900 text-white rounded-2xl shadow-xl px-4 py-3 flex items-center gap-3",
+                span { class: "flex-1 text-sm", "{entry.emoji()}{entry.name()} 用完了，加入购物清单？" }
+                button {
+                    class: "px-3 py-1.5 bg-white text-gray-900 rounded-lg text-sm font-medium hover:bg-gray-100 transition-colors",
+                    onclick: move |_| {
+                        shopping.add(entry.clone());
+                        restock_offer.set(None);
+                    },
+                    "加入"
+                }
+                button {
+                    class: "material-symbols-outlined p-1 rounded-full text-gray-400 hover:text-white transition-colors",
+                    onclick: move |_| restock_offer.set(None),
+                    "close"
+                }
+            }
+        }
+
+        // 读屏软件朗读操作结果
+        div { class: "sr-only", role: "status", "aria-live": "polite", "{announcement}" }
+
+        // --- 卡片操作的提示 ---
+        if let Some(msg) = notice() {
+            div { class: "fixed bottom-24 inset-x-4 max-w-md mx-auto z-40 bg-gray-900 text-white rounded-2xl shadow-xl px-4 py-3 flex items-center gap-3",
+                span { class: "flex-1 text-sm", "{msg}" }
+                button {
+                    class: "material-symbols-outlined p-1 rounded-full text-gray-400 hover:text-white transition-colors",
+                    onclick: move |_| notice.set(None),
+                    "close"
+                }
+            }
+        }
+
+        // --- 批量操作的撤销提示 ---
+        if let Some(operation) = last_bulk() {
+            div { class: "fixed bottom-24 inset-x-4 max-w-md mx-auto z-40 bg-gray-900 text-white rounded-2xl shadow-xl px-4 py-3 flex items-center gap-3",
+                span { class: "flex-1 text-sm", "{operation.describe()}" }
+                button {
+                    class: "px-3 py-1.5 bg-white text-gray-900 rounded-lg text-sm font-medium hover:bg-gray-100 transition-colors",
+                    onclick: move |_| {
+                        if let Some(operation) = last_bulk.take() {
+                            operation.undo(&mut inventory.write(), &mut history.write(), household.member());
+                            announcement.set(format!("已撤销：{}", operation.describe()));
+                        }
+                    },
+                    "撤销"
+                }
+                button {
+                    class: "material-symbols-outlined p-1 rounded-full text-gray-400 hover:text-white transition-colors",
+                    onclick: move |_| last_bulk.set(None),
+                    "close"
+                }
+            }
+        }
+
+        // --- 多选工具栏 ---
+        if selecting() {
+            div { class: "fixed bottom-4 inset-x-4 max-w-2xl mx-auto z-40 bg-white rounded-2xl shadow-xl border border-gray-100 p-3 flex flex-col gap-3",
+                div { class: "flex items-center gap-3 text-sm",
+                    span { class: "flex-1 font-medium text-gray-900", "已选 {selected.read().len()} 项" }
+                    button {
+                        class: "text-blue-600 hover:underline",
+                        onclick: move |_| selected.set(if all_selected { Vec::new() } else { visible_ids() }),
+                        if all_selected { "取消全选" } else { "全选" }
+                    }
+                    button {
+                        class: "material-symbols-outlined p-1 rounded-full text-gray-400 hover:text-gray-700 transition-colors",
+                        "aria-label": "退出多选",
+                        onclick: move |_| exit_selecting(),
+                        "close"
+                    }
+                }
+                div { class: "flex flex-wrap items-center gap-2 text-sm",
+                    button {
+                        class: "flex items-center gap-1 px-3 py-2 rounded-xl bg-green-50 text-green-700 hover:bg-green-100 disabled:opacity-40",
+                        disabled: selected.read().is_empty(),
+                        onclick: move |_| run_bulk(BulkAction::Consume),
+                        span { class: "material-symbols-outlined text-base", "restaurant" }
+                        "吃掉"
+                    }
+                    button {
+                        class: "flex items-center gap-1 px-3 py-2 rounded-xl bg-red-50 text-red-600 hover:bg-red-100 disabled:opacity-40",
+                        disabled: selected.read().is_empty(),
+                        onclick: move |_| run_bulk(BulkAction::Waste),
+                        span { class: "material-symbols-outlined text-base", "delete" }
+                        "扔掉"
+                    }
+                    select {
+                        class: "px-3 py-2 rounded-xl bg-gray-100 text-gray-700 disabled:opacity-40",
+                        "aria-label": "移到",
+                        disabled: selected.read().is_empty(),
+                        onchange: move |evt: FormEvent| {
+                            if let Some(location) = Location::from_key(&evt.value()) {
+                                run_bulk(BulkAction::Move(location));
+                            }
+                        },
+                        option { value: "", selected: true, "移到…" }
+                        for location in Location::ALL {
+                            option { value: location.key(), "{location.label()}" }
+                        }
+                    }
+                    div { class: "flex items-center rounded-xl bg-gray-100 text-gray-700",
+                        button {
+                            class: "px-3 py-2 hover:bg-gray-200 rounded-l-xl disabled:opacity-40",
+                            "aria-label": "过期日期提前",
+                            disabled: selected.read().is_empty(),
+                            onclick: move |_| shift_selected(-1),
+                            "−"
+                        }
+                        input {
+                            r#type: "number",
+                            min: "1",
+                            class: "w-12 bg-transparent text-center focus:outline-none",
+                            "aria-label": "改期天数",
+                            value: "{shift_days}",
+                            oninput: move |evt| shift_days.set(evt.value()),
+                        }
+                        span { class: "pr-1", "天" }
+                        button {
+                            class: "px-3 py-2 hover:bg-gray-200 rounded-r-xl disabled:opacity-40",
+                            "aria-label": "过期日期推后",
+                            disabled: selected.read().is_empty(),
+                            onclick: move |_| shift_selected(1),
+                            "+"
+                        }
+                    }
+                    button {
+                        class: "flex items-center gap-1 px-3 py-2 rounded-xl bg-gray-100 text-gray-600 hover:bg-gray-200 disabled:opacity-40",
+                        disabled: selected.read().is_empty(),
+                        onclick: move |_| run_bulk(BulkAction::Delete),
+                        span { class: "material-symbols-outlined text-base", "delete_forever" }
+                        "删除"
                     }
                 }
             }
         }
 
         // --- FAB ---
-        Link { to: Route::AddItem {}, class: "fixed bottom-6 right-6 w-14 h-14 bg-blue-600 text-white rounded-2xl shadow-lg shadow-blue-600/30 flex items-center justify-center hover:scale-105 active:scale-95 transition-all z-40",
-            span { class: "material-symbols-outlined text-3xl", "add" }
+        if !selecting() {
+            Link { to: Route::AddItem {}, class: "fixed bottom-6 right-6 w-14 h-14 bg-blue-600 text-white rounded-2xl shadow-lg shadow-blue-600/30 flex items-center justify-center hover:scale-105 active:scale-95 transition-all z-40",
+                span { class: "material-symbols-outlined text-3xl", "add" }
+            }
         }
     }
 }
+
+// 吃掉或扔掉之后朗读的结果，例如“已吃掉 2个 苹果，还剩 1个”
+fn removal_message(verb: &str, item: &Item, count: Quantity, left: Option<Quantity>) -> String {
+    match left {
+        Some(left) => format!("已{verb} {count} {}，还剩 {left}", item.name()),
+        None => format!("已{verb} {count} {}，已经用完", item.name()),
+    }
+}
+
+// 用掉了为饭菜计划预留的部分时的提醒
+fn reservation_warning(item: &Item, shortfall: Quantity) -> String {
+    format!("{} 中有 {shortfall} 是为饭菜计划预留的，计划里可能不够用了", item.name())
+}
+
+// 筛选条中单个选项的样式
+fn chip_class(active: bool) -> &'static str {
+    if active {
+        "shrink-0 px-3 py-1.5 rounded-full text-sm font-medium bg-blue-600 text-white transition-colors"
+    } else {
+        "shrink-0 px-3 py-1.5 rounded-full text-sm font-medium bg-gray-100 text-gray-700 hover:bg-gray-200 transition-colors"
+    }
+}
@@ -0,0 +1,393 @@
+use crate::models::{normalize_invite_code, Household, HouseholdSession, Member, INVITE_CODE_LEN};
+use crate::router::Route;
+use crate::state::{HistoryState, HouseholdState, SyncState};
+use crate::sync::{FetchTransport, HouseholdClient, SyncStatus};
+use crate::utils::qr_code_svg;
+use dioxus::core::spawn_forever;
+use dioxus::prelude::*;
+
+// 活动记录中最多展示的条数
+const MAX_ACTIVITY: usize = 30;
+
+/// 家庭共享设置页
+#[component]
+pub fn HouseholdSettings() -> Element {
+    rsx! {
+        HouseholdPanel { initial_code: String::new() }
+    }
+}
+
+/// 通过邀请链接 / 二维码进入的加入页，邀请码已预填
+#[component]
+pub fn JoinHousehold(code: String) -> Element {
+    rsx! {
+        HouseholdPanel { initial_code: code }
+    }
+}
+
+#[component]
+fn HouseholdPanel(initial_code: String) -> Element {
+    let session = use_context::<HouseholdState>().0;
+    // 退出家庭后当前面板已被卸载，通知服务器失败的提示放在这里
+    let leave_error = use_signal(|| Option::<String>::None);
+
+    rsx! {
+        div { class: "flex flex-col p-6 max-w-2xl mx-auto min-h-screen bg-white",
+            // Top Bar
+            div { class: "flex items-center mb-6",
+                Link { to: Route::Home { query: Default::default() }, class: "material-symbols-outlined text-gray-600 p-2 -ml-2 rounded-full hover:bg-gray-100 transition-colors", "arrow_back" }
+                h2 { class: "flex-1 text-center text-lg font-semibold text-gray-900 pr-8", "家庭共享" }
+            }
+
+            if let Some(current) = session() {
+                CurrentHousehold { session: current, leave_error }
+            } else {
+                if let Some(err) = leave_error() {
+                    div { class: "bg-amber-50 text-amber-700 p-4 rounded-xl mb-6 text-sm", "{err}" }
+                }
+                SetupHousehold { initial_code }
+            }
+        }
+    }
+}
+
+// 创建会话，填写了家庭口令时同时派生端到端加密密钥
+fn new_session(server_url: String, household: Household, member: Member, passphrase: &str) -> Result<HouseholdSession, String> {
+    let mut session = HouseholdSession {
+        server_url,
+        household,
+        member,
+        revision: 0,
+        encryption: None,
+    };
+    if !passphrase.is_empty() {
+        session.encryption = Some(session.derive_key(passphrase)?);
+    }
+    Ok(session)
+}
+
+// 未加入家庭时：创建或加入
+#[component]
+fn SetupHousehold(initial_code: String) -> Element {
+    let mut session = use_context::<HouseholdState>().0;
+
+    let mut server_url = use_signal(String::new);
+    let mut my_name = use_signal(String::new);
+    let mut household_name = use_signal(String::new);
+    let mut invite_code = use_signal(|| initial_code.clone());
+    let mut passphrase = use_signal(String::new);
+    let mut busy = use_signal(|| false);
+    let mut error_message = use_signal(|| Option::<String>::None);
+
+    // 校验公共字段，返回 (服务器地址, 成员)
+    let mut validate = move || -> Option<(String, Member)> {
+        let server = server_url.read().trim().to_string();
+        let name = my_name.read().trim().to_string();
+        if server.is_empty() || name.is_empty() {
+            error_message.set(Some("请填写同步服务器地址和你的昵称".to_string()));
+            return None;
+        }
+        Some((server, Member::new(name)))
+    };
+
+    let create = move |_| {
+        let Some((server, member)) = validate() else {
+            return;
+        };
+        let name = household_name.read().trim().to_string();
+        if name.is_empty() {
+            error_message.set(Some("请填写家庭名称".to_string()));
+            return;
+        }
+        let passphrase = passphrase.read().clone();
+        busy.set(true);
+        spawn(async move {
+            let client = HouseholdClient::new(&server, FetchTransport);
+            let result = client.create(&name, &member).await;
+            match result.and_then(|household| new_session(server, household, member, &passphrase)) {
+                Ok(created) => {
+                    session.set(Some(created));
+                    error_message.set(None);
+                }
+                Err(e) => error_message.set(Some(e)),
+            }
+            busy.set(false);
+        });
+    };
+
+    let join = move |_| {
+        let Some((server, member)) = validate() else {
+            return;
+        };
+        let Some(code) = normalize_invite_code(&invite_code.read()) else {
+            error_message.set(Some(format!("邀请码应为 {} 位字母或数字", INVITE_CODE_LEN)));
+            return;
+        };
+        let passphrase = passphrase.read().clone();
+        busy.set(true);
+        spawn(async move {
+            let client = HouseholdClient::new(&server, FetchTransport);
+            let result = client.join(&code, &member).await;
+            match result.and_then(|household| new_session(server, household, member, &passphrase)) {
+                Ok(created) => {
+                    session.set(Some(created));
+                    error_message.set(None);
+                }
+                Err(e) => error_message.set(Some(e)),
+            }
+            busy.set(false);
+        });
+    };
+
+    let input_class = "w-full bg-gray-50 border border-gray-200 rounded-xl px-4 py-3 text-base focus:outline-none focus:ring-2 focus:ring-blue-500 focus:bg-white transition-all";
+
+    rsx! {
+        p { class: "text-sm text-gray-500 mb-6",
+            "加入家庭后，家人们共享同一份库存，并能看到谁添加、吃掉或扔掉了什么。本机已有的物品会合并到家庭库存中。"
+        }
+
+        div { class: "flex flex-col mb-4",
+            label { class: "block text-sm font-medium text-gray-700 mb-2", "同步服务器" }
+            input {
+                r#type: "url",
+                class: input_class,
+                value: "{server_url}",
+                oninput: move |evt| server_url.set(evt.value()),
+                placeholder: "https://sync.example.com"
+            }
+        }
+
+        div { class: "flex flex-col mb-4",
+            label { class: "block text-sm font-medium text-gray-700 mb-2", "我的昵称" }
+            input {
+                r#type: "text",
+                class: input_class,
+                value: "{my_name}",
+                oninput: move |evt| my_name.set(evt.value()),
+                placeholder: "例如：妈妈"
+            }
+        }
+
+        div { class: "flex flex-col mb-8",
+            label { class: "block text-sm font-medium text-gray-700 mb-2", "家庭口令（可选）" }
+            input {
+                r#type: "password",
+                class: input_class,
+                value: "{passphrase}",
+                oninput: move |evt| passphrase.set(evt.value()),
+                placeholder: "设置后数据在本机加密，服务器无法读取"
+            }
+        }
+
+        // 加入已有家庭
+        div { class: "flex flex-col mb-8 p-4 rounded-xl border border-gray-100 bg-gray-50",
+            label { class: "block text-sm font-medium text-gray-700 mb-2", "加入已有家庭" }
+            div { class: "flex items-center gap-3",
+                input {
+                    r#type: "text",
+                    class: "flex-1 bg-white border border-gray-200 rounded-xl px-4 py-3 text-base font-mono uppercase tracking-widest focus:outline-none focus:ring-2 focus:ring-blue-500",
+                    maxlength: "8",
+                    value: "{invite_code}",
+                    oninput: move |evt| invite_code.set(evt.value()),
+                    placeholder: "邀请码"
+                }
+                button {
+                    class: "px-5 py-3 bg-blue-600 hover:bg-blue-700 text-white rounded-xl font-medium transition-colors disabled:opacity-50",
+                    disabled: busy(),
+                    onclick: join,
+                    "加入"
+                }
+            }
+        }
+
+        // 创建新家庭
+        div { class: "flex flex-col mb-8 p-4 rounded-xl border border-gray-100 bg-gray-50",
+            label { class: "block text-sm font-medium text-gray-700 mb-2", "创建新家庭" }
+            div { class: "flex items-center gap-3",
+                input {
+                    r#type: "text",
+                    class: "flex-1 bg-white border border-gray-200 rounded-xl px-4 py-3 text-base focus:outline-none focus:ring-2 focus:ring-blue-500",
+                    value: "{household_name}",
+                    oninput: move |evt| household_name.set(evt.value()),
+                    placeholder: "例如：我们家的冰箱"
+                }
+                button {
+                    class: "px-5 py-3 bg-gray-900 hover:bg-gray-700 text-white rounded-xl font-medium transition-colors disabled:opacity-50",
+                    disabled: busy(),
+                    onclick: create,
+                    "创建"
+                }
+            }
+        }
+
+        if let Some(err) = error_message() {
+            div { class: "bg-red-50 text-red-700 p-4 rounded-xl mb-4 shadow-sm", "{err}" }
+        }
+    }
+}
+
+// 已加入家庭时：邀请码、成员和活动记录
+#[component]
+fn CurrentHousehold(session: HouseholdSession, mut leave_error: Signal<Option<String>>) -> Element {
+    let mut household_state = use_context::<HouseholdState>().0;
+    let history = use_context::<HistoryState>().0;
+    let status = use_context::<SyncState>().0;
+    let mut error_message = use_signal(|| Option::<String>::None);
+    let mut new_passphrase = use_signal(String::new);
+
+    let origin = web_sys::window()
+        .and_then(|w| w.location().origin().ok())
+        .unwrap_or_default();
+    let join_url = session.join_url(&origin);
+    let qr_svg = qr_code_svg(&join_url).unwrap_or_default();
+
+    let household = session.household.clone();
+    let me = session.member.id();
+
+    // 最近的活动记录（最新的在前）
+    let activity: Vec<_> = history.read().iter().rev().take(MAX_ACTIVITY).cloned().collect();
+
+    let status_text = match status() {
+        SyncStatus::Idle => "尚未同步".to_string(),
+        SyncStatus::Syncing => "同步中…".to_string(),
+        SyncStatus::Synced(at) => format!("已同步 {}", at.format("%H:%M")),
+        SyncStatus::Failed(e) => format!("同步失败: {}", e),
+    };
+
+    let encrypted = session.encryption.is_some();
+
+    // 设置或更换家庭口令：服务器上的密文先用旧密钥解密、再用新密钥加密，成功后才在本机换用新密钥；
+    // 服务器上还是明文时，下次同步会把它替换为密文
+    let key_session = session.clone();
+    let mut rekeying = use_signal(|| false);
+    let set_passphrase = move |_| {
+        let passphrase = new_passphrase.read().clone();
+        if passphrase.is_empty() {
+            error_message.set(Some("请输入家庭口令".to_string()));
+            return;
+        }
+        let key = match key_session.derive_key(&passphrase) {
+            Ok(key) => key,
+            Err(e) => {
+                error_message.set(Some(e));
+                return;
+            }
+        };
+        let current = key_session.clone();
+        rekeying.set(true);
+        spawn(async move {
+            let client = HouseholdClient::new(&current.server_url, FetchTransport).with_encryption(current.encryption.clone());
+            match client.rekey(current.household.id(), current.member.id(), &key).await {
+                Ok(()) => {
+                    if let Some(s) = household_state.write().as_mut() {
+                        s.encryption = Some(key);
+                    }
+                    new_passphrase.set(String::new());
+                    error_message.set(None);
+                }
+                Err(e) => error_message.set(Some(format!("更换口令失败: {}", e))),
+            }
+            rekeying.set(false);
+        });
+    };
+
+    // 退出家庭：本地会话总是清除，本地库存保留为个人副本；通知服务器失败时只提示
+    let leave = move |_| {
+        let confirmed = web_sys::window()
+            .and_then(|w| w.confirm_with_message("退出家庭后，当前库存会保留在本机。确定退出吗？").ok())
+            .unwrap_or(false);
+        if !confirmed {
+            return;
+        }
+        let current = session.clone();
+        leave_error.set(None);
+        household_state.set(None);
+        spawn_forever(async move {
+            let client = HouseholdClient::new(&current.server_url, FetchTransport);
+            if let Err(e) = client.leave(current.household.id(), current.member.id()).await {
+                // 用户可能已经离开了这个页面
+                if let Ok(mut slot) = leave_error.try_write() {
+                    *slot = Some(format!("已在本机退出家庭，但未能通知服务器: {}", e));
+                }
+            }
+        });
+    };
+
+    rsx! {
+        div { class: "flex flex-col items-center mb-8",
+            h3 { class: "text-xl font-bold text-gray-900", "{household.name()}" }
+            span { class: "text-xs text-gray-500 mt-1", "{status_text}" }
+        }
+
+        // 邀请码与二维码
+        div { class: "flex flex-col items-center mb-8 p-4 rounded-xl border border-gray-100 bg-gray-50",
+            span { class: "text-sm text-gray-500 mb-2", "邀请家人扫码或输入邀请码" }
+            span { class: "text-3xl font-mono font-bold tracking-[0.3em] text-gray-900 mb-4", "{household.invite_code()}" }
+            div { class: "w-48 h-48 bg-white p-2 rounded-lg", dangerous_inner_html: "{qr_svg}" }
+        }
+
+        // 端到端加密
+        div { class: "flex flex-col mb-8 p-4 rounded-xl border border-gray-100 bg-gray-50",
+            div { class: "flex items-center gap-2 mb-2",
+                span { class: "material-symbols-outlined text-gray-500", if encrypted { "lock" } else { "lock_open" } }
+                span { class: "text-sm font-medium text-gray-700",
+                    if encrypted { "已启用端到端加密" } else { "未启用端到端加密" }
+                }
+            }
+            p { class: "text-xs text-gray-500 mb-3", "所有成员需要输入相同的家庭口令。口令只保存在各自设备上，服务器只能看到密文。" }
+            div { class: "flex items-center gap-3",
+                input {
+                    r#type: "password",
+                    class: "flex-1 bg-white border border-gray-200 rounded-xl px-4 py-3 text-base focus:outline-none focus:ring-2 focus:ring-blue-500",
+                    value: "{new_passphrase}",
+                    oninput: move |evt| new_passphrase.set(evt.value()),
+                    placeholder: "家庭口令"
+                }
+                button {
+                    class: "px-5 py-3 bg-gray-900 hover:bg-gray-700 text-white rounded-xl font-medium transition-colors disabled:opacity-50",
+                    disabled: rekeying(),
+                    onclick: set_passphrase,
+                    if encrypted { "更换" } else { "启用" }
+                }
+            }
+        }
+
+        // 成员列表
+        div { class: "flex flex-col mb-8",
+            label { class: "block text-sm font-medium text-gray-700 mb-2", "成员 ({household.members().len()})" }
+            for member in household.members().iter() {
+                div { key: "{member.id()}", class: "flex items-center gap-3 py-2 border-b border-gray-100 last:border-b-0",
+                    span { class: "material-symbols-outlined text-gray-400", "person" }
+                    span { class: "text-gray-900", "{member.name()}" }
+                    if member.id() == me {
+                        span { class: "text-xs text-gray-400", "(我)" }
+                    }
+                }
+            }
+        }
+
+        // 活动记录
+        div { class: "flex flex-col mb-8",
+            label { class: "block text-sm font-medium text-gray-700 mb-2", "最近动态" }
+            if activity.is_empty() {
+                span { class: "text-sm text-gray-400", "还没有任何记录" }
+            }
+            for event in activity {
+                div { key: "{event.id()}", class: "flex justify-between items-center py-2 border-b border-gray-100 last:border-b-0 text-sm",
+                    span { class: "text-gray-700", "{event.describe()}" }
+                    span { class: "text-xs text-gray-400", {event.at().format("%m-%d %H:%M").to_string()} }
+                }
+            }
+        }
+
+        if let Some(err) = error_message() {
+            div { class: "bg-red-50 text-red-700 p-4 rounded-xl mb-4 shadow-sm", "{err}" }
+        }
+
+        button {
+            class: "w-full border border-red-200 text-red-600 hover:bg-red-50 font-medium py-3 rounded-xl transition-colors",
+            onclick: leave,
+            "退出家庭"
+        }
+    }
+}
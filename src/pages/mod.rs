@@ -1,5 +1,29 @@
 mod add_item;
+mod appearance;
+mod backup;
+mod calendar;
+mod categories;
+mod developer;
+mod gestures;
 mod home;
+mod household;
+mod plan;
+mod recipes;
+mod report;
+mod shopping;
+mod snapshots;
 
 pub use add_item::AddItem;
+pub use appearance::AppearanceSettings;
+pub use backup::BackupSettings;
+pub use calendar::Calendar;
+pub use categories::Categories;
+pub use developer::DeveloperSettings;
+pub use gestures::GestureSettings;
 pub use home::Home;
+pub use household::{HouseholdSettings, JoinHousehold};
+pub use plan::Plan;
+pub use recipes::Recipes;
+pub use report::Report;
+pub use shopping::Shopping;
+pub use snapshots::Snapshots;
@@ -0,0 +1,302 @@
+use crate::models::{complete_meal, reserved_amount, EventKind, HistoryEvent, Item, Meal, MealSlot, Quantity, Reservation};
+use crate::router::Route;
+use crate::state::{ClockState, HistoryState, HouseholdState, InventoryState, PlanState};
+use chrono::{Datelike, Duration, NaiveDate};
+use dioxus::prelude::*;
+use uuid::Uuid;
+
+const WEEKDAYS: [&str; 7] = ["周一", "周二", "周三", "周四", "周五", "周六", "周日"];
+
+/// 饭菜计划页：按周安排每餐用到的库存物品，预留数量并在完成时扣减
+#[component]
+pub fn Plan() -> Element {
+    let mut inventory = use_context::<InventoryState>().0;
+    let mut history = use_context::<HistoryState>().0;
+    let household = use_context::<HouseholdState>();
+    let mut plan = use_context::<PlanState>().0;
+
+    let today = use_context::<ClockState>().today();
+    // 相对本周的偏移（周）
+    let mut week_offset = use_signal(|| 0_i64);
+
+    // 新增一餐的表单
+    let mut date_str = use_signal(|| today.format("%Y-%m-%d").to_string());
+    let mut slot = use_signal(MealSlot::default);
+    let mut title = use_signal(String::new);
+    let mut draft = use_signal(Vec::<Reservation>::new);
+    let mut picked_item = use_signal(|| Option::<Uuid>::None);
+    let mut amount_str = use_signal(|| "1".to_string());
+    let mut error = use_signal(|| Option::<String>::None);
+    let mut message = use_signal(|| Option::<String>::None);
+
+    let items = inventory.read().clone();
+    let mut meals = plan.read().clone();
+    meals.sort_by_key(|m| (m.date(), m.slot()));
+
+    // 物品还能预留的数量：总量减去其他计划和当前草稿已预留的部分
+    let available = move |item: &Item| {
+        let drafted: f64 = draft.read().iter().filter(|r| r.item_id == item.id()).map(|r| r.amount).sum();
+        item.quantity().amount() - reserved_amount(&plan.read(), item.id(), None) - drafted
+    };
+
+    let handle_reserve = move |_| {
+        let Some(id) = picked_item() else {
+            error.set(Some("请选择物品".to_string()));
+            return;
+        };
+        let items = inventory.read();
+        let Some(item) = items.iter().find(|i| i.id() == id) else {
+            return;
+        };
+        let Some(amount) = amount_str.read().trim().parse::<f64>().ok().filter(|a| a.is_finite() && *a > 0.0) else {
+            error.set(Some("请输入有效的数量".to_string()));
+            return;
+        };
+        let left = available(item);
+        if amount > left + 1e-6 {
+            let unit = item.quantity().unit();
+            error.set(Some(format!("{} 只剩 {} 可以预留", item.name(), Quantity::new(left.max(0.0), unit))));
+            return;
+        }
+        draft.write().push(Reservation { item_id: id, amount });
+        error.set(None);
+    };
+
+    let handle_save = move |_| {
+        let Ok(date) = NaiveDate::parse_from_str(&date_str.read(), "%Y-%m-%d") else {
+            error.set(Some("请选择日期".to_string()));
+            return;
+        };
+        if draft.read().is_empty() {
+            error.set(Some("至少预留一样食材".to_string()));
+            return;
+        }
+        let name = Some(title.read().trim().to_string()).filter(|t| !t.is_empty()).unwrap_or_else(|| slot().label().to_string());
+        plan.write().push(Meal::new(date, slot(), name, draft.read().clone()));
+        draft.write().clear();
+        title.set(String::new());
+        error.set(None);
+    };
+
+    // 完成一餐：扣减预留的数量，逐个记录“吃掉了”，并移出计划
+    let handle_complete = move |meal: Meal| {
+        let consumed = complete_meal(&meal, &mut inventory.write());
+        let member = household.member();
+        let mut events = history.write();
+        for c in &consumed {
+            let value = c.item.value_of(c.amount.amount());
+            events.push(
+                HistoryEvent::new(&c.item, EventKind::Consumed, c.amount, c.removed, member.clone()).with_value(value),
+            );
+        }
+        plan.write().retain(|m| m.id() != meal.id());
+        message.set(Some(format!("{} 已完成，用掉 {} 样食材", meal.title(), consumed.len())));
+    };
+
+    let monday = today - Duration::days(today.weekday().num_days_from_monday() as i64) + Duration::weeks(week_offset());
+    let days: Vec<NaiveDate> = (0..7).map(|d| monday + Duration::days(d)).collect();
+    let warning_count: usize = meals.iter().map(|m| m.expiring_before(&items, &meals).len()).sum();
+    let draft_date = NaiveDate::parse_from_str(&date_str.read(), "%Y-%m-%d").ok();
+    let input_class = "bg-gray-50 border border-gray-200 rounded-xl px-3 py-2 text-base focus:outline-none focus:ring-2 focus:ring-blue-500 focus:bg-white transition-all";
+
+    rsx! {
+        div { class: "flex flex-col p-6 max-w-2xl mx-auto min-h-screen bg-white",
+            // Top Bar
+            div { class: "flex items-center mb-6",
+                Link { to: Route::Home { query: Default::default() }, class: "material-symbols-outlined text-gray-600 p-2 -ml-2 rounded-full hover:bg-gray-100 transition-colors", "arrow_back" }
+                h2 { class: "flex-1 text-center text-lg font-semibold text-gray-900 pr-8", "饭菜计划" }
+            }
+
+            if let Some(msg) = message() {
+                div { class: "bg-green-50 text-green-700 p-4 rounded-xl mb-4 shadow-sm", "{msg}" }
+            }
+            if warning_count > 0 {
+                div { class: "bg-red-50 text-red-600 p-4 rounded-xl mb-4 shadow-sm text-sm",
+                    "⚠️ 有 {warning_count} 样预留的食材会在用餐日期之前过期"
+                }
+            }
+
+            // 1. 一周视图
+            div { class: "flex items-center justify-between mb-2",
+                button {
+                    class: "material-symbols-outlined p-2 rounded-full text-gray-500 hover:bg-gray-100",
+                    "aria-label": "上一周",
+                    onclick: move |_| week_offset -= 1,
+                    "chevron_left"
+                }
+                span { class: "text-sm font-medium text-gray-700",
+                    "{monday.format(\"%m/%d\")} - {(monday + Duration::days(6)).format(\"%m/%d\")}"
+                }
+                button {
+                    class: "material-symbols-outlined p-2 rounded-full text-gray-500 hover:bg-gray-100",
+                    "aria-label": "下一周",
+                    onclick: move |_| week_offset += 1,
+                    "chevron_right"
+                }
+            }
+            for (index, day) in days.into_iter().enumerate() {
+                div { key: "{day}", class: "flex gap-3 py-3 border-b border-gray-100 last:border-b-0",
+                    div { class: if day == today { "w-12 flex flex-col text-blue-600" } else { "w-12 flex flex-col text-gray-500" },
+                        span { class: "text-sm font-medium", "{WEEKDAYS[index]}" }
+                        span { class: "text-xs", "{day.format(\"%m/%d\")}" }
+                    }
+                    div { class: "flex-1 flex flex-col gap-2",
+                        for meal in meals.iter().filter(|m| m.date() == day).cloned() {
+                            MealRow {
+                                key: "{meal.id()}",
+                                meal: meal.clone(),
+                                items: items.clone(),
+                                meals: meals.clone(),
+                                on_complete: handle_complete,
+                                on_delete: move |id: Uuid| plan.write().retain(|m| m.id() != id),
+                            }
+                        }
+                    }
+                }
+            }
+
+            // 2. 新增一餐
+            div { class: "flex flex-col gap-3 mt-8",
+                h3 { class: "text-sm font-medium text-gray-700", "安排一餐" }
+                div { class: "flex gap-3",
+                    input {
+                        r#type: "date",
+                        class: "flex-1 min-w-0 {input_class}",
+                        value: "{date_str}",
+                        oninput: move |evt| date_str.set(evt.value())
+                    }
+                    select {
+                        class: "w-24 {input_class}",
+                        onchange: move |evt| {
+                            if let Some(s) = MealSlot::from_key(&evt.value()) {
+                                slot.set(s);
+                            }
+                        },
+                        for s in MealSlot::ALL {
+                            option { value: s.key(), selected: slot() == s, "{s.label()}" }
+                        }
+                    }
+                }
+                input {
+                    r#type: "text",
+                    class: "{input_class}",
+                    value: "{title}",
+                    oninput: move |evt| title.set(evt.value()),
+                    placeholder: "菜名，例如：番茄炒蛋"
+                }
+                div { class: "flex gap-3",
+                    select {
+                        class: "flex-1 min-w-0 {input_class}",
+                        onchange: move |evt| picked_item.set(Uuid::parse_str(&evt.value()).ok()),
+                        option { value: "", selected: picked_item().is_none(), "选择食材" }
+                        for item in items.iter() {
+                            option {
+                                value: "{item.id()}",
+                                selected: picked_item() == Some(item.id()),
+                                "{item.emoji()}{item.name()}（可预留 {Quantity::new(available(item).max(0.0), item.quantity().unit())}）"
+                            }
+                        }
+                    }
+                    input {
+                        r#type: "number",
+                        min: "0",
+                        step: "any",
+                        class: "w-20 {input_class}",
+                        value: "{amount_str}",
+                        oninput: move |evt| amount_str.set(evt.value())
+                    }
+                    button {
+                        class: "material-symbols-outlined p-2 bg-gray-100 hover:bg-gray-200 text-gray-700 rounded-xl transition-colors",
+                        "aria-label": "预留",
+                        onclick: handle_reserve,
+                        "add"
+                    }
+                }
+                if !draft.read().is_empty() {
+                    div { class: "flex flex-wrap gap-2",
+                        for (index, r) in draft.read().iter().copied().enumerate() {
+                            if let Some(item) = items.iter().find(|i| i.id() == r.item_id) {
+                                span {
+                                    class: if draft_date.is_some_and(|d| d > item.expiry_date()) {
+                                        "flex items-center gap-1 px-2 py-1 rounded-full text-xs bg-red-50 text-red-600"
+                                    } else {
+                                        "flex items-center gap-1 px-2 py-1 rounded-full text-xs bg-gray-100 text-gray-600"
+                                    },
+                                    "{item.emoji()}{item.name()} {Quantity::new(r.amount, item.quantity().unit())}"
+                                    if draft_date.is_some_and(|d| d > item.expiry_date()) {
+                                        " · 届时已过期"
+                                    }
+                                    button {
+                                        class: "material-symbols-outlined text-sm",
+                                        onclick: move |_| {
+                                            draft.write().remove(index);
+                                        },
+                                        "close"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                if let Some(msg) = error() {
+                    div { class: "bg-red-50 text-red-600 p-3 rounded-xl text-sm", "{msg}" }
+                }
+                button {
+                    class: "w-full bg-blue-600 hover:bg-blue-700 text-white font-medium py-3 rounded-xl shadow-lg shadow-blue-600/20 transition-all active:scale-95",
+                    onclick: handle_save,
+                    "保存"
+                }
+            }
+        }
+    }
+}
+
+// 计划中的一餐：预留的食材、过期提醒，以及完成/删除按钮
+#[component]
+fn MealRow(meal: Meal, items: Vec<Item>, meals: Vec<Meal>, on_complete: EventHandler<Meal>, on_delete: EventHandler<Uuid>) -> Element {
+    let expiring: Vec<Uuid> = meal.expiring_before(&items, &meals).iter().map(|i| i.id()).collect();
+    let meal_id = meal.id();
+
+    rsx! {
+        div { class: "flex flex-col gap-1 bg-gray-50 rounded-xl p-3",
+            div { class: "flex items-center gap-2",
+                span { class: "text-xs text-gray-400", "{meal.slot().label()}" }
+                span { class: "flex-1 text-sm font-medium text-gray-900", "{meal.title()}" }
+                button {
+                    class: "px-2 py-1 text-xs rounded-lg bg-blue-600 text-white hover:bg-blue-700",
+                    onclick: {
+                        let meal = meal.clone();
+                        move |_| on_complete.call(meal.clone())
+                    },
+                    "完成"
+                }
+                button {
+                    class: "material-symbols-outlined p-1 rounded-full text-gray-400 hover:bg-gray-100 text-base",
+                    "aria-label": "删除",
+                    onclick: move |_| on_delete.call(meal_id),
+                    "delete"
+                }
+            }
+            div { class: "flex flex-wrap gap-1",
+                for r in meal.reservations().iter() {
+                    match items.iter().find(|i| i.id() == r.item_id) {
+                        Some(item) => rsx! {
+                            span {
+                                class: if expiring.contains(&item.id()) {
+                                    "px-2 py-0.5 rounded-full text-xs bg-red-50 text-red-600"
+                                } else {
+                                    "px-2 py-0.5 rounded-full text-xs bg-white text-gray-600"
+                                },
+                                title: if expiring.contains(&item.id()) { "用餐前会过期（{item.expiry_date()}）" } else { "" },
+                                "{item.emoji()}{item.name()} {Quantity::new(r.amount, item.quantity().unit())}"
+                            }
+                        },
+                        None => rsx! {
+                            span { class: "px-2 py-0.5 rounded-full text-xs bg-white text-gray-300 line-through", "已不在库存" }
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
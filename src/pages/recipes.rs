@@ -0,0 +1,184 @@
+use crate::models::{cook, parse_recipes, suggest_recipes, EventKind, HistoryEvent, RecipeSuggestion};
+use crate::router::Route;
+use crate::state::{CategoryState, ClockState, HistoryState, HouseholdState, InventoryState, PlanState, RecipeState};
+use dioxus::prelude::*;
+use gloo_file::callbacks::{read_as_text, FileReader};
+use gloo_file::File;
+use wasm_bindgen::JsCast;
+use web_sys::HtmlInputElement;
+
+/// 菜谱推荐页：按用到的临期食材推荐菜谱，一键消耗所需食材
+#[component]
+pub fn Recipes() -> Element {
+    let mut inventory = use_context::<InventoryState>().0;
+    let mut history = use_context::<HistoryState>().0;
+    let household = use_context::<HouseholdState>();
+    let categories = use_context::<CategoryState>();
+    let plan = use_context::<PlanState>();
+    let recipe_state = use_context::<RecipeState>();
+    let mut imported = recipe_state.0;
+
+    let mut message = use_signal(|| Option::<String>::None);
+    let mut error = use_signal(|| Option::<String>::None);
+    // 保持 FileReader 存活，防止异步导入回调被取消
+    let mut reader_slot = use_signal(|| Option::<FileReader>::None);
+
+    let items = inventory.read().clone();
+    let today = use_context::<ClockState>().today();
+    let suggestions = suggest_recipes(&recipe_state.all(), &items, &categories.all(), today);
+
+    // 一次性扣减菜谱用到的所有食材，并逐个记录“吃掉了”
+    let mut handle_cook = move |suggestion: RecipeSuggestion| {
+        let consumed = cook(&suggestion, &mut inventory.write());
+        let member = household.member();
+        let mut events = history.write();
+        for c in &consumed {
+            let value = c.item.value_of(c.amount.amount());
+            events.push(
+                HistoryEvent::new(&c.item, EventKind::Consumed, c.amount, c.removed, member.clone()).with_value(value),
+            );
+        }
+        // 用到了为饭菜计划预留的食材时一并提醒
+        let reserved: Vec<&str> = consumed
+            .iter()
+            .filter(|c| plan.shortfall(&c.item, c.amount.amount()) > 0.0)
+            .map(|c| c.item.name())
+            .collect();
+        let mut text = format!("做了{}，用掉 {} 样食材", suggestion.recipe.name(), consumed.len());
+        if !reserved.is_empty() {
+            text.push_str(&format!("；其中 {} 是为饭菜计划预留的，计划里可能不够用了", reserved.join("、")));
+        }
+        message.set(Some(text));
+    };
+
+    // 导入 JSON 或 YAML 菜谱文件，追加到已导入的菜谱中
+    let handle_import = move |_| {
+        let Some(document) = web_sys::window().and_then(|w| w.document()) else {
+            return;
+        };
+        let Some(input) = document
+            .create_element("input")
+            .ok()
+            .and_then(|e| e.dyn_into::<HtmlInputElement>().ok())
+        else {
+            return;
+        };
+        input.set_type("file");
+        input.set_accept(".json,.yaml,.yml");
+
+        let onchange = wasm_bindgen::closure::Closure::wrap(Box::new(move |event: web_sys::Event| {
+            let Some(file) = event
+                .target()
+                .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+                .and_then(|input| input.files())
+                .and_then(|files| files.get(0))
+            else {
+                return;
+            };
+            let file = File::from(file);
+            let name = file.name().to_lowercase();
+            let yaml = name.ends_with(".yaml") || name.ends_with(".yml");
+            let reader = read_as_text(&file, move |result| {
+                match result.map_err(|_| "读取文件失败".to_string()).and_then(|text| parse_recipes(&text, yaml)) {
+                    Ok(recipes) => {
+                        let count = recipes.len();
+                        let mut list = imported.write();
+                        // 同一标识的菜谱以新导入的为准
+                        list.retain(|r| !recipes.iter().any(|n| n.id() == r.id()));
+                        list.extend(recipes);
+                        error.set(None);
+                        message.set(Some(format!("已导入 {} 个菜谱", count)));
+                    }
+                    Err(e) => error.set(Some(format!("导入失败: {}", e))),
+                }
+                reader_slot.set(None);
+            });
+            reader_slot.set(Some(reader));
+        }) as Box<dyn FnMut(_)>);
+
+        input.set_onchange(Some(onchange.as_ref().unchecked_ref()));
+        onchange.forget();
+        input.click();
+    };
+
+    let imported_count = imported.read().len();
+
+    rsx! {
+        div { class: "flex flex-col p-6 max-w-2xl mx-auto min-h-screen bg-white",
+            // Top Bar
+            div { class: "flex items-center mb-6",
+                Link { to: Route::Home { query: Default::default() }, class: "material-symbols-outlined text-gray-600 p-2 -ml-2 rounded-full hover:bg-gray-100 transition-colors", "arrow_back" }
+                h2 { class: "flex-1 text-center text-lg font-semibold text-gray-900 pr-8", "临期菜谱" }
+            }
+
+            if let Some(msg) = message() {
+                div { class: "bg-green-50 text-green-700 p-4 rounded-xl mb-4 shadow-sm", "{msg}" }
+            }
+            if let Some(msg) = error() {
+                div { class: "bg-red-50 text-red-600 p-4 rounded-xl mb-4 shadow-sm", "{msg}" }
+            }
+
+            // 1. 推荐列表
+            if suggestions.is_empty() {
+                span { class: "text-sm text-gray-400", "暂时没有临期食材能用上的菜谱" }
+            }
+            for suggestion in suggestions {
+                div { key: "{suggestion.recipe.id()}", class: "flex flex-col gap-2 py-4 border-b border-gray-100 last:border-b-0",
+                    div { class: "flex items-center gap-2",
+                        span { class: "text-2xl", "{suggestion.recipe.emoji()}" }
+                        span { class: "flex-1 font-medium text-gray-900", "{suggestion.recipe.name()}" }
+                        if let Some(minutes) = suggestion.recipe.minutes() {
+                            span { class: "text-xs text-gray-400", "约 {minutes} 分钟" }
+                        }
+                    }
+                    div { class: "flex flex-wrap gap-2",
+                        for m in suggestion.matches.iter().filter_map(|m| items.iter().find(|i| i.id() == m.item_id).map(|item| (m.clone(), item.clone()))) {
+                            span {
+                                class: if m.0.urgent {
+                                    "px-2 py-1 rounded-full text-xs bg-amber-50 text-amber-700"
+                                } else {
+                                    "px-2 py-1 rounded-full text-xs bg-gray-100 text-gray-600"
+                                },
+                                "{m.1.emoji()}{m.1.name()}"
+                                if m.0.urgent {
+                                    " · {m.1.display_deadline(today)}"
+                                }
+                            }
+                        }
+                    }
+                    if !suggestion.missing.is_empty() {
+                        span { class: "text-xs text-gray-400", "还缺：{suggestion.missing.join(\"、\")}" }
+                    }
+                    button {
+                        class: "self-start px-4 py-2 bg-blue-600 hover:bg-blue-700 text-white rounded-xl text-sm font-medium transition-colors",
+                        onclick: {
+                            let suggestion = suggestion.clone();
+                            move |_| handle_cook(suggestion.clone())
+                        },
+                        "做这道菜"
+                    }
+                }
+            }
+
+            div { class: "flex-1" }
+
+            // 2. 导入菜谱
+            div { class: "flex items-center gap-3 mt-8 text-sm",
+                button {
+                    class: "flex items-center gap-1 px-4 py-2 bg-gray-100 hover:bg-gray-200 text-gray-700 rounded-xl transition-colors",
+                    onclick: handle_import,
+                    span { class: "material-symbols-outlined text-base", "upload_file" }
+                    "导入菜谱（JSON / YAML）"
+                }
+                if imported_count > 0 {
+                    span { class: "flex-1 text-gray-400", "已导入 {imported_count} 个" }
+                    button {
+                        class: "text-red-500 hover:underline",
+                        onclick: move |_| imported.write().clear(),
+                        "清除"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use crate::models::{monthly_reports, Price};
+use crate::router::Route;
+use crate::state::HistoryState;
+use dioxus::prelude::*;
+
+/// 浪费统计页：按月对比吃掉和扔掉的金额
+#[component]
+pub fn Report() -> Element {
+    let history = use_context::<HistoryState>().0;
+    let reports = monthly_reports(&history.read());
+
+    rsx! {
+        div { class: "flex flex-col p-6 max-w-2xl mx-auto min-h-screen bg-white",
+            // Top Bar
+            div { class: "flex items-center mb-6",
+                Link { to: Route::Home { query: Default::default() }, class: "material-symbols-outlined text-gray-600 p-2 -ml-2 rounded-full hover:bg-gray-100 transition-colors", "arrow_back" }
+                h2 { class: "flex-1 text-center text-lg font-semibold text-gray-900 pr-8", "浪费统计" }
+            }
+
+            if reports.is_empty() {
+                span { class: "text-sm text-gray-400", "还没有数据。录入物品时填写价格后，吃掉和扔掉的金额会记录在这里。" }
+            }
+
+            for report in reports {
+                div { key: "{report.year()}-{report.month()}", class: "flex flex-col gap-3 py-4 border-b border-gray-100 last:border-b-0",
+                    span { class: "text-sm font-medium text-gray-700", "{report.label()}" }
+                    for currency in report.currencies() {
+                        div { class: "flex flex-col gap-1",
+                            div { class: "flex justify-between text-sm",
+                                span { class: "text-green-600", "吃掉 {Price::new(report.consumed(currency), currency)}" }
+                                span { class: "text-red-500", "扔掉 {Price::new(report.wasted(currency), currency)}" }
+                            }
+                            // 红色部分为扔掉的金额占比
+                            div { class: "h-2 rounded-full bg-green-100 overflow-hidden",
+                                div {
+                                    class: "h-full bg-red-400",
+                                    style: "width: {report.waste_ratio(currency) * 100.0:.0}%",
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use crate::models::{known_shelf_life, EventKind, HistoryEvent, ShoppingEntry};
+use crate::router::Route;
+use crate::state::{ClockState, HistoryState, HouseholdState, InventoryState, ShoppingState};
+use chrono::NaiveDate;
+use dioxus::prelude::*;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// 购物清单页：手动添加、勾选已买到的东西，并一键入库
+#[component]
+pub fn Shopping() -> Element {
+    let mut inventory = use_context::<InventoryState>();
+    let mut history = use_context::<HistoryState>().0;
+    let household = use_context::<HouseholdState>();
+    let mut shopping = use_context::<ShoppingState>();
+
+    let mut name = use_signal(String::new);
+    let mut quantity_str = use_signal(|| "1".to_string());
+    let mut message = use_signal(|| Option::<String>::None);
+    // 入库前手动修改过的过期日期
+    let mut expiry_overrides = use_signal(HashMap::<Uuid, String>::new);
+
+    let clock = use_context::<ClockState>();
+    let today = clock.today();
+
+    let mut handle_add = move || {
+        let text = name.read().trim().to_string();
+        if text.is_empty() {
+            return;
+        }
+        let quantity = quantity_str.read().parse::<u32>().ok().filter(|q| *q >= 1).unwrap_or(1);
+        let mut entry = ShoppingEntry::new(text, quantity, None, clock.now());
+        let shelf_life = known_shelf_life(&entry, &inventory.0.peek(), &history.peek());
+        entry.set_shelf_life_days(shelf_life);
+        shopping.add(entry);
+        name.set(String::new());
+        quantity_str.set("1".to_string());
+    };
+
+    // 已勾选的项转换为库存物品，并从清单中移除
+    let handle_stock = move |_| {
+        let overrides = expiry_overrides.read().clone();
+        let bought: Vec<ShoppingEntry> = shopping.0.read().iter().filter(|e| e.checked()).cloned().collect();
+        if bought.is_empty() {
+            return;
+        }
+        for entry in &bought {
+            let expiry = overrides
+                .get(&entry.id())
+                .and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok())
+                .unwrap_or_else(|| entry.expiry_from(today));
+            let mut item = entry.to_item(expiry, today);
+            // 清单上没有选位置和分类，沿用库存里同名物品的，这样会合并为它的新批次
+            if let Some(existing) = inventory.0.peek().iter().find(|i| i.emoji() == item.emoji() && i.name() == item.name()) {
+                item = item.with_location(existing.location()).with_category(existing.category().map(str::to_string));
+            }
+            let quantity = item.quantity();
+            let stocked = inventory.stock(item);
+            history
+                .write()
+                .push(HistoryEvent::new(&stocked, EventKind::Added, quantity, false, household.member()));
+        }
+        shopping.0.write().retain(|e| !e.checked());
+        expiry_overrides.write().clear();
+        message.set(Some(format!("已入库 {} 项", bought.len())));
+    };
+
+    let mut entries = shopping.0.read().clone();
+    // 未勾选的在前，各自按加入时间排列
+    entries.sort_by_key(|e| (e.checked(), e.added_at()));
+    let checked_count = entries.iter().filter(|e| e.checked()).count();
+    let input_class = "bg-gray-50 border border-gray-200 rounded-xl px-4 py-3 text-base focus:outline-none focus:ring-2 focus:ring-blue-500 focus:bg-white transition-all";
+
+    rsx! {
+        div { class: "flex flex-col p-6 max-w-2xl mx-auto min-h-screen bg-white",
+            // Top Bar
+            div { class: "flex items-center mb-6",
+                Link { to: Route::Home { query: Default::default() }, class: "material-symbols-outlined text-gray-600 p-2 -ml-2 rounded-full hover:bg-gray-100 transition-colors", "arrow_back" }
+                h2 { class: "flex-1 text-center text-lg font-semibold text-gray-900 pr-8", "购物清单" }
+            }
+
+            // 1. 手动添加
+            div { class: "flex items-center gap-3 mb-6",
+                input {
+                    r#type: "text",
+                    class: "flex-1 min-w-0 {input_class}",
+                    value: "{name}",
+                    oninput: move |evt| name.set(evt.value()),
+                    onkeydown: move |evt| {
+                        if evt.key() == Key::Enter {
+                            handle_add();
+                        }
+                    },
+                    placeholder: "例如：🥛牛奶"
+                }
+                input {
+                    r#type: "number",
+                    min: "1",
+                    step: "1",
+                    class: "w-20 {input_class}",
+                    value: "{quantity_str}",
+                    oninput: move |evt| quantity_str.set(evt.value())
+                }
+                button {
+                    class: "material-symbols-outlined p-3 bg-blue-600 hover:bg-blue-700 text-white rounded-xl transition-colors",
+                    onclick: move |_| handle_add(),
+                    "add"
+                }
+            }
+
+            if let Some(msg) = message() {
+                div { class: "bg-green-50 text-green-700 p-4 rounded-xl mb-4 shadow-sm", "{msg}" }
+            }
+
+            // 2. 清单
+            if entries.is_empty() {
+                span { class: "text-sm text-gray-400", "清单是空的" }
+            }
+            for entry in entries {
+                div { key: "{entry.id()}", class: "flex items-center gap-3 py-3 border-b border-gray-100 last:border-b-0",
+                    button {
+                        class: if entry.checked() {
+                            "material-symbols-outlined text-blue-600"
+                        } else {
+                            "material-symbols-outlined text-gray-300 hover:text-gray-400"
+                        },
+                        "aria-pressed": "{entry.checked()}",
+                        onclick: {
+                            let id = entry.id();
+                            move |_| {
+                                if let Some(e) = shopping.0.write().iter_mut().find(|e| e.id() == id) {
+                                    e.toggle();
+                                }
+                            }
+                        },
+                        if entry.checked() { "check_box" } else { "check_box_outline_blank" }
+                    }
+                    span { class: if entry.checked() { "flex-1 text-gray-400 line-through" } else { "flex-1 text-gray-900" },
+                        "{entry.emoji()}{entry.name()}"
+                        if entry.quantity() > 1 {
+                            span { class: "text-sm text-gray-400 ml-1", "x{entry.quantity()}" }
+                        }
+                    }
+                    // 勾选后可在入库前调整过期日期
+                    if entry.checked() {
+                        input {
+                            r#type: "date",
+                            class: "bg-gray-50 border border-gray-200 rounded-lg px-2 py-1 text-sm",
+                            value: expiry_overrides
+                                .read()
+                                .get(&entry.id())
+                                .cloned()
+                                .unwrap_or_else(|| entry.expiry_from(today).format("%Y-%m-%d").to_string()),
+                            oninput: {
+                                let id = entry.id();
+                                move |evt: FormEvent| {
+                                    expiry_overrides.write().insert(id, evt.value());
+                                }
+                            }
+                        }
+                    }
+                    button {
+                        class: "material-symbols-outlined p-1 rounded-full text-gray-400 hover:bg-gray-100",
+                        onclick: {
+                            let id = entry.id();
+                            move |_| shopping.0.write().retain(|e| e.id() != id)
+                        },
+                        "delete"
+                    }
+                }
+            }
+
+            div { class: "flex-1" }
+
+            // 3. 入库
+            button {
+                class: "w-full bg-blue-600 hover:bg-blue-700 text-white font-medium py-4 rounded-xl shadow-lg shadow-blue-600/20 transition-all active:scale-95 text-lg mt-4 disabled:opacity-50",
+                disabled: checked_count == 0,
+                onclick: handle_stock,
+                "入库已买到的 {checked_count} 项"
+            }
+        }
+    }
+}
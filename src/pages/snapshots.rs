@@ -0,0 +1,91 @@
+use crate::models::{Snapshot, SnapshotKind};
+use crate::router::Route;
+use crate::state::{ClockState, InventoryState, SnapshotState};
+use dioxus::prelude::*;
+
+/// 本地快照页：列出自动保存的历史库存，并可恢复到任一时间点
+#[component]
+pub fn Snapshots() -> Element {
+    let mut inventory = use_context::<InventoryState>().0;
+    let mut snapshots = use_context::<SnapshotState>();
+    let clock = use_context::<ClockState>();
+    let mut message = use_signal(|| Option::<String>::None);
+
+    let mut restore = move |snapshot: Snapshot| {
+        let confirmed = web_sys::window()
+            .and_then(|w| w.confirm_with_message("恢复后当前库存将被替换，确定继续吗？").ok())
+            .unwrap_or(false);
+        if !confirmed {
+            return;
+        }
+        // 恢复前保存当前状态，恢复本身也可以撤回
+        snapshots.capture(&inventory.peek(), SnapshotKind::BeforeRestore, clock.now());
+        let count = snapshot.items().len();
+        inventory.set(snapshot.items().to_vec());
+        message.set(Some(format!("已恢复到 {}，共 {} 个物品", snapshot.taken_at().format("%m-%d %H:%M"), count)));
+    };
+
+    let current = inventory.read().clone();
+    let list = snapshots.0.read().clone();
+
+    rsx! {
+        div { class: "flex flex-col p-6 max-w-2xl mx-auto min-h-screen bg-white",
+            // Top Bar
+            div { class: "flex items-center mb-6",
+                Link { to: Route::Home { query: Default::default() }, class: "material-symbols-outlined text-gray-600 p-2 -ml-2 rounded-full hover:bg-gray-100 transition-colors", "arrow_back" }
+                h2 { class: "flex-1 text-center text-lg font-semibold text-gray-900 pr-8", "历史快照" }
+            }
+
+            p { class: "text-sm text-gray-500 mb-6",
+                "每小时第一次改动库存时，自动保存改动前的库存：最近 24 小时每小时一份，最近 7 天每天一份，最近 4 周每周一份。导入、恢复和批量操作前也会各保存一份，保留最近 5 份。快照占用空间过多时会先删除最旧的自动快照。"
+            }
+
+            if let Some(msg) = message() {
+                div { class: "bg-green-50 text-green-700 p-4 rounded-xl mb-4 shadow-sm", "{msg}" }
+            }
+
+            if list.is_empty() {
+                span { class: "text-sm text-gray-400", "暂无快照" }
+            }
+            for snapshot in list {
+                {
+                    let diff = snapshot.diff(&current);
+                    rsx! {
+                        div { key: "{snapshot.id()}", class: "flex items-center justify-between py-3 border-b border-gray-100 last:border-b-0",
+                            div { class: "flex flex-col gap-1",
+                                div { class: "flex items-center gap-2",
+                                    span { class: "text-sm text-gray-900",
+                                        {snapshot.taken_at().format("%Y-%m-%d %H:%M").to_string()}
+                                    }
+                                    span { class: "px-2 py-0.5 rounded-full bg-gray-100 text-gray-500 text-xs", "{snapshot.kind().label()}" }
+                                }
+                                span { class: "text-xs text-gray-500",
+                                    "{snapshot.items().len()} 个物品"
+                                    if diff.is_empty() {
+                                        " · 与当前一致"
+                                    } else {
+                                        if diff.restored > 0 {
+                                            span { class: "text-green-600", " · +{diff.restored} 找回" }
+                                        }
+                                        if diff.dropped > 0 {
+                                            span { class: "text-red-600", " · -{diff.dropped} 移除" }
+                                        }
+                                        if diff.changed > 0 {
+                                            span { class: "text-amber-600", " · {diff.changed} 变化" }
+                                        }
+                                    }
+                                }
+                            }
+                            button {
+                                class: "px-3 py-1.5 bg-blue-50 text-blue-600 rounded-lg text-xs font-medium hover:bg-blue-100 transition-colors disabled:opacity-50",
+                                disabled: diff.is_empty(),
+                                onclick: move |_| restore(snapshot.clone()),
+                                "恢复"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,11 +1,40 @@
-use crate::pages::{AddItem, Home};
+use crate::models::InventoryQuery;
+use crate::pages::{
+    AddItem, AppearanceSettings, BackupSettings, Calendar, Categories, DeveloperSettings, GestureSettings, Home, HouseholdSettings, JoinHousehold, Plan, Recipes, Report, Shopping, Snapshots,
+};
 use dioxus::prelude::*;
 
 #[derive(Routable, Clone)]
 #[rustfmt::skip]
 pub enum Route {
-    #[route("/")]
-    Home {},
+    #[route("/?:..query")]
+    Home { query: InventoryQuery },
     #[route("/add")]
     AddItem {},
+    #[route("/household")]
+    HouseholdSettings {},
+    #[route("/join/:code")]
+    JoinHousehold { code: String },
+    #[route("/backup")]
+    BackupSettings {},
+    #[route("/shopping")]
+    Shopping {},
+    #[route("/snapshots")]
+    Snapshots {},
+    #[route("/categories")]
+    Categories {},
+    #[route("/report")]
+    Report {},
+    #[route("/recipes")]
+    Recipes {},
+    #[route("/plan")]
+    Plan {},
+    #[route("/calendar")]
+    Calendar {},
+    #[route("/gestures")]
+    GestureSettings {},
+    #[route("/appearance")]
+    AppearanceSettings {},
+    #[route("/developer")]
+    DeveloperSettings {},
 }
@@ -1,6 +1,182 @@
-use crate::models::Item;
+use crate::models::{
+    bundled_recipes, record_snapshot, reservation_shortfall, Appearance, reserved_amount, Category, HistoryEvent, HouseholdSession, Item, Meal, Member,
+    Recipe, ShoppingEntry, Snapshot, SnapshotKind, SwipeSettings, WebDavSettings,
+};
+use crate::clock::{Clock, SimulatedClock, SystemClock};
+use crate::error::UkeepError;
+use crate::sync::SyncStatus;
+use chrono::{NaiveDate, NaiveDateTime};
 use dioxus::prelude::*;
+use std::rc::Rc;
 
 /// 全局状态 Context Key
 #[derive(Clone, Copy)]
 pub struct InventoryState(pub Signal<Vec<Item>>);
+
+impl InventoryState {
+    /// 入库：库存里已有同一种东西时合并为新的批次，返回入库后的物品
+    pub fn stock(&mut self, item: Item) -> Item {
+        let mut items = self.0.write();
+        let mut item = item;
+        for existing in items.iter_mut() {
+            match existing.absorb(item) {
+                Ok(()) => return existing.clone(),
+                Err(rejected) => item = rejected,
+            }
+        }
+        items.push(item.clone());
+        item
+    }
+}
+
+/// 库存操作记录 Context Key
+#[derive(Clone, Copy)]
+pub struct HistoryState(pub Signal<Vec<HistoryEvent>>);
+
+/// 当前加入的家庭 Context Key，未加入时为 None
+#[derive(Clone, Copy)]
+pub struct HouseholdState(pub Signal<Option<HouseholdSession>>);
+
+impl HouseholdState {
+    /// 当前设备对应的家庭成员，用于记录“谁”操作了物品
+    pub fn member(&self) -> Option<Member> {
+        self.0.read().as_ref().map(|s| s.member.clone())
+    }
+}
+
+/// 家庭同步状态 Context Key
+#[derive(Clone, Copy)]
+pub struct SyncState(pub Signal<SyncStatus>);
+
+/// WebDAV 备份设置 Context Key
+#[derive(Clone, Copy)]
+pub struct BackupState(pub Signal<WebDavSettings>);
+
+/// 本地自动快照 Context Key
+#[derive(Clone, Copy)]
+pub struct SnapshotState(pub Signal<Vec<Snapshot>>);
+
+impl SnapshotState {
+    /// 在导入、恢复等会覆盖整个库存的操作之前保存一份快照，`now` 取自 `ClockState::now`
+    pub fn capture(&mut self, items: &[Item], kind: SnapshotKind, now: NaiveDateTime) {
+        record_snapshot(&mut self.0.write(), Snapshot::new_at(items, kind, now), now);
+    }
+}
+
+/// 最近一次保存到本地存储失败的原因 Context Key，例如存储空间已满；用户关闭提示后清空
+#[derive(Clone, Copy)]
+pub struct StorageErrorState(pub Signal<Option<UkeepError>>);
+
+impl StorageErrorState {
+    /// 记录一次保存的结果，失败时提示用户
+    pub fn report(&self, result: Result<(), UkeepError>) {
+        if let Err(e) = result {
+            let mut error = self.0;
+            error.set(Some(e));
+        }
+    }
+}
+
+/// 购物清单 Context Key
+#[derive(Clone, Copy)]
+pub struct ShoppingState(pub Signal<Vec<ShoppingEntry>>);
+
+impl ShoppingState {
+    /// 加入清单；已有同名且未勾选的项时只增加数量
+    pub fn add(&mut self, entry: ShoppingEntry) {
+        let mut entries = self.0.write();
+        match entries
+            .iter_mut()
+            .find(|e| !e.checked() && e.emoji() == entry.emoji() && e.name() == entry.name())
+        {
+            Some(existing) => existing.add_quantity(entry.quantity()),
+            None => entries.push(entry),
+        }
+    }
+}
+
+/// 用户自定义分类 Context Key，内置分类不保存在这里
+#[derive(Clone, Copy)]
+pub struct CategoryState(pub Signal<Vec<Category>>);
+
+impl CategoryState {
+    /// 内置分类加上自定义分类
+    pub fn all(&self) -> Vec<Category> {
+        Category::all(&self.0.read())
+    }
+}
+
+/// 用户导入的菜谱 Context Key，内置菜谱不保存在这里
+#[derive(Clone, Copy)]
+pub struct RecipeState(pub Signal<Vec<Recipe>>);
+
+impl RecipeState {
+    /// 内置菜谱加上导入的菜谱
+    pub fn all(&self) -> Vec<Recipe> {
+        let mut all = bundled_recipes().to_vec();
+        all.extend(self.0.read().iter().cloned());
+        all
+    }
+}
+
+/// 一周饭菜计划 Context Key
+#[derive(Clone, Copy)]
+pub struct PlanState(pub Signal<Vec<Meal>>);
+
+impl PlanState {
+    /// 某个物品被所有计划预留的总量
+    pub fn reserved(&self, item: &Item) -> f64 {
+        reserved_amount(&self.0.read(), item.id(), None)
+    }
+
+    /// 从物品中用掉 `amount` 会挤占的预留数量，不影响计划时为 0
+    pub fn shortfall(&self, item: &Item, amount: f64) -> f64 {
+        reservation_shortfall(&self.0.peek(), item, amount)
+    }
+}
+
+/// 左右滑动操作设置 Context Key
+#[derive(Clone, Copy)]
+pub struct SwipeState(pub Signal<SwipeSettings>);
+
+/// 外观设置 Context Key
+#[derive(Clone, Copy)]
+pub struct AppearanceState(pub Signal<Appearance>);
+
+/// 时间来源 Context Key：依赖“今天”的页面都从这里取时间，测试或开发者设置中可以替换
+#[derive(Clone, Copy)]
+pub struct ClockState(pub Signal<Rc<dyn Clock>>);
+
+impl ClockState {
+    /// 按开发者设置创建：设置了模拟日期时使用该日期，否则使用系统时间
+    pub fn simulating(date: Option<NaiveDate>) -> Self {
+        Self(Signal::new(clock_for(date)))
+    }
+
+    /// 当前的本地时间
+    pub fn now(&self) -> NaiveDateTime {
+        self.0.read().now()
+    }
+
+    /// 今天的日期
+    pub fn today(&self) -> NaiveDate {
+        self.0.read().today()
+    }
+
+    /// 模拟的日期，使用真实日期时为 None
+    pub fn simulated_date(&self) -> Option<NaiveDate> {
+        self.0.read().simulated_date()
+    }
+
+    /// 改为模拟某一天，传入 None 恢复真实日期
+    pub fn simulate(&mut self, date: Option<NaiveDate>) {
+        self.0.set(clock_for(date));
+    }
+}
+
+fn clock_for(date: Option<NaiveDate>) -> Rc<dyn Clock> {
+    match date {
+        Some(date) => Rc::new(SimulatedClock::new(date)),
+        None => Rc::new(SystemClock),
+    }
+}
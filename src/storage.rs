@@ -1,83 +1,524 @@
-use crate::models::Item;
+use crate::crypto;
+use crate::error::{FileProblem, RowError, RowField, UkeepError};
+use crate::models::{
+    Appearance, Category, Currency, HistoryEvent, HouseholdSession, Item, Location, Meal, Price, Quantity, Recipe, ShoppingEntry, Snapshot,
+    SwipeSettings, Unit, WebDavSettings,
+};
+use crate::utils::{parse_csv, write_csv};
+use chrono::{NaiveDate, NaiveDateTime};
 use gloo_storage::{LocalStorage, Storage};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
 use wasm_bindgen::JsCast;
 use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url};
 
 const STORAGE_KEY: &str = "ukeep_inventory";
+const HISTORY_KEY: &str = "ukeep_history";
+const HOUSEHOLD_KEY: &str = "ukeep_household";
+const WEBDAV_KEY: &str = "ukeep_webdav";
+const SNAPSHOTS_KEY: &str = "ukeep_snapshots";
+const SHOPPING_KEY: &str = "ukeep_shopping";
+const CATEGORIES_KEY: &str = "ukeep_categories";
+const RECIPES_KEY: &str = "ukeep_recipes";
+const MEAL_PLAN_KEY: &str = "ukeep_meal_plan";
+const SWIPE_KEY: &str = "ukeep_swipe";
+const SIMULATED_DATE_KEY: &str = "ukeep_simulated_date";
+const APPEARANCE_KEY: &str = "ukeep_appearance";
 
-/// 从 LocalStorage 加载库存数据
-/// 如果读取失败或数据损坏，返回空数组并清空存储
-pub fn load_inventory() -> Vec<Item> {
-    match LocalStorage::get::<Vec<Item>>(STORAGE_KEY) {
-        Ok(items) => items,
+// 所有数据的 key，清空或丢弃演示数据时逐个删除
+const DATA_KEYS: [&str; 12] = [
+    STORAGE_KEY,
+    HISTORY_KEY,
+    HOUSEHOLD_KEY,
+    WEBDAV_KEY,
+    SNAPSHOTS_KEY,
+    SHOPPING_KEY,
+    CATEGORIES_KEY,
+    RECIPES_KEY,
+    MEAL_PLAN_KEY,
+    SWIPE_KEY,
+    SIMULATED_DATE_KEY,
+    APPEARANCE_KEY,
+];
+
+// 是否处于演示模式，本身不属于任何命名空间
+const DEMO_MODE_KEY: &str = "ukeep_demo_mode";
+// 演示模式下所有数据的 key 加上这个前缀，与真实数据互不影响
+const DEMO_PREFIX: &str = "demo:";
+
+// 启动时读取一次的模式。切换模式总会重新加载页面，所以本次运行中不会变；
+// 切换后、重新加载前的保存仍写回原来的命名空间，不会把演示数据写进真实库存
+static DEMO_MODE: OnceLock<bool> = OnceLock::new();
+
+/// 是否处于演示模式：数据读写都在独立的命名空间中，不会碰到真实库存
+pub fn is_demo_mode() -> bool {
+    *DEMO_MODE.get_or_init(|| LocalStorage::get::<bool>(DEMO_MODE_KEY).unwrap_or(false))
+}
+
+// 演示命名空间中的 key
+fn demo_key(key: &str) -> String {
+    format!("{}{}", DEMO_PREFIX, key)
+}
+
+// 按当前模式选择实际使用的 key
+fn namespaced(key: &str) -> String {
+    if is_demo_mode() {
+        demo_key(key)
+    } else {
+        key.to_string()
+    }
+}
+
+// 删除演示命名空间中的所有数据
+fn discard_demo_data() {
+    for key in DATA_KEYS {
+        LocalStorage::delete(demo_key(key));
+    }
+}
+
+// 重新加载页面，让全局状态从切换后的命名空间重新读取
+fn reload_page() {
+    if let Some(window) = web_sys::window() {
+        let _ = window.location().reload();
+    }
+}
+
+/// 进入演示模式：清掉上次留下的演示数据，填入 `items` 作为演示库存，然后重新加载页面
+pub fn enter_demo_mode(items: &[Item]) {
+    discard_demo_data();
+    // 本次运行仍是真实模式，演示库存直接写进演示命名空间
+    let entered = LocalStorage::set(demo_key(STORAGE_KEY), items)
+        .and_then(|()| LocalStorage::set(DEMO_MODE_KEY, true))
+        .map_err(UkeepError::from);
+    if let Err(e) = entered {
+        log::error!("Failed to enter demo mode: {}", e);
+        return;
+    }
+    reload_page();
+}
+
+/// 退出演示模式：丢弃演示期间的所有数据，然后重新加载页面回到真实库存
+pub fn exit_demo_mode() {
+    discard_demo_data();
+    LocalStorage::delete(DEMO_MODE_KEY);
+    reload_page();
+}
+
+// 读取指定 key 的数据，如果读取失败或数据损坏，返回默认值并清空该 key
+fn load_or_default<T: DeserializeOwned + Default>(key: &str, what: &str) -> T {
+    let key = namespaced(key);
+    match LocalStorage::get::<T>(&key) {
+        Ok(value) => value,
         Err(e) => {
             // 数据损坏或不存在，清空存储
-            log::warn!("Failed to load inventory: {:?}", e);
-            let _ = LocalStorage::delete(STORAGE_KEY);
-            Vec::new()
+            log::warn!("Failed to load {}: {:?}", what, e);
+            LocalStorage::delete(key);
+            T::default()
         }
     }
 }
 
+// 写入指定 key 的数据，失败时记录日志并返回错误，由界面提示用户（例如存储空间已满）
+fn save<T: Serialize + ?Sized>(key: &str, what: &str, value: &T) -> Result<(), UkeepError> {
+    LocalStorage::set(namespaced(key), value).map_err(UkeepError::from).inspect_err(|e| {
+        log::error!("Failed to save {}: {}", what, e);
+    })
+}
+
+/// 从 LocalStorage 加载库存数据
+/// 如果读取失败或数据损坏，返回空数组并清空存储；旧数据缺少的入库日期按 `today` 补上
+pub fn load_inventory(today: NaiveDate) -> Vec<Item> {
+    let mut items: Vec<Item> = load_or_default(STORAGE_KEY, "inventory");
+    items.iter_mut().for_each(|item| item.settle_added_on(today));
+    items
+}
+
 /// 保存库存数据到 LocalStorage
-pub fn save_inventory(items: &Vec<Item>) {
-    if let Err(e) = LocalStorage::set(STORAGE_KEY, items) {
-        log::error!("Failed to save inventory: {:?}", e);
-    }
+pub fn save_inventory(items: &[Item]) -> Result<(), UkeepError> {
+    save(STORAGE_KEY, "inventory", items)
+}
+
+/// 加载库存操作记录
+pub fn load_history() -> Vec<HistoryEvent> {
+    load_or_default(HISTORY_KEY, "history")
+}
+
+/// 保存库存操作记录
+pub fn save_history(events: &[HistoryEvent]) -> Result<(), UkeepError> {
+    save(HISTORY_KEY, "history", events)
+}
+
+/// 加载当前加入的家庭，未加入时返回 None
+pub fn load_household() -> Option<HouseholdSession> {
+    load_or_default(HOUSEHOLD_KEY, "household")
+}
+
+/// 保存当前加入的家庭，传入 None 表示已退出
+pub fn save_household(session: &Option<HouseholdSession>) -> Result<(), UkeepError> {
+    save(HOUSEHOLD_KEY, "household", session)
+}
+
+/// 加载 WebDAV 备份设置
+pub fn load_webdav_settings() -> WebDavSettings {
+    load_or_default(WEBDAV_KEY, "webdav settings")
+}
+
+/// 保存 WebDAV 备份设置
+pub fn save_webdav_settings(settings: &WebDavSettings) -> Result<(), UkeepError> {
+    save(WEBDAV_KEY, "webdav settings", settings)
+}
+
+/// 加载购物清单
+pub fn load_shopping_list() -> Vec<ShoppingEntry> {
+    load_or_default(SHOPPING_KEY, "shopping list")
+}
+
+/// 保存购物清单
+pub fn save_shopping_list(entries: &[ShoppingEntry]) -> Result<(), UkeepError> {
+    save(SHOPPING_KEY, "shopping list", entries)
+}
+
+/// 加载本地自动快照
+pub fn load_snapshots() -> Vec<Snapshot> {
+    load_or_default(SNAPSHOTS_KEY, "snapshots")
+}
+
+/// 保存本地自动快照
+pub fn save_snapshots(snapshots: &[Snapshot]) -> Result<(), UkeepError> {
+    save(SNAPSHOTS_KEY, "snapshots", snapshots)
+}
+
+/// 加载用户自定义分类
+pub fn load_categories() -> Vec<Category> {
+    load_or_default(CATEGORIES_KEY, "categories")
+}
+
+/// 保存用户自定义分类
+pub fn save_categories(categories: &[Category]) -> Result<(), UkeepError> {
+    save(CATEGORIES_KEY, "categories", categories)
+}
+
+/// 加载用户导入的菜谱
+pub fn load_recipes() -> Vec<Recipe> {
+    load_or_default(RECIPES_KEY, "recipes")
+}
+
+/// 保存用户导入的菜谱
+pub fn save_recipes(recipes: &[Recipe]) -> Result<(), UkeepError> {
+    save(RECIPES_KEY, "recipes", recipes)
+}
+
+/// 加载一周饭菜计划
+pub fn load_meal_plan() -> Vec<Meal> {
+    load_or_default(MEAL_PLAN_KEY, "meal plan")
 }
 
-/// 清空所有存储数据
+/// 保存一周饭菜计划
+pub fn save_meal_plan(meals: &[Meal]) -> Result<(), UkeepError> {
+    save(MEAL_PLAN_KEY, "meal plan", meals)
+}
+
+/// 加载左右滑动的操作设置
+pub fn load_swipe_settings() -> SwipeSettings {
+    load_or_default(SWIPE_KEY, "swipe settings")
+}
+
+/// 保存左右滑动的操作设置
+pub fn save_swipe_settings(settings: &SwipeSettings) -> Result<(), UkeepError> {
+    save(SWIPE_KEY, "swipe settings", settings)
+}
+
+/// 加载外观设置
+pub fn load_appearance() -> Appearance {
+    load_or_default(APPEARANCE_KEY, "appearance")
+}
+
+/// 保存外观设置
+pub fn save_appearance(appearance: &Appearance) -> Result<(), UkeepError> {
+    save(APPEARANCE_KEY, "appearance", appearance)
+}
+
+/// 加载开发者设置中模拟的日期，未设置时返回 None
+pub fn load_simulated_date() -> Option<NaiveDate> {
+    load_or_default(SIMULATED_DATE_KEY, "simulated date")
+}
+
+/// 保存模拟的日期，传入 None 表示使用真实日期
+pub fn save_simulated_date(date: &Option<NaiveDate>) -> Result<(), UkeepError> {
+    save(SIMULATED_DATE_KEY, "simulated date", date)
+}
+
+/// 清空当前模式下的所有存储数据
 pub fn clear_storage() {
-    let _ = LocalStorage::delete(STORAGE_KEY);
+    for key in DATA_KEYS {
+        LocalStorage::delete(namespaced(key));
+    }
 }
 
-/// 导出数据为 JSON 文件并触发下载
-pub fn export_data(items: &Vec<Item>) -> Result<(), String> {
+/// 备份文件的格式版本，写在备份的 `version` 字段中；
+/// 早期的备份直接是物品数组，没有版本字段，仍然可以导入
+pub const BACKUP_VERSION: u32 = 1;
+
+// 备份文件的内容：带格式版本的物品列表
+#[derive(Serialize, Deserialize)]
+struct BackupFile<T> {
+    version: u32,
+    items: T,
+}
+
+/// 备份文件名前缀
+pub const BACKUP_PREFIX: &str = "ukeep_backup_";
+
+/// 生成备份文件名：加密备份使用 .ukeep 扩展名，否则为 .json
+pub fn backup_filename(now: NaiveDateTime, encrypted: bool) -> String {
+    let extension = if encrypted { "ukeep" } else { "json" };
+    format!("{}{}.{}", BACKUP_PREFIX, now.format("%Y%m%d_%H%M%S"), extension)
+}
+
+/// 序列化为备份用的 JSON，导出文件与远程备份共用这一格式
+pub fn serialize_backup(items: &[Item]) -> Result<String, UkeepError> {
+    let file = BackupFile { version: BACKUP_VERSION, items };
+    serde_json::to_string_pretty(&file).map_err(|e| UkeepError::Serialization(e.to_string()))
+}
+
+/// 导出数据为文件并触发下载
+/// 提供口令时导出为端到端加密的 .ukeep 文件，否则导出为明文 JSON，文件名带上 `now`
+pub fn export_data(items: &[Item], passphrase: Option<&str>, now: NaiveDateTime) -> Result<(), UkeepError> {
     // 序列化为 JSON
-    let json_str = serde_json::to_string_pretty(items)
-        .map_err(|e| format!("序列化失败: {}", e))?;
+    let json_str = serialize_backup(items)?;
 
+    match passphrase {
+        Some(passphrase) => {
+            let bytes = crypto::encrypt(json_str.as_bytes(), passphrase)?;
+            download_bytes(&bytes, &backup_filename(now, true), "application/octet-stream")
+        }
+        None => download_bytes(json_str.as_bytes(), &backup_filename(now, false), "application/json"),
+    }
+}
+
+/// 把库存变动记录转换为 CSV，按时间先后排列，带 UTF-8 BOM 以便表格软件识别中文
+pub fn history_to_csv(events: &[HistoryEvent]) -> String {
+    let mut sorted: Vec<&HistoryEvent> = events.iter().collect();
+    sorted.sort_by_key(|e| e.at());
+    let header = ["时间", "成员", "操作", "物品", "数量", "价值", "原因", "备注"];
+    let mut rows = vec![header.iter().map(|h| h.to_string()).collect::<Vec<_>>()];
+    for event in sorted {
+        rows.push(vec![
+            event.at().format("%Y-%m-%d %H:%M").to_string(),
+            event.member().map(|m| m.name().to_string()).unwrap_or_default(),
+            event.kind().label().to_string(),
+            format!("{}{}", event.emoji(), event.item_name()),
+            event.count().to_string(),
+            event.value().map(|v| v.to_string()).unwrap_or_default(),
+            event.reason().map(|r| r.label().to_string()).unwrap_or_default(),
+            event.note().unwrap_or_default().to_string(),
+        ]
+        .into_iter()
+        .map(|cell| guard_formula(&cell))
+        .collect());
+    }
+    format!("\u{FEFF}{}", write_csv(&rows))
+}
+
+// 以 = + - @（或制表符、回车）开头的单元格会被表格软件当作公式执行，
+// 物品名、成员名和备注都来自用户输入，前面加一个 ' 让它按文本显示
+fn guard_formula(cell: &str) -> String {
+    if cell.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{}", cell)
+    } else {
+        cell.to_string()
+    }
+}
+
+/// 导出库存变动记录为 CSV 文件并触发下载，文件名带上 `now`
+pub fn export_history(events: &[HistoryEvent], now: NaiveDateTime) -> Result<(), UkeepError> {
+    let filename = format!("ukeep_history_{}.csv", now.format("%Y%m%d_%H%M%S"));
+    download_bytes(history_to_csv(events).as_bytes(), &filename, "text/csv;charset=utf-8")
+}
+
+// 把字节内容包装为 Blob 并通过临时 <a> 元素触发下载
+fn download_bytes(bytes: &[u8], filename: &str, mime: &str) -> Result<(), UkeepError> {
     // 创建 Blob
     let array = js_sys::Array::new();
-    array.push(&wasm_bindgen::JsValue::from_str(&json_str));
+    array.push(&js_sys::Uint8Array::from(bytes));
 
     let blob_options = BlobPropertyBag::new();
-    blob_options.set_type("application/json");
+    blob_options.set_type(mime);
 
-    let blob = Blob::new_with_str_sequence_and_options(&array, &blob_options)
-        .map_err(|_| "创建 Blob 失败".to_string())?;
+    let blob = Blob::new_with_u8_array_sequence_and_options(&array, &blob_options)
+        .map_err(|_| UkeepError::DomUnavailable("Blob"))?;
 
     // 创建下载链接
     let url = Url::create_object_url_with_blob(&blob)
-        .map_err(|_| "创建 URL 失败".to_string())?;
+        .map_err(|_| UkeepError::DomUnavailable("URL"))?;
 
     // 获取 window 和 document
-    let window = web_sys::window().ok_or("无法获取 window")?;
-    let document = window.document().ok_or("无法获取 document")?;
+    let window = web_sys::window().ok_or(UkeepError::DomUnavailable("window"))?;
+    let document = window.document().ok_or(UkeepError::DomUnavailable("document"))?;
 
     // 创建临时 <a> 元素
     let anchor = document
         .create_element("a")
-        .map_err(|_| "创建元素失败")?
-        .dyn_into::<HtmlAnchorElement>()
-        .map_err(|_| "类型转换失败")?;
-
-    // 设置下载属性
-    let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
-    let filename = format!("ukeep_backup_{}.json", timestamp);
+        .ok()
+        .and_then(|element| element.dyn_into::<HtmlAnchorElement>().ok())
+        .ok_or(UkeepError::DomUnavailable("<a>"))?;
 
     anchor.set_href(&url);
-    anchor.set_download(&filename);
+    anchor.set_download(filename);
     anchor.click();
 
     // 清理 URL
-    Url::revoke_object_url(&url).map_err(|_| "清理 URL 失败")?;
+    Url::revoke_object_url(&url).map_err(|_| UkeepError::DomUnavailable("URL"))?;
 
     Ok(())
 }
 
-/// 从 JSON 字符串导入数据
-pub fn import_data_from_json(json_str: &str) -> Result<Vec<Item>, String> {
-    serde_json::from_str::<Vec<Item>>(json_str)
-        .map_err(|e| format!("数据格式错误: {}", e))
+/// 从 JSON 字符串导入数据，旧备份缺少的入库日期按 `today` 补上
+pub fn import_data_from_json(json_str: &str, today: NaiveDate) -> Result<Vec<Item>, UkeepError> {
+    let mut items = parse_backup(json_str)?;
+    items.iter_mut().for_each(|item| item.settle_added_on(today));
+    Ok(items)
+}
+
+// 解析备份文件内容，兼容早期直接保存物品数组的格式
+fn parse_backup(json_str: &str) -> Result<Vec<Item>, UkeepError> {
+    if json_str.trim().is_empty() {
+        return Err(UkeepError::InvalidFile(FileProblem::Empty));
+    }
+    let malformed = |e: serde_json::Error| UkeepError::InvalidFile(FileProblem::Malformed(e.to_string()));
+    let value: serde_json::Value = serde_json::from_str(json_str).map_err(malformed)?;
+    // 早期的备份直接是物品数组
+    if value.is_array() {
+        return serde_json::from_value(value).map_err(malformed);
+    }
+    let Some(found) = value.get("version").and_then(|v| v.as_u64()) else {
+        return Err(UkeepError::InvalidFile(FileProblem::Malformed("缺少 version 字段".to_string())));
+    };
+    if found != u64::from(BACKUP_VERSION) {
+        return Err(UkeepError::VersionMismatch {
+            found: u32::try_from(found).unwrap_or(u32::MAX),
+            supported: BACKUP_VERSION,
+        });
+    }
+    let file: BackupFile<Vec<Item>> = serde_json::from_value(value).map_err(malformed)?;
+    Ok(file.items)
+}
+
+/// 从文件内容导入数据，加密备份需要提供口令
+pub fn import_data_from_bytes(bytes: &[u8], passphrase: Option<&str>, today: NaiveDate) -> Result<Vec<Item>, UkeepError> {
+    let plain = if crypto::is_encrypted(bytes) {
+        let passphrase = passphrase.ok_or(UkeepError::PassphraseRequired)?;
+        crypto::decrypt(bytes, passphrase)?
+    } else {
+        bytes.to_vec()
+    };
+    let json_str = String::from_utf8(plain).map_err(|_| UkeepError::InvalidFile(FileProblem::Encoding))?;
+    import_data_from_json(&json_str, today)
+}
+
+// 收集 CSV 中一行的错误：某个字段解析失败时记录下来，继续检查其它字段
+struct RowErrors {
+    line: usize,
+    errors: Vec<RowError>,
+}
+
+impl RowErrors {
+    fn check<T>(&mut self, field: RowField, value: Option<&str>, parsed: Option<T>) -> Option<T> {
+        if parsed.is_none() {
+            self.errors.push(RowError {
+                line: self.line,
+                field,
+                value: value.map(str::to_string),
+            });
+        }
+        parsed
+    }
+}
+
+/// 从 CSV 导入物品，第一行为表头，列名可以是英文或中文：
+/// name/名称（必填）、expiry/过期日期（必填）、quantity/数量、unit/单位、
+/// location/位置、category/分类、price/价格（总价）、currency/货币。
+/// 有无效的行时不导入任何物品，返回所有出错的行；`today` 为入库日期
+pub fn import_items_from_csv(text: &str, categories: &[Category], today: NaiveDate) -> Result<Vec<Item>, UkeepError> {
+    let mut rows = parse_csv(text).into_iter();
+    let header = rows.next().ok_or(UkeepError::InvalidFile(FileProblem::Empty))?;
+    let column = |names: &[&str]| {
+        header
+            .iter()
+            .position(|h| names.iter().any(|n| h.trim().eq_ignore_ascii_case(n)))
+    };
+    let missing = |name| UkeepError::InvalidFile(FileProblem::MissingColumn(name));
+    let name_col = column(&["name", "名称"]).ok_or(missing("name"))?;
+    let expiry_col = column(&["expiry", "expiry_date", "过期日期"]).ok_or(missing("expiry"))?;
+    let quantity_col = column(&["quantity", "数量"]);
+    let unit_col = column(&["unit", "单位"]);
+    let location_col = column(&["location", "位置"]);
+    let category_col = column(&["category", "分类"]);
+    let price_col = column(&["price", "价格"]);
+    let currency_col = column(&["currency", "货币"]);
+
+    let mut items = Vec::new();
+    let mut errors = Vec::new();
+    // 表头是第 1 行
+    for (line, row) in rows.enumerate().map(|(i, row)| (i + 2, row)) {
+        let cell = |col: Option<usize>| col.and_then(|c| row.get(c)).map(|v| v.trim()).filter(|v| !v.is_empty());
+        // 一次报告所有无效的字段，而不是遇到第一个就停下
+        let mut row_errors = RowErrors { line, errors: Vec::new() };
+
+        let name = row_errors.check(RowField::Name, None, cell(Some(name_col)));
+        let expiry_text = cell(Some(expiry_col));
+        let parsed_expiry = expiry_text
+            .and_then(|t| ["%Y-%m-%d", "%Y/%m/%d"].iter().find_map(|f| NaiveDate::parse_from_str(t, f).ok()));
+        let expiry = row_errors.check(RowField::Expiry, expiry_text, parsed_expiry);
+        let amount = match cell(quantity_col) {
+            Some(q) => {
+                let parsed = q.parse::<f64>().ok().filter(|q| q.is_finite() && *q > 0.0);
+                row_errors.check(RowField::Quantity, Some(q), parsed)
+            }
+            None => Some(1.0),
+        };
+        let unit = match cell(unit_col) {
+            Some(u) => row_errors.check(RowField::Unit, Some(u), Unit::parse(u)),
+            None => Some(Unit::default()),
+        };
+        let location = match cell(location_col) {
+            Some(l) => {
+                let parsed = Location::ALL.into_iter().find(|loc| loc.key().eq_ignore_ascii_case(l) || loc.label() == l);
+                row_errors.check(RowField::Location, Some(l), parsed)
+            }
+            None => Some(Location::default()),
+        };
+        // 分类可以写标识或名称，找不到时视为未分类
+        let category = cell(category_col)
+            .and_then(|c| categories.iter().find(|cat| cat.id() == c || cat.name() == c))
+            .map(|c| c.id().to_string());
+        let currency = match cell(currency_col) {
+            Some(c) => row_errors.check(RowField::Currency, Some(c), Currency::parse(c)),
+            None => Some(Currency::default()),
+        };
+        let price = match cell(price_col) {
+            Some(p) => {
+                let parsed = p.parse::<f64>().ok().filter(|p| p.is_finite() && *p >= 0.0);
+                row_errors.check(RowField::Price, Some(p), parsed).map(Some)
+            }
+            None => Some(None),
+        };
+
+        if let (Some(name), Some(expiry), Some(amount), Some(unit), Some(location), Some(currency), Some(price)) =
+            (name, expiry, amount, unit, location, currency, price)
+        {
+            items.push(
+                Item::new_with_amount(name.to_string(), expiry, Quantity::new(amount, unit), today)
+                    .with_location(location)
+                    .with_category(category)
+                    .with_total_price(price.map(|p| Price::new(p, currency))),
+            );
+        }
+        errors.extend(row_errors.errors);
+    }
+    if !errors.is_empty() {
+        return Err(UkeepError::InvalidRows(errors));
+    }
+    Ok(items)
 }
@@ -0,0 +1,49 @@
+use super::http::FetchTransport;
+use super::webdav::WebDavClient;
+use crate::error::UkeepError;
+use crate::models::{Item, WebDavSettings};
+use crate::state::{BackupState, InventoryState};
+use crate::storage::is_demo_mode;
+use chrono::{Local, NaiveDateTime};
+use dioxus::prelude::*;
+use gloo_timers::future::TimeoutFuture;
+
+// 检查是否需要自动备份的间隔（毫秒）
+const CHECK_INTERVAL_MS: u32 = 10 * 60 * 1000;
+
+/// 根据设置创建 WebDAV 客户端
+pub fn webdav_client(settings: &WebDavSettings) -> WebDavClient<FetchTransport> {
+    WebDavClient::new(&settings.url, &settings.username, &settings.password, FetchTransport)
+}
+
+/// 立即备份到 WebDAV，返回上传的文件名
+pub async fn run_backup(settings: &WebDavSettings, items: &[Item], now: NaiveDateTime) -> Result<String, UkeepError> {
+    webdav_client(settings)
+        .backup(items, settings.encryption.as_ref(), now, settings.keep as usize)
+        .await
+}
+
+/// 按设置的频率自动备份到 WebDAV
+pub fn use_scheduled_backup() {
+    let inventory = use_context::<InventoryState>().0;
+    let mut settings = use_context::<BackupState>().0;
+
+    use_future(move || async move {
+        loop {
+            let now = Local::now().naive_local();
+            let current = settings.peek().clone();
+            // 演示数据不备份
+            if current.is_due(now) && !is_demo_mode() {
+                let items = inventory.peek().clone();
+                match run_backup(&current, &items, now).await {
+                    Ok(name) => {
+                        log::info!("[Backup] Uploaded {}", name);
+                        settings.write().last_backup = Some(now);
+                    }
+                    Err(e) => log::error!("[Backup] Scheduled backup failed: {}", e),
+                }
+            }
+            TimeoutFuture::new(CHECK_INTERVAL_MS).await;
+        }
+    });
+}
@@ -0,0 +1,270 @@
+use super::http::{HttpRequest, HttpTransport};
+use crate::crypto::DerivedKey;
+use crate::models::{HistoryEvent, Household, Item, Member};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+// 家庭同步服务的 REST 接口约定（均为 JSON）：
+// - POST   {server}/api/households                       创建家庭   {name, member}        -> Household
+// - POST   {server}/api/households/join                  加入家庭   {code, member}        -> Household
+// - GET    {server}/api/households/{id}                  拉取库存                         -> RemoteInventory
+// - PUT    {server}/api/households/{id}/inventory        推送库存   {member_id, base_revision, inventory}
+//                                                        成功 -> {revision}；版本落后 -> 409
+// - DELETE {server}/api/households/{id}/members/{member} 退出家庭
+//
+// 启用端到端加密后，inventory 字段变为 {"ciphertext": "<base64>"}，服务器只保存密文
+
+/// 在家庭成员之间共享的数据：库存 + 操作记录
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct SharedInventory {
+    pub items: Vec<Item>,
+    #[serde(default)]
+    pub history: Vec<HistoryEvent>,
+}
+
+impl SharedInventory {
+    /// 合并两份库存：同一物品以 `preferred` 为准，已被吃完/扔掉/删除的物品不会被另一份“复活”
+    pub fn merge(preferred: &SharedInventory, other: &SharedInventory) -> SharedInventory {
+        // 操作记录按 ID 去重后合并，并按时间排序
+        let mut history = preferred.history.clone();
+        let known: HashSet<Uuid> = history.iter().map(|e| e.id()).collect();
+        history.extend(other.history.iter().filter(|e| !known.contains(&e.id())).cloned());
+        history.sort_by_key(|e| e.at());
+
+        // 以每个物品最后一条记录为准：撤销后补记的“放回了”会让物品重新出现
+        let mut removed: HashSet<Uuid> = HashSet::new();
+        for event in &history {
+            if event.removed() {
+                removed.insert(event.item_id());
+            } else {
+                removed.remove(&event.item_id());
+            }
+        }
+
+        let mut items = preferred.items.clone();
+        let present: HashSet<Uuid> = items.iter().map(|i| i.id()).collect();
+        items.extend(other.items.iter().filter(|i| !present.contains(&i.id())).cloned());
+        items.retain(|i| !removed.contains(&i.id()));
+
+        SharedInventory { items, history }
+    }
+}
+
+/// 传输中的库存：明文或端到端加密后的密文
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+enum SyncPayload {
+    Encrypted { ciphertext: String },
+    Plain(SharedInventory),
+}
+
+impl SyncPayload {
+    // 有密钥时加密，否则以明文传输
+    fn seal(inventory: &SharedInventory, key: Option<&DerivedKey>) -> Result<Self, String> {
+        let Some(key) = key else {
+            return Ok(SyncPayload::Plain(inventory.clone()));
+        };
+        let json = serde_json::to_vec(inventory).map_err(|e| format!("序列化失败: {}", e))?;
+        let sealed = key.encrypt(&json).map_err(|e| e.to_string())?;
+        Ok(SyncPayload::Encrypted {
+            ciphertext: BASE64.encode(sealed),
+        })
+    }
+
+    // 解密服务器上的数据；启用加密前留下的明文数据仍然可以读取
+    fn open(self, key: Option<&DerivedKey>) -> Result<SharedInventory, String> {
+        match self {
+            SyncPayload::Plain(inventory) => Ok(inventory),
+            SyncPayload::Encrypted { ciphertext } => {
+                let key = key.ok_or("该家庭已启用端到端加密，请先输入家庭口令")?;
+                let sealed = BASE64.decode(ciphertext).map_err(|_| "加密数据格式错误".to_string())?;
+                let json = key.decrypt(&sealed).map_err(|e| format!("解密失败: {}", e))?;
+                serde_json::from_slice(&json).map_err(|e| format!("数据格式错误: {}", e))
+            }
+        }
+    }
+}
+
+/// 服务器上的家庭库存快照（已解密）
+#[derive(Clone, Debug)]
+pub struct RemoteInventory {
+    pub household: Household,
+    pub revision: u64,
+    pub inventory: SharedInventory,
+    /// 服务器上保存的是否为密文
+    pub encrypted: bool,
+}
+
+#[derive(Deserialize)]
+struct RemoteInventoryWire {
+    household: Household,
+    revision: u64,
+    inventory: SyncPayload,
+}
+
+/// 推送库存的结果
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PushOutcome {
+    /// 推送成功，返回新的版本号
+    Pushed(u64),
+    /// 服务器上已有更新的版本，需要先合并
+    Conflict,
+}
+
+#[derive(Serialize)]
+struct CreateRequest<'a> {
+    name: &'a str,
+    member: &'a Member,
+}
+
+#[derive(Serialize)]
+struct JoinRequest<'a> {
+    code: &'a str,
+    member: &'a Member,
+}
+
+#[derive(Serialize)]
+struct PushRequest<'a> {
+    member_id: Uuid,
+    base_revision: u64,
+    inventory: &'a SyncPayload,
+}
+
+#[derive(Deserialize)]
+struct PushResponse {
+    revision: u64,
+}
+
+/// 家庭同步服务的客户端
+pub struct HouseholdClient<T: HttpTransport> {
+    server_url: String,
+    transport: T,
+    key: Option<DerivedKey>,
+}
+
+impl<T: HttpTransport> HouseholdClient<T> {
+    /// 创建客户端，`server_url` 形如 https://sync.example.com
+    pub fn new(server_url: &str, transport: T) -> Self {
+        Self {
+            server_url: server_url.trim().trim_end_matches('/').to_string(),
+            transport,
+            key: None,
+        }
+    }
+
+    /// 使用端到端加密：推送前加密库存，拉取后解密
+    pub fn with_encryption(mut self, key: Option<DerivedKey>) -> Self {
+        self.key = key;
+        self
+    }
+
+    fn endpoint(&self, path: &str) -> String {
+        format!("{}/api/households{}", self.server_url, path)
+    }
+
+    /// 创建一个新家庭，当前成员成为第一个成员
+    pub async fn create(&self, name: &str, member: &Member) -> Result<Household, String> {
+        let request = HttpRequest::new("POST", self.endpoint("")).json(&CreateRequest { name, member })?;
+        let response = self.transport.send(request).await?;
+        if !response.is_success() {
+            return Err(format!("创建家庭失败 (HTTP {})", response.status));
+        }
+        response.json()
+    }
+
+    /// 通过邀请码加入家庭
+    pub async fn join(&self, code: &str, member: &Member) -> Result<Household, String> {
+        let request = HttpRequest::new("POST", self.endpoint("/join")).json(&JoinRequest { code, member })?;
+        let response = self.transport.send(request).await?;
+        match response.status {
+            404 => Err("邀请码无效或已过期".to_string()),
+            _ if !response.is_success() => Err(format!("加入家庭失败 (HTTP {})", response.status)),
+            _ => response.json(),
+        }
+    }
+
+    /// 拉取家庭的最新库存
+    pub async fn fetch(&self, household_id: Uuid) -> Result<RemoteInventory, String> {
+        self.fetch_with(household_id, self.key.as_ref()).await
+    }
+
+    async fn fetch_with(&self, household_id: Uuid, key: Option<&DerivedKey>) -> Result<RemoteInventory, String> {
+        let request = HttpRequest::new("GET", self.endpoint(&format!("/{}", household_id)));
+        let response = self.transport.send(request).await?;
+        if !response.is_success() {
+            return Err(format!("拉取库存失败 (HTTP {})", response.status));
+        }
+        let wire: RemoteInventoryWire = response.json()?;
+        let encrypted = matches!(wire.inventory, SyncPayload::Encrypted { .. });
+        Ok(RemoteInventory {
+            household: wire.household,
+            revision: wire.revision,
+            inventory: wire.inventory.open(key)?,
+            encrypted,
+        })
+    }
+
+    /// 推送本地库存，`base_revision` 为本地所基于的服务器版本
+    pub async fn push(
+        &self,
+        household_id: Uuid,
+        member_id: Uuid,
+        base_revision: u64,
+        inventory: &SharedInventory,
+    ) -> Result<PushOutcome, String> {
+        self.push_with(household_id, member_id, base_revision, inventory, self.key.as_ref()).await
+    }
+
+    async fn push_with(
+        &self,
+        household_id: Uuid,
+        member_id: Uuid,
+        base_revision: u64,
+        inventory: &SharedInventory,
+        key: Option<&DerivedKey>,
+    ) -> Result<PushOutcome, String> {
+        let payload = SyncPayload::seal(inventory, key)?;
+        let request = HttpRequest::new("PUT", self.endpoint(&format!("/{}/inventory", household_id))).json(
+            &PushRequest {
+                member_id,
+                base_revision,
+                inventory: &payload,
+            },
+        )?;
+        let response = self.transport.send(request).await?;
+        match response.status {
+            409 => Ok(PushOutcome::Conflict),
+            _ if !response.is_success() => Err(format!("推送库存失败 (HTTP {})", response.status)),
+            _ => Ok(PushOutcome::Pushed(response.json::<PushResponse>()?.revision)),
+        }
+    }
+
+    /// 更换家庭口令：用当前密钥解密服务器上的数据，再用新密钥加密后推送。
+    /// 服务器上的数据已经能用新密钥读取时（其他成员已经换过口令，或者还是明文）不做改动
+    pub async fn rekey(&self, household_id: Uuid, member_id: Uuid, new_key: &DerivedKey) -> Result<(), String> {
+        if self.fetch_with(household_id, Some(new_key)).await.is_ok() {
+            return Ok(());
+        }
+        let remote = self.fetch(household_id).await?;
+        match self
+            .push_with(household_id, member_id, remote.revision, &remote.inventory, Some(new_key))
+            .await?
+        {
+            PushOutcome::Pushed(_) => Ok(()),
+            PushOutcome::Conflict => Err("同步冲突，请稍后重试".to_string()),
+        }
+    }
+
+    /// 退出家庭
+    pub async fn leave(&self, household_id: Uuid, member_id: Uuid) -> Result<(), String> {
+        let request = HttpRequest::new("DELETE", self.endpoint(&format!("/{}/members/{}", household_id, member_id)));
+        let response = self.transport.send(request).await?;
+        if !response.is_success() && response.status != 404 {
+            return Err(format!("退出家庭失败 (HTTP {})", response.status));
+        }
+        Ok(())
+    }
+}
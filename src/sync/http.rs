@@ -0,0 +1,90 @@
+use gloo_net::http::{Method, RequestBuilder};
+
+/// 与具体平台无关的 HTTP 请求描述
+#[derive(Clone, Debug, PartialEq)]
+pub struct HttpRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Vec<u8>>,
+}
+
+impl HttpRequest {
+    /// 创建一个不带请求体的请求
+    pub fn new(method: &str, url: impl Into<String>) -> Self {
+        Self {
+            method: method.to_string(),
+            url: url.into(),
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+
+    /// 追加一个请求头
+    pub fn header(mut self, key: &str, value: &str) -> Self {
+        self.headers.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// 设置原始请求体
+    pub fn body(mut self, body: Vec<u8>) -> Self {
+        self.body = Some(body);
+        self
+    }
+
+    /// 设置 JSON 请求体
+    pub fn json<T: serde::Serialize>(self, value: &T) -> Result<Self, String> {
+        let body = serde_json::to_vec(value).map_err(|e| format!("序列化失败: {}", e))?;
+        Ok(self.header("Content-Type", "application/json").body(body))
+    }
+}
+
+/// HTTP 响应：只保留状态码和响应体
+#[derive(Clone, Debug, PartialEq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    /// 状态码是否为 2xx
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// 将响应体解析为 JSON
+    pub fn json<T: serde::de::DeserializeOwned>(&self) -> Result<T, String> {
+        serde_json::from_slice(&self.body).map_err(|e| format!("响应格式错误: {}", e))
+    }
+}
+
+/// 发送 HTTP 请求的抽象，浏览器中使用 fetch，测试中可替换为其它实现
+#[allow(async_fn_in_trait)]
+pub trait HttpTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse, String>;
+}
+
+/// 基于浏览器 fetch API 的实现
+#[derive(Clone, Copy, Default)]
+pub struct FetchTransport;
+
+impl HttpTransport for FetchTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse, String> {
+        let method = Method::from_bytes(request.method.as_bytes())
+            .map_err(|_| format!("不支持的请求方法: {}", request.method))?;
+        let mut builder = RequestBuilder::new(&request.url).method(method);
+        for (key, value) in &request.headers {
+            builder = builder.header(key, value);
+        }
+        let built = match request.body {
+            Some(body) => builder.body(js_sys::Uint8Array::from(body.as_slice())),
+            None => builder.build(),
+        }
+        .map_err(|e| format!("创建请求失败: {}", e))?;
+
+        let response = built.send().await.map_err(|e| format!("网络请求失败: {}", e))?;
+        let status = response.status();
+        let body = response.binary().await.map_err(|e| format!("读取响应失败: {}", e))?;
+        Ok(HttpResponse { status, body })
+    }
+}
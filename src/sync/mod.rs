@@ -0,0 +1,185 @@
+mod backup;
+mod household;
+mod http;
+mod webdav;
+
+pub use backup::{run_backup, use_scheduled_backup, webdav_client};
+pub use household::{HouseholdClient, PushOutcome, RemoteInventory, SharedInventory};
+pub use http::{FetchTransport, HttpRequest, HttpResponse, HttpTransport};
+pub use webdav::{RemoteSnapshot, WebDavClient};
+
+use crate::models::{HistoryEvent, Household, HouseholdSession, Item};
+use crate::state::{HistoryState, HouseholdState, InventoryState, SyncState};
+use crate::storage::is_demo_mode;
+use chrono::{Local, NaiveDateTime};
+use dioxus::prelude::*;
+use gloo_timers::future::TimeoutFuture;
+
+// 定时拉取家庭库存的间隔（毫秒）
+const PULL_INTERVAL_MS: u32 = 30_000;
+
+/// 家庭同步状态，用于在界面上展示
+#[derive(Clone, PartialEq, Debug, Default)]
+pub enum SyncStatus {
+    #[default]
+    Idle,
+    Syncing,
+    Synced(NaiveDateTime),
+    Failed(String),
+}
+
+// 同步任务需要读写的全部信号
+#[derive(Clone, Copy)]
+struct SyncHandles {
+    inventory: Signal<Vec<Item>>,
+    history: Signal<Vec<HistoryEvent>>,
+    session: Signal<Option<HouseholdSession>>,
+    status: Signal<SyncStatus>,
+    // 最近一次与服务器一致的数据，用于判断本地是否有未推送的改动
+    last_synced: Signal<Option<SharedInventory>>,
+    busy: Signal<bool>,
+    // 同步进行中又有本地改动或新的拉取请求，这一轮结束后需要再同步一次
+    dirty: Signal<bool>,
+}
+
+impl SyncHandles {
+    fn local(&self) -> SharedInventory {
+        SharedInventory {
+            items: self.inventory.peek().clone(),
+            history: self.history.peek().clone(),
+        }
+    }
+
+    // 用合并结果覆盖本地数据，`synced` 为服务器上已有的版本
+    fn apply(&mut self, merged: SharedInventory, synced: SharedInventory) {
+        self.last_synced.set(Some(synced));
+        self.inventory.set(merged.items);
+        self.history.set(merged.history);
+    }
+
+    // 只在确实变化时写入会话，避免触发无意义的重新同步
+    fn update_session(&mut self, revision: u64, household: Option<Household>) {
+        let changed = match self.session.peek().as_ref() {
+            Some(s) => s.revision != revision || household.as_ref().is_some_and(|h| *h != s.household),
+            None => false,
+        };
+        if !changed {
+            return;
+        }
+        if let Some(s) = self.session.write().as_mut() {
+            s.revision = revision;
+            if let Some(household) = household {
+                s.household = household;
+            }
+        }
+    }
+
+    async fn run(mut self) {
+        // 演示数据不同步到家庭
+        if self.session.peek().is_none() || is_demo_mode() {
+            return;
+        }
+        if *self.busy.peek() {
+            // 不丢掉这次改动：记下来，由正在进行的同步结束后再跑一轮
+            self.dirty.set(true);
+            return;
+        }
+        self.busy.set(true);
+        loop {
+            self.dirty.set(false);
+            let Some(current) = self.session.peek().clone() else {
+                break;
+            };
+            self.status.set(SyncStatus::Syncing);
+            let result = self.sync(current).await;
+            self.status.set(match result {
+                Ok(()) => SyncStatus::Synced(Local::now().naive_local()),
+                Err(e) => SyncStatus::Failed(e),
+            });
+            if !*self.dirty.peek() {
+                break;
+            }
+        }
+        self.busy.set(false);
+    }
+
+    async fn sync(&mut self, current: HouseholdSession) -> Result<(), String> {
+        let client = HouseholdClient::new(&current.server_url, FetchTransport).with_encryption(current.encryption.clone());
+        let household_id = current.household.id();
+        let member_id = current.member.id();
+        let local = self.local();
+        let dirty = self.last_synced.peek().as_ref() != Some(&local);
+
+        if !dirty {
+            // 本地没有改动：拉取服务器版本，以服务器数据为准合并
+            let remote = client.fetch(household_id).await?;
+            if remote.revision != current.revision {
+                let merged = SharedInventory::merge(&remote.inventory, &self.local());
+                self.apply(merged, remote.inventory);
+            }
+            self.update_session(remote.revision, Some(remote.household));
+
+            // 刚启用端到端加密：立即用密文覆盖服务器上的明文
+            if current.encryption.is_some() && !remote.encrypted {
+                let local = self.local();
+                if let PushOutcome::Pushed(revision) = client.push(household_id, member_id, remote.revision, &local).await? {
+                    self.last_synced.set(Some(local));
+                    self.update_session(revision, None);
+                }
+            }
+            return Ok(());
+        }
+
+        // 本地有改动：先尝试直接推送
+        if let PushOutcome::Pushed(revision) = client.push(household_id, member_id, current.revision, &local).await? {
+            self.last_synced.set(Some(local));
+            self.update_session(revision, None);
+            return Ok(());
+        }
+
+        // 服务器版本更新：拉取后以本地数据为准合并，再推送一次
+        let remote = client.fetch(household_id).await?;
+        let merged = SharedInventory::merge(&self.local(), &remote.inventory);
+        match client.push(household_id, member_id, remote.revision, &merged).await? {
+            PushOutcome::Pushed(revision) => {
+                self.apply(merged.clone(), merged);
+                self.update_session(revision, Some(remote.household));
+                Ok(())
+            }
+            PushOutcome::Conflict => Err("同步冲突，请稍后重试".to_string()),
+        }
+    }
+}
+
+/// 加入家庭后，在本地库存变化时自动推送，并定时拉取其他成员的改动
+pub fn use_household_sync() {
+    let handles = SyncHandles {
+        inventory: use_context::<InventoryState>().0,
+        history: use_context::<HistoryState>().0,
+        session: use_context::<HouseholdState>().0,
+        status: use_context::<SyncState>().0,
+        last_synced: use_signal(|| None),
+        busy: use_signal(|| false),
+        dirty: use_signal(|| false),
+    };
+
+    // 订阅库存、记录与会话的变化
+    use_effect(move || {
+        let _ = handles.inventory.read();
+        let _ = handles.history.read();
+        if handles.session.read().is_some() {
+            spawn(handles.run());
+        } else {
+            // 退出家庭后清空同步基线，下次加入时会把本地库存合并上去
+            let mut last_synced = handles.last_synced;
+            last_synced.set(None);
+        }
+    });
+
+    use_future(move || async move {
+        loop {
+            TimeoutFuture::new(PULL_INTERVAL_MS).await;
+            handles.run().await;
+        }
+    });
+}
@@ -0,0 +1,182 @@
+use super::http::{HttpRequest, HttpTransport};
+use crate::crypto::DerivedKey;
+use crate::error::UkeepError;
+use crate::models::Item;
+use crate::storage::{backup_filename, serialize_backup, BACKUP_PREFIX};
+use crate::utils::percent_decode;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chrono::NaiveDateTime;
+
+// PROPFIND 只请求最基本的属性，兼容 Nextcloud / ownCloud / Apache mod_dav 等实现
+const PROPFIND_BODY: &str = r#"<?xml version="1.0" encoding="utf-8"?><d:propfind xmlns:d="DAV:"><d:prop><d:resourcetype/></d:prop></d:propfind>"#;
+
+/// WebDAV 备份目录中的一份快照
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RemoteSnapshot {
+    /// 文件名，例如 ukeep_backup_20250101_080000.json
+    pub name: String,
+}
+
+impl RemoteSnapshot {
+    /// 是否为加密备份
+    pub fn is_encrypted(&self) -> bool {
+        self.name.ends_with(".ukeep")
+    }
+
+    /// 从文件名中解析备份时间
+    pub fn taken_at(&self) -> Option<NaiveDateTime> {
+        let stem = self.name.strip_prefix(BACKUP_PREFIX)?.split('.').next()?;
+        NaiveDateTime::parse_from_str(stem, "%Y%m%d_%H%M%S").ok()
+    }
+}
+
+/// WebDAV 备份客户端：`base_url` 指向存放备份的目录，例如
+/// https://cloud.example.com/remote.php/dav/files/alice/ukeep
+pub struct WebDavClient<T: HttpTransport> {
+    base_url: String,
+    authorization: Option<String>,
+    transport: T,
+}
+
+impl<T: HttpTransport> WebDavClient<T> {
+    /// 创建客户端，用户名为空时不发送认证信息
+    pub fn new(base_url: &str, username: &str, password: &str, transport: T) -> Self {
+        let authorization = (!username.is_empty())
+            .then(|| format!("Basic {}", BASE64.encode(format!("{}:{}", username, password))));
+        Self {
+            base_url: base_url.trim().trim_end_matches('/').to_string(),
+            authorization,
+            transport,
+        }
+    }
+
+    fn request(&self, method: &str, name: Option<&str>) -> HttpRequest {
+        let url = match name {
+            Some(name) => format!("{}/{}", self.base_url, name),
+            None => format!("{}/", self.base_url),
+        };
+        let request = HttpRequest::new(method, url);
+        match &self.authorization {
+            Some(auth) => request.header("Authorization", auth),
+            None => request,
+        }
+    }
+
+    async fn send(&self, request: HttpRequest) -> Result<Vec<u8>, UkeepError> {
+        let response = self.transport.send(request).await.map_err(UkeepError::Network)?;
+        match response.status {
+            401 | 403 => Err(UkeepError::Unauthorized),
+            status if !response.is_success() => Err(UkeepError::HttpStatus(status)),
+            _ => Ok(response.body),
+        }
+    }
+
+    /// 确保备份目录存在（目录已存在时服务器返回 405，视为成功）
+    pub async fn ensure_collection(&self) -> Result<(), UkeepError> {
+        match self.send(self.request("MKCOL", None)).await {
+            Err(UkeepError::HttpStatus(405)) => Ok(()),
+            result => result.map(|_| ()),
+        }
+    }
+
+    /// 上传一份备份文件
+    pub async fn upload(&self, name: &str, bytes: Vec<u8>) -> Result<(), UkeepError> {
+        let request = self
+            .request("PUT", Some(name))
+            .header("Content-Type", "application/octet-stream")
+            .body(bytes);
+        self.send(request).await.map(|_| ())
+    }
+
+    /// 列出目录中的备份，按时间从新到旧排列
+    pub async fn list(&self) -> Result<Vec<RemoteSnapshot>, UkeepError> {
+        let request = self
+            .request("PROPFIND", None)
+            .header("Depth", "1")
+            .header("Content-Type", "application/xml; charset=utf-8")
+            .body(PROPFIND_BODY.as_bytes().to_vec());
+        let body = self.send(request).await?;
+        let xml = String::from_utf8_lossy(&body);
+
+        let mut snapshots: Vec<RemoteSnapshot> = parse_hrefs(&xml)
+            .iter()
+            .filter_map(|href| href.trim_end_matches('/').rsplit('/').next().map(percent_decode))
+            .filter(|name| is_backup_name(name))
+            .map(|name| RemoteSnapshot { name })
+            .collect();
+        // 文件名中带有时间戳，按名称倒序即为从新到旧
+        snapshots.sort_by(|a, b| b.name.cmp(&a.name));
+        snapshots.dedup();
+        Ok(snapshots)
+    }
+
+    /// 下载一份备份文件的原始内容
+    pub async fn download(&self, name: &str) -> Result<Vec<u8>, UkeepError> {
+        self.send(self.request("GET", Some(name))).await
+    }
+
+    /// 删除一份备份文件
+    pub async fn delete(&self, name: &str) -> Result<(), UkeepError> {
+        self.send(self.request("DELETE", Some(name))).await.map(|_| ())
+    }
+
+    /// 只保留最新的 `keep` 份备份，返回被删除的文件名
+    pub async fn rotate(&self, keep: usize) -> Result<Vec<String>, UkeepError> {
+        let mut removed = Vec::new();
+        for snapshot in self.list().await?.into_iter().skip(keep.max(1)) {
+            self.delete(&snapshot.name).await?;
+            removed.push(snapshot.name);
+        }
+        Ok(removed)
+    }
+
+    /// 备份库存：与导出文件使用相同的序列化格式，上传后轮换旧快照，返回新文件名
+    pub async fn backup(
+        &self,
+        items: &[Item],
+        key: Option<&DerivedKey>,
+        now: NaiveDateTime,
+        keep: usize,
+    ) -> Result<String, UkeepError> {
+        let json = serialize_backup(items)?;
+        let bytes = match key {
+            Some(key) => key.encrypt(json.as_bytes())?,
+            None => json.into_bytes(),
+        };
+        let name = backup_filename(now, key.is_some());
+
+        self.ensure_collection().await?;
+        self.upload(&name, bytes).await?;
+        self.rotate(keep).await?;
+        Ok(name)
+    }
+}
+
+// 是否为 uKeep 生成的备份文件
+fn is_backup_name(name: &str) -> bool {
+    name.starts_with(BACKUP_PREFIX) && (name.ends_with(".json") || name.ends_with(".ukeep"))
+}
+
+// 从 PROPFIND 的 multistatus 响应中提取所有 href，兼容任意命名空间前缀（d:href、D:href、href）
+fn parse_hrefs(xml: &str) -> Vec<String> {
+    let mut hrefs = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start + 1..];
+        let Some(end) = rest.find('>') else {
+            break;
+        };
+        let tag = &rest[..end];
+        rest = &rest[end + 1..];
+        let local = tag.rsplit(':').next().unwrap_or(tag);
+        if !local.eq_ignore_ascii_case("href") || tag.starts_with('/') {
+            continue;
+        }
+        if let Some(close) = rest.find("</") {
+            hrefs.push(rest[..close].trim().to_string());
+            rest = &rest[close..];
+        }
+    }
+    hrefs
+}
@@ -1,19 +1,136 @@
-use crate::models::Item;
-use chrono::{Duration, Local};
-
-/// 生成模拟数据用于测试
-pub fn generate_mock_data() -> Vec<Item> {
-    let today = Local::now().date_naive();
-    vec![
-        Item::new("🥛全脂牛奶".into(), today - Duration::days(2)), // 已过期
-        Item::new("🍞切片面包".into(), today + Duration::days(1)), // 临期 (红)
-        Item::new("🍓草莓酸奶".into(), today + Duration::days(3)), // 警告 (黄)
-        Item::new("🐟三文鱼".into(), today + Duration::days(2)),   // 警告 (黄)
-        Item::new(
-            "🥚鸡蛋 (12枚)".into(),
-            today + Duration::days(10),
-        ), // 安全
-        Item::new("🍕冷冻披萨".into(), today + Duration::days(60)), // 安全
-        Item::new("🍎苹果".into(), today + Duration::days(5)),     // 安全
-    ]
+use crate::clock::Clock;
+use crate::models::{Currency, Item, Location, Price, Quantity, Unit};
+use chrono::Duration;
+
+// 模拟数据：名称、距今天的过期天数、数量、单位、位置、分类、总价（元）
+type MockRow = (&'static str, i64, f64, Unit, Location, &'static str, f64);
+
+const MOCK_ITEMS: [MockRow; 16] = [
+    ("🥛全脂牛奶", -2, 1.0, Unit::Liter, Location::Fridge, "dairy", 12.5),     // 已过期
+    ("🍞切片面包", 1, 1.0, Unit::Pack, Location::Pantry, "bakery", 9.9),       // 临期 (红)
+    ("🍓草莓酸奶", 3, 4.0, Unit::Piece, Location::Fridge, "dairy", 16.0),      // 警告 (黄)
+    ("🐟三文鱼", 2, 300.0, Unit::Gram, Location::Fridge, "seafood", 45.0),     // 警告 (黄)
+    ("🥚鸡蛋", 10, 12.0, Unit::Piece, Location::Fridge, "eggs", 18.0),        // 安全
+    ("🍕冷冻披萨", 60, 2.0, Unit::Piece, Location::Freezer, "other", 39.8),    // 安全
+    ("🍎苹果", 5, 6.0, Unit::Piece, Location::Fridge, "produce", 15.0),        // 安全
+    ("🥬菠菜", 0, 250.0, Unit::Gram, Location::Fridge, "produce", 4.5),        // 今天到期
+    ("🍅西红柿", 4, 4.0, Unit::Piece, Location::Fridge, "produce", 8.0),
+    ("🥩牛排", 45, 2.0, Unit::Piece, Location::Freezer, "meat", 88.0),
+    ("🍗鸡翅", 1, 500.0, Unit::Gram, Location::Fridge, "meat", 22.0),
+    ("🧀奶酪片", 12, 1.0, Unit::Pack, Location::Fridge, "dairy", 25.0),
+    ("🥤橙汁", 20, 1.5, Unit::Liter, Location::Pantry, "drinks", 14.0),
+    ("🍪曲奇饼干", 75, 2.0, Unit::Pack, Location::Pantry, "snacks", 19.9),
+    ("🧂生抽", 200, 500.0, Unit::Milliliter, Location::Pantry, "condiments", 11.5),
+    ("🍌香蕉", 2, 5.0, Unit::Piece, Location::Pantry, "produce", 7.5),
+];
+
+/// 生成演示和测试用的模拟数据：覆盖各个分类和存放位置，
+/// 过期日期相对 `clock` 的今天，已过期、临期、安全的物品都有
+pub fn generate_mock_data(clock: &dyn Clock) -> Vec<Item> {
+    let today = clock.today();
+    let mut items: Vec<Item> = MOCK_ITEMS
+        .iter()
+        .map(|&(name, days, amount, unit, location, category, price)| {
+            Item::new_with_amount(name.to_string(), today + Duration::days(days), Quantity::new(amount, unit), today)
+                .with_location(location)
+                .with_category(Some(category.to_string()))
+                .with_total_price(Some(Price::new(price, Currency::Cny)))
+        })
+        .collect();
+    // 先后买的两批鸡蛋，以及一盒已经开封的酸奶
+    if let Some(eggs) = items.iter_mut().find(|i| i.name() == "鸡蛋") {
+        eggs.add_batch(Quantity::pieces(6.0), today + Duration::days(3), today);
+    }
+    if let Some(yogurt) = items.iter_mut().find(|i| i.name() == "草莓酸奶") {
+        yogurt.mark_opened(today - Duration::days(1), None);
+    }
+    items
+}
+
+/// 把文本渲染为二维码 SVG 字符串，用于展示家庭邀请链接
+pub fn qr_code_svg(text: &str) -> Option<String> {
+    use qrcode::render::svg;
+    use qrcode::QrCode;
+
+    let code = QrCode::new(text.as_bytes()).ok()?;
+    Some(
+        code.render::<svg::Color>()
+            .min_dimensions(200, 200)
+            .quiet_zone(false)
+            .build(),
+    )
+}
+
+/// 解码 URL 中的 %XX 转义，无效的转义原样保留
+pub fn percent_decode(input: &str) -> String {
+    let hex = |b: u8| (b as char).to_digit(16).map(|d| d as u8);
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            if let (Some(hi), Some(lo)) = (hex(bytes[i + 1]), hex(bytes[i + 2])) {
+                out.push(hi << 4 | lo);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// 解析 CSV 文本为若干行，支持双引号包裹的字段（字段内的 "" 表示一个引号）、
+/// CRLF 换行和 UTF-8 BOM，空行会被跳过
+pub fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    let text = text.strip_prefix('\u{FEFF}').unwrap_or(text);
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => row.push(std::mem::take(&mut field)),
+            '\r' if !in_quotes => {}
+            '\n' if !in_quotes => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows.retain(|r| r.iter().any(|f| !f.trim().is_empty()));
+    rows
+}
+
+/// 把若干行写成 CSV 文本（CRLF 换行），含逗号、引号或换行的字段用双引号包裹，
+/// 与 `parse_csv` 互逆
+pub fn write_csv(rows: &[Vec<String>]) -> String {
+    let mut out = String::new();
+    for row in rows {
+        let fields: Vec<String> = row
+            .iter()
+            .map(|f| {
+                if f.contains([',', '"', '\n', '\r']) {
+                    format!("\"{}\"", f.replace('"', "\"\""))
+                } else {
+                    f.clone()
+                }
+            })
+            .collect();
+        out.push_str(&fields.join(","));
+        out.push_str("\r\n");
+    }
+    out
 }
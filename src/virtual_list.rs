@@ -0,0 +1,63 @@
+//! 长列表的窗口化渲染：只渲染视口附近的行，上下用等高的空白撑开。
+//! 不依赖浏览器，输入列表顶部相对视口的位置和视口高度，输出要渲染的行号范围。
+//!
+//! 行高使用估计值，展开批次等使个别卡片变高时只影响空白高度的精度，不影响显示哪些行
+
+use std::ops::Range;
+
+/// 每行（卡片加间距）的估计高度（像素）
+pub const ROW_HEIGHT: f64 = 88.0;
+/// 视口上下额外渲染的行数，快速滚动时不出现空白
+pub const OVERSCAN: usize = 6;
+// 还没测量到视口时渲染的行数
+const INITIAL_ROWS: usize = 12;
+
+/// 视口中可以看到的行：第一行的行号和行数，尚未按列表长度截断
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct VisibleRows {
+    first: usize,
+    count: usize,
+}
+
+impl Default for VisibleRows {
+    fn default() -> Self {
+        Self {
+            first: 0,
+            count: INITIAL_ROWS,
+        }
+    }
+}
+
+impl VisibleRows {
+    /// 根据列表顶部相对视口的位置（向下滚过列表顶部后为负数）和视口高度计算
+    pub fn measure(list_top: f64, viewport_height: f64) -> Self {
+        let scrolled = (-list_top).max(0.0);
+        let first = (scrolled / ROW_HEIGHT).floor() as usize;
+        // 列表顶部在视口中间时，只有下半部分能看到列表
+        let visible = (viewport_height - list_top.max(0.0)).max(0.0);
+        let count = (visible / ROW_HEIGHT).ceil() as usize + 1;
+        Self { first, count }
+    }
+
+    /// 要渲染的行号范围：可见行加上下各 `OVERSCAN` 行，按列表长度 `len` 截断
+    pub fn range(&self, len: usize) -> Range<usize> {
+        let start = self.first.saturating_sub(OVERSCAN).min(len);
+        let end = (self.first + self.count + OVERSCAN).min(len).max(start);
+        start..end
+    }
+
+    /// 第 `index` 行是否完整地在视口中
+    pub fn contains(&self, index: usize) -> bool {
+        index >= self.first && index + 1 < self.first + self.count
+    }
+}
+
+/// 第 `index` 行顶部距离列表顶部的估计距离
+pub fn row_offset(index: usize) -> f64 {
+    index as f64 * ROW_HEIGHT
+}
+
+/// 渲染范围上下的空白高度，使滚动条和总高度与完整渲染时一致
+pub fn padding(range: &Range<usize>, len: usize) -> (f64, f64) {
+    (row_offset(range.start), row_offset(len.saturating_sub(range.end)))
+}
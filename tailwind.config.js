@@ -1,9 +1,26 @@
 /** @type {import('tailwindcss').Config} */
+
+// 中性色取自 CSS 变量（定义在 input.css），深色主题下整体反转灰阶，
+// 页面不需要给每个元素单独写 dark: 变体
+const gray = Object.fromEntries(
+  [50, 100, 200, 300, 400, 500, 600, 700, 800, 900, 950].map((shade) => [
+    shade,
+    `rgb(var(--gray-${shade}) / <alpha-value>)`,
+  ]),
+);
+
 module.exports = {
   mode: "all",
+  darkMode: "class",
   content: ["./src/**/*.{rs,html,css}", "./dist/**/*.html"],
   theme: {
-    extend: {},
+    extend: {
+      colors: { gray },
+      // 只替换 bg-white，按钮上的 text-white 保持白色
+      backgroundColor: {
+        white: "rgb(var(--surface) / <alpha-value>)",
+      },
+    },
   },
   plugins: [],
 };
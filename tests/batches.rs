@@ -0,0 +1,55 @@
+//! 同一种东西的多个批次：合并入库，先吃最早过期的。
+
+mod common;
+
+use common::{day, expiring, today};
+use ukeep::models::{Item, Location, Quantity, Unit};
+
+fn eggs(count: f64, days: i64) -> Item {
+    expiring("🥚鸡蛋", days, Quantity::pieces(count))
+}
+
+#[test]
+fn same_product_is_absorbed_as_a_new_batch() {
+    let mut existing = eggs(6.0, 10);
+    assert!(existing.absorb(eggs(4.0, 3)).is_ok());
+    assert_eq!(existing.quantity(), Quantity::pieces(10.0));
+    assert_eq!(existing.expiry_date(), day(3));
+    assert_eq!(existing.batches().len(), 2);
+}
+
+#[test]
+fn consumption_takes_the_earliest_batch_first() {
+    let mut existing = eggs(6.0, 10);
+    existing.absorb(eggs(4.0, 3)).unwrap();
+    assert!(!existing.consume(5.0));
+    assert_eq!(existing.batches().len(), 1);
+    assert_eq!(existing.quantity(), Quantity::pieces(5.0));
+    assert_eq!(existing.expiry_date(), day(10));
+    assert!(existing.consume(5.0));
+}
+
+#[test]
+fn different_location_or_category_is_kept_separate() {
+    let mut fridge = eggs(6.0, 10);
+    let frozen = eggs(4.0, 60).with_location(Location::Freezer);
+    let rejected = fridge.absorb(frozen.clone()).unwrap_err();
+    assert_eq!(rejected, frozen);
+
+    let categorized = eggs(4.0, 3).with_category(Some("eggs".to_string()));
+    assert!(fridge.absorb(categorized).is_err());
+    assert_eq!(fridge.quantity(), Quantity::pieces(6.0));
+
+    // 位置和分类都相同时照常合并
+    let mut dairy = eggs(6.0, 10).with_category(Some("eggs".to_string()));
+    assert!(dairy.absorb(eggs(2.0, 5).with_category(Some("eggs".to_string()))).is_ok());
+}
+
+#[test]
+fn units_are_converted_when_merging() {
+    let milk = |amount: f64, unit: Unit| Item::new_with_amount("🥛牛奶".to_string(), today(), Quantity::new(amount, unit), today());
+    let mut existing = milk(1.0, Unit::Liter);
+    assert!(existing.absorb(milk(500.0, Unit::Milliliter)).is_ok());
+    assert_eq!(existing.quantity(), Quantity::new(1.5, Unit::Liter));
+    assert!(existing.absorb(milk(2.0, Unit::Piece)).is_err());
+}
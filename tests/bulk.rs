@@ -0,0 +1,99 @@
+//! 批量操作的集成测试：执行、记录历史，以及整体撤销后补记的抵消记录。
+
+use chrono::{Duration, NaiveDate};
+use ukeep::models::{monthly_reports, BulkAction, BulkOperation, Currency, EventKind, HistoryEvent, Item, Location, Price, Quantity};
+use ukeep::sync::SharedInventory;
+use uuid::Uuid;
+
+fn expiry() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, 3, 1).unwrap()
+}
+
+fn pantry() -> Vec<Item> {
+    vec![
+        Item::new_with_amount("🥛牛奶".to_string(), expiry(), Quantity::pieces(2.0), expiry())
+            .with_total_price(Some(Price::new(10.0, Currency::Cny))),
+        Item::new("🍞面包".to_string(), expiry(), expiry()),
+        Item::new("🧀奶酪".to_string(), expiry(), expiry()),
+    ]
+}
+
+fn ids(items: &[Item]) -> Vec<Uuid> {
+    items.iter().take(2).map(|i| i.id()).collect()
+}
+
+// 模拟另一台设备：仍保留批量操作前的库存，只同步到了历史记录
+fn merged_with_stale_copy(items: &[Item], history: &[HistoryEvent], stale: &[Item]) -> Vec<Item> {
+    let mine = SharedInventory { items: items.to_vec(), history: history.to_vec() };
+    let theirs = SharedInventory { items: stale.to_vec(), history: Vec::new() };
+    SharedInventory::merge(&mine, &theirs).items
+}
+
+#[test]
+fn consume_removes_items_and_records_their_value() {
+    let mut items = pantry();
+    let selected = ids(&items);
+    let operation = BulkOperation::apply(BulkAction::Consume, &selected, &mut items, None);
+    assert_eq!(items.len(), 1);
+    assert_eq!(operation.count(), 2);
+    assert_eq!(operation.describe(), "已批量吃掉 2 项");
+    assert!(operation.events().iter().all(|e| e.kind() == EventKind::Consumed && e.removed()));
+    assert_eq!(operation.events()[0].value(), Some(Price::new(10.0, Currency::Cny)));
+}
+
+#[test]
+fn delete_records_a_removal_that_survives_sync() {
+    let stale = pantry();
+    let mut items = stale.clone();
+    let selected = ids(&items);
+    let operation = BulkOperation::apply(BulkAction::Delete, &selected, &mut items, None);
+    assert_eq!(operation.events().len(), 2);
+    assert!(operation.events().iter().all(|e| e.kind() == EventKind::Deleted && e.removed() && e.value().is_none()));
+
+    // 另一台设备上的旧副本不会把删除的物品带回来
+    let merged = merged_with_stale_copy(&items, operation.events(), &stale);
+    assert_eq!(merged.len(), 1);
+    assert!(monthly_reports(operation.events()).is_empty());
+}
+
+#[test]
+fn undo_restores_items_and_appends_compensating_events() {
+    let stale = pantry();
+    let mut items = stale.clone();
+    let selected = ids(&items);
+    let operation = BulkOperation::apply(BulkAction::Consume, &selected, &mut items, None);
+    let mut history: Vec<HistoryEvent> = operation.events().to_vec();
+
+    operation.undo(&mut items, &mut history, None);
+    assert_eq!(items.len(), 3);
+    // 原来的记录保留，另外补记两条“放回了”
+    assert_eq!(history.len(), 4);
+    for (original, restored) in history[..2].iter().zip(&history[2..]) {
+        assert_eq!(restored.kind(), EventKind::Restored);
+        assert_eq!(restored.reverts(), Some(original.id()));
+        assert_eq!(restored.item_id(), original.item_id());
+        assert!(!restored.removed());
+    }
+    // 撤销掉的吃掉不计入报告，同步后物品也还在
+    assert!(monthly_reports(&history).is_empty());
+    assert_eq!(merged_with_stale_copy(&items, &history, &stale).len(), 3);
+}
+
+#[test]
+fn undo_puts_moved_and_shifted_items_back() {
+    let mut items = pantry();
+    let selected = ids(&items);
+    let original = items.clone();
+
+    let moved = BulkOperation::apply(BulkAction::Move(Location::Freezer), &selected, &mut items, None);
+    assert!(moved.events().is_empty());
+    assert_eq!(items[0].location(), Location::Freezer);
+    let shifted = BulkOperation::apply(BulkAction::ShiftExpiry(3), &selected, &mut items, None);
+    assert_eq!(items[1].expiry_date(), expiry() + Duration::days(3));
+
+    let mut history = Vec::new();
+    shifted.undo(&mut items, &mut history, None);
+    moved.undo(&mut items, &mut history, None);
+    assert_eq!(items, original);
+    assert!(history.is_empty());
+}
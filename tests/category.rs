@@ -0,0 +1,30 @@
+//! 分类识别的集成测试：按名称里命中的关键词猜测分类。
+
+use ukeep::models::{guess_category, Category};
+
+fn custom(name: &str, keywords: &[&str]) -> Category {
+    let mut category = Category::custom(name.to_string(), "🍶".to_string());
+    category.keywords = keywords.iter().map(|k| k.to_string()).collect();
+    category
+}
+
+#[test]
+fn longest_keyword_wins() {
+    let all = Category::all(&[]);
+    assert_eq!(guess_category(&all, "原味酸奶").map(|c| c.id()), Some("dairy"));
+    assert_eq!(guess_category(&all, "Chicken Wings").map(|c| c.id()), Some("meat"));
+    assert_eq!(guess_category(&all, "蛋糕").map(|c| c.id()), Some("bakery"));
+    assert!(guess_category(&all, "纸巾").is_none());
+}
+
+#[test]
+fn custom_category_wins_a_tie() {
+    // "奶" 和内置乳制品的关键词一样长
+    let mine = custom("宝宝辅食", &["奶"]);
+    let all = Category::all(std::slice::from_ref(&mine));
+    assert_eq!(guess_category(&all, "奶粉").map(|c| c.id()), Some(mine.id()));
+
+    // 更长的内置关键词仍然优先
+    assert_eq!(guess_category(&all, "酸奶").map(|c| c.id()), Some("dairy"));
+}
+
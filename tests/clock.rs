@@ -0,0 +1,71 @@
+//! 依赖“今天”的逻辑使用注入的时间：固定时间下结果不随运行日期变化。
+
+use chrono::{Duration, NaiveDate};
+use ukeep::clock::{Clock, FixedClock, SimulatedClock};
+use ukeep::models::{InventoryQuery, Item, Quantity, StatusFilter};
+use ukeep::utils::generate_mock_data;
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+#[test]
+fn deadline_follows_the_injected_day() {
+    let clock = FixedClock::on(date(2026, 3, 1));
+    let item = Item::new("🥛牛奶".to_string(), date(2026, 3, 3), clock.today());
+    assert_eq!(item.days_remaining(clock.today()), 2);
+    assert_eq!(item.display_deadline(clock.today()), "还剩 2 天");
+
+    // 模拟到三天后，牛奶已经过期
+    let later = SimulatedClock::new(clock.today() + Duration::days(3));
+    assert_eq!(later.simulated_date(), Some(date(2026, 3, 4)));
+    assert_eq!(later.today(), date(2026, 3, 4));
+    assert_eq!(item.display_deadline(later.today()), "已过期 1 天");
+    assert_eq!(clock.simulated_date(), None);
+}
+
+#[test]
+fn status_filter_uses_the_given_day() {
+    let clock = FixedClock::on(date(2026, 3, 1));
+    let items = generate_mock_data(&clock);
+    let expired = InventoryQuery { status: Some(StatusFilter::Expired), ..Default::default() };
+    let names: Vec<String> = expired.apply(&items, &[], clock.today()).iter().map(|i| i.name().to_string()).collect();
+    assert_eq!(names, ["全脂牛奶"]);
+
+    // 模拟数据里最晚的生抽 200 天后到期，再过一天全部过期
+    let later = clock.today() + Duration::days(201);
+    assert_eq!(expired.apply(&items, &[], later).len(), items.len());
+}
+
+#[test]
+fn new_items_and_batches_are_stocked_on_the_injected_day() {
+    let clock = FixedClock::on(date(2026, 3, 1));
+    let mut item = Item::new("🥛牛奶".to_string(), date(2026, 3, 8), clock.today());
+    assert_eq!(item.added_on(), date(2026, 3, 1));
+    assert!(item.add_batch(Quantity::pieces(1.0), date(2026, 3, 5), date(2026, 3, 2)));
+    let added: Vec<NaiveDate> = item.batches().iter().map(|b| b.added_on()).collect();
+    assert_eq!(added, [date(2026, 3, 2), date(2026, 3, 1)]);
+
+    // 合并时保留另一批原来的入库日期
+    let later = Item::new("🥛牛奶".to_string(), date(2026, 3, 20), date(2026, 3, 10));
+    item.absorb(later).unwrap();
+    assert_eq!(item.batches().last().map(|b| b.added_on()), Some(date(2026, 3, 10)));
+
+    let mock = generate_mock_data(&clock);
+    assert!(mock.iter().all(|i| i.added_on() == clock.today()));
+    assert!(mock.iter().flat_map(|i| i.batches()).all(|b| b.added_on() == clock.today()));
+}
+
+#[test]
+fn batches_saved_without_a_date_take_the_item_date() {
+    let item: Item = serde_json::from_value(serde_json::json!({
+        "id": "6f1b3a4e-8f52-4f7a-9a53-2d7b8c1e0f11",
+        "name": "牛奶",
+        "emoji": "🥛",
+        "unit": "Piece",
+        "batches": [{"amount": 1.0, "expiry_date": "2026-03-08"}],
+        "added_on": "2026-02-20"
+    }))
+    .unwrap();
+    assert_eq!(item.batches()[0].added_on(), date(2026, 2, 20));
+}
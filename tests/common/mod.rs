@@ -0,0 +1,26 @@
+//! 集成测试共用的固定日期和物品构造函数，各测试文件通过 `mod common;` 引入。
+
+#![allow(dead_code)]
+
+use chrono::{Duration, NaiveDate};
+use ukeep::models::{Item, Quantity};
+
+/// 测试中的“今天”，固定下来让结果不随运行日期变化
+pub fn today() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, 3, 1).unwrap()
+}
+
+/// 相对 `today` 的某一天
+pub fn day(offset: i64) -> NaiveDate {
+    today() + Duration::days(offset)
+}
+
+/// 今天入库、今天过期的一个物品
+pub fn item(name: &str) -> Item {
+    Item::new(name.to_string(), today(), today())
+}
+
+/// 今天入库、`days` 天后过期的物品
+pub fn expiring(name: &str, days: i64, quantity: Quantity) -> Item {
+    Item::new_with_amount(name.to_string(), day(days), quantity, today())
+}
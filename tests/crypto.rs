@@ -0,0 +1,49 @@
+//! 加密备份：口令错误和数据被篡改要报告为不同的错误。
+
+use ukeep::crypto::{self, CryptoError, DerivedKey};
+
+const PLAINTEXT: &[u8] = b"[{\"name\": \"milk\"}]";
+
+#[test]
+fn round_trip_with_the_right_passphrase() {
+    let sealed = crypto::encrypt(PLAINTEXT, "correct horse").unwrap();
+    assert!(crypto::is_encrypted(&sealed));
+    assert_eq!(crypto::decrypt(&sealed, "correct horse").unwrap(), PLAINTEXT);
+}
+
+#[test]
+fn wrong_passphrase_is_not_reported_as_tampering() {
+    let sealed = crypto::encrypt(PLAINTEXT, "correct horse").unwrap();
+    assert_eq!(crypto::decrypt(&sealed, "battery staple"), Err(CryptoError::WrongPassphrase));
+}
+
+#[test]
+fn modified_ciphertext_is_reported_as_tampering() {
+    let mut sealed = crypto::encrypt(PLAINTEXT, "correct horse").unwrap();
+    let last = sealed.len() - 1;
+    sealed[last] ^= 0x01;
+    assert_eq!(crypto::decrypt(&sealed, "correct horse"), Err(CryptoError::Tampered));
+}
+
+#[test]
+fn cached_key_distinguishes_wrong_key_from_tampering() {
+    let salt = [7u8; 16];
+    let key = DerivedKey::derive("correct horse", salt).unwrap();
+    let mut sealed = key.encrypt(PLAINTEXT).unwrap();
+    assert_eq!(key.decrypt(&sealed).unwrap(), PLAINTEXT);
+
+    let other = DerivedKey::derive("battery staple", salt).unwrap();
+    assert_eq!(other.decrypt(&sealed), Err(CryptoError::WrongPassphrase));
+
+    // 改动 nonce 之后的密文
+    let middle = sealed.len() - 8;
+    sealed[middle] ^= 0x80;
+    assert_eq!(key.decrypt(&sealed), Err(CryptoError::Tampered));
+}
+
+#[test]
+fn broken_files_are_rejected_before_decrypting() {
+    assert_eq!(crypto::decrypt(b"[]", "correct horse"), Err(CryptoError::NotEncrypted));
+    let sealed = crypto::encrypt(PLAINTEXT, "correct horse").unwrap();
+    assert_eq!(crypto::decrypt(&sealed[..40], "correct horse"), Err(CryptoError::Truncated));
+}
@@ -0,0 +1,91 @@
+//! 食物保质期知识库的集成测试：校验打包数据的完整性，以及按名称查询和推算过期日期。
+
+use chrono::NaiveDate;
+use std::collections::HashSet;
+use ukeep::models::{Category, FoodDatabase, Location};
+
+fn date(s: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+}
+
+#[test]
+fn bundled_data_is_versioned_and_well_formed() {
+    let db = FoodDatabase::bundled();
+    assert!(db.version() >= 1);
+    assert!(db.foods().len() >= 40);
+
+    let categories: HashSet<String> = Category::builtin().iter().map(|c| c.id().to_string()).collect();
+    let mut ids = HashSet::new();
+    let mut terms = HashSet::new();
+    for food in db.foods() {
+        assert!(ids.insert(food.id()), "重复的标识: {}", food.id());
+        assert!(!food.name_zh().is_empty() && !food.name_en().is_empty(), "{} 缺少名称", food.id());
+        assert!(categories.contains(food.category()), "{} 的分类不存在", food.id());
+        for term in [food.name_zh(), food.name_en()].into_iter().chain(food.aliases().iter().map(String::as_str)) {
+            assert!(terms.insert(term.to_lowercase()), "名称或别名重复: {}", term);
+        }
+        // 每种食物至少有一种推荐的存放方式，开封后不会比未开封保存得更久
+        assert!(Location::ALL.into_iter().any(|l| food.days(l, false).is_some()), "{} 没有保质期", food.id());
+        for location in Location::ALL {
+            if let (Some(opened), Some(unopened)) = (food.days(location, true), food.days(location, false)) {
+                assert!(opened <= unopened, "{} 开封后保质期更长", food.id());
+            }
+        }
+    }
+}
+
+#[test]
+fn lookup_by_chinese_english_and_alias() {
+    let db = FoodDatabase::bundled();
+    let id = |name: &str| db.lookup(name).map(|f| f.id().to_string());
+
+    assert_eq!(id("牛奶").as_deref(), Some("milk"));
+    assert_eq!(id("Milk").as_deref(), Some("milk"));
+    assert_eq!(id("西红柿").as_deref(), Some("tomato"));
+    assert_eq!(id("🥛 蒙牛纯牛奶").as_deref(), Some("milk"));
+    assert_eq!(id("  whole milk 2L ").as_deref(), Some("milk"));
+}
+
+#[test]
+fn lookup_prefers_the_longest_match() {
+    let db = FoodDatabase::bundled();
+    assert_eq!(db.lookup("番茄酱").unwrap().id(), "ketchup");
+    assert_eq!(db.lookup("鲜橙汁").unwrap().id(), "juice");
+    assert_eq!(db.lookup("全麦面包").unwrap().id(), "bread");
+}
+
+#[test]
+fn english_terms_match_whole_words_only() {
+    let db = FoodDatabase::bundled();
+    assert_eq!(db.lookup("free range eggs").unwrap().id(), "eggs");
+    assert!(db.lookup("eggplant").is_none());
+    assert!(db.lookup("").is_none());
+    assert!(db.lookup("不存在的东西").is_none());
+}
+
+#[test]
+fn suggests_expiry_per_location_and_opened_state() {
+    let db = FoodDatabase::bundled();
+    let milk = db.get("milk").unwrap();
+    let produced = date("2026-10-01");
+
+    assert_eq!(milk.expiry_from(produced, Location::Fridge, false), Some(date("2026-10-08")));
+    assert_eq!(milk.expiry_from(produced, Location::Fridge, true), Some(date("2026-10-04")));
+    assert_eq!(milk.expiry_from(produced, Location::Freezer, false), Some(date("2026-12-30")));
+    assert_eq!(milk.expiry_from(produced, Location::Pantry, false), None);
+
+    // 没有单独的开封数据时沿用未开封的保质期
+    let chicken = db.get("chicken").unwrap();
+    assert_eq!(chicken.days(Location::Fridge, true), chicken.days(Location::Fridge, false));
+}
+
+#[test]
+fn from_json_reports_invalid_data() {
+    assert!(FoodDatabase::from_json("{\"version\": 1}").is_err());
+    let db = FoodDatabase::from_json(
+        r#"{"version": 2, "foods": [{"id": "x", "zh": "测试", "en": "Test", "emoji": "📦", "category": "other", "unopened": {"pantry": 3}}]}"#,
+    )
+    .unwrap();
+    assert_eq!(db.version(), 2);
+    assert_eq!(db.lookup("测试").unwrap().days(Location::Pantry, true), Some(3));
+}
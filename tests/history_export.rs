@@ -0,0 +1,51 @@
+//! 导出历史记录为 CSV：表头、时间顺序，以及防止单元格被表格软件当作公式执行。
+
+mod common;
+
+use common::item;
+use ukeep::models::{EventKind, HistoryEvent, Member, Quantity, WasteReason};
+use ukeep::storage::history_to_csv;
+use ukeep::utils::parse_csv;
+
+#[test]
+fn exports_a_header_and_one_row_per_event() {
+    let milk = item("🥛牛奶");
+    let events = vec![
+        HistoryEvent::new(&milk, EventKind::Added, Quantity::pieces(2.0), false, None),
+        HistoryEvent::new(&milk, EventKind::Wasted, Quantity::pieces(1.0), false, None)
+            .with_reason(Some(WasteReason::Expired), Some("忘了".to_string())),
+    ];
+    let csv = history_to_csv(&events);
+    assert!(csv.starts_with('\u{FEFF}'));
+
+    let rows = parse_csv(&csv);
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[0], ["时间", "成员", "操作", "物品", "数量", "价值", "原因", "备注"]);
+    assert_eq!(rows[1][2], "添加了");
+    assert_eq!(rows[1][3], "🥛牛奶");
+    assert_eq!(rows[2][2], "扔掉了");
+    assert_eq!(rows[2][6], WasteReason::Expired.label());
+    assert_eq!(rows[2][7], "忘了");
+}
+
+#[test]
+fn cells_that_look_like_formulas_are_escaped() {
+    let milk = item("🥛牛奶");
+    let member = Member::new("=HYPERLINK(\"http://example.com\",\"点我\")".to_string());
+    let events = vec![
+        HistoryEvent::new(&milk, EventKind::Consumed, Quantity::pieces(1.0), false, Some(member))
+            .with_reason(None, Some("+1 明天再买".to_string())),
+        HistoryEvent::new(&milk, EventKind::Consumed, Quantity::pieces(1.0), false, None)
+            .with_reason(None, Some("@SUM(A1)".to_string())),
+        HistoryEvent::new(&milk, EventKind::Consumed, Quantity::pieces(1.0), false, None)
+            .with_reason(None, Some("-2+3".to_string())),
+    ];
+    let rows = parse_csv(&history_to_csv(&events));
+    assert_eq!(rows[1][1], "'=HYPERLINK(\"http://example.com\",\"点我\")");
+    assert_eq!(rows[1][7], "'+1 明天再买");
+    assert_eq!(rows[2][7], "'@SUM(A1)");
+    assert_eq!(rows[3][7], "'-2+3");
+    // 普通内容不变
+    assert_eq!(rows[1][3], "🥛牛奶");
+    assert_eq!(rows[2][1], "");
+}
@@ -0,0 +1,184 @@
+//! 家庭共享：库存合并和邀请码输入。
+
+mod common;
+
+use common::item;
+use futures::executor::block_on;
+use serde_json::{json, Value};
+use std::cell::RefCell;
+use ukeep::crypto::DerivedKey;
+use ukeep::models::{normalize_invite_code, EventKind, HistoryEvent, Item, Location, Quantity};
+use ukeep::sync::{HouseholdClient, HttpRequest, HttpResponse, HttpTransport, SharedInventory};
+use uuid::Uuid;
+
+fn added(item: &Item) -> HistoryEvent {
+    HistoryEvent::new(item, EventKind::Added, Quantity::pieces(1.0), false, None)
+}
+
+fn consumed(item: &Item) -> HistoryEvent {
+    HistoryEvent::new(item, EventKind::Consumed, Quantity::pieces(1.0), true, None)
+}
+
+#[test]
+fn merge_keeps_both_sides_without_duplicates() {
+    let milk = item("🥛牛奶");
+    let bread = item("🍞面包");
+    let shared_event = added(&milk);
+    let mine = SharedInventory {
+        items: vec![milk.clone()],
+        history: vec![shared_event.clone()],
+    };
+    let theirs = SharedInventory {
+        items: vec![milk.clone(), bread.clone()],
+        history: vec![shared_event.clone(), added(&bread)],
+    };
+
+    let merged = SharedInventory::merge(&mine, &theirs);
+    assert_eq!(merged.items, vec![milk, bread]);
+    assert_eq!(merged.history.len(), 2);
+    assert_eq!(merged.history.iter().filter(|e| e.id() == shared_event.id()).count(), 1);
+}
+
+#[test]
+fn merge_prefers_the_preferred_copy_of_an_item() {
+    let milk = item("🥛牛奶");
+    let mut moved = milk.clone();
+    moved.set_location(Location::Freezer);
+    let mine = SharedInventory { items: vec![moved.clone()], history: Vec::new() };
+    let theirs = SharedInventory { items: vec![milk], history: Vec::new() };
+
+    assert_eq!(SharedInventory::merge(&mine, &theirs).items, vec![moved]);
+}
+
+#[test]
+fn removed_items_are_not_brought_back() {
+    let milk = item("🥛牛奶");
+    let bread = item("🍞面包");
+    // 另一位成员吃完了牛奶，本地还保留着旧的牛奶
+    let mine = SharedInventory {
+        items: vec![milk.clone(), bread.clone()],
+        history: vec![added(&milk), added(&bread)],
+    };
+    let theirs = SharedInventory {
+        items: vec![bread.clone()],
+        history: vec![consumed(&milk)],
+    };
+
+    let merged = SharedInventory::merge(&mine, &theirs);
+    assert_eq!(merged.items, vec![bread.clone()]);
+    assert_eq!(SharedInventory::merge(&theirs, &mine).items, vec![bread]);
+}
+
+#[test]
+fn invite_codes_are_normalized() {
+    assert_eq!(normalize_invite_code(" ab3-d5f "), Some("AB3D5F".to_string()));
+    assert_eq!(normalize_invite_code("AB3 D5F"), Some("AB3D5F".to_string()));
+    // 长度不对
+    assert_eq!(normalize_invite_code("AB3D5"), None);
+    assert_eq!(normalize_invite_code("AB3D5FG"), None);
+    // 含有容易混淆、不会出现在邀请码里的字符
+    assert_eq!(normalize_invite_code("AB0D5F"), None);
+    assert_eq!(normalize_invite_code("ABOD5F"), None);
+    assert_eq!(normalize_invite_code("AB1D5F"), None);
+}
+
+// 内存中的同步服务器替身，只实现拉取和推送库存
+struct StandInServer {
+    household_id: Uuid,
+    revision: RefCell<u64>,
+    inventory: RefCell<Value>,
+}
+
+impl StandInServer {
+    fn new(inventory: &SharedInventory) -> Self {
+        Self {
+            household_id: Uuid::new_v4(),
+            revision: RefCell::new(1),
+            inventory: RefCell::new(serde_json::to_value(inventory).unwrap()),
+        }
+    }
+
+    fn is_encrypted(&self) -> bool {
+        self.inventory.borrow().get("ciphertext").is_some()
+    }
+}
+
+impl HttpTransport for &StandInServer {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse, String> {
+        let revision = *self.revision.borrow();
+        let (status, body) = match request.method.as_str() {
+            "GET" => {
+                let household = json!({
+                    "id": self.household_id,
+                    "name": "我们家",
+                    "invite_code": "AB3D5F",
+                    "members": [],
+                });
+                (200, json!({ "household": household, "revision": revision, "inventory": *self.inventory.borrow() }))
+            }
+            "PUT" => {
+                let body: Value = serde_json::from_slice(&request.body.unwrap()).unwrap();
+                if body["base_revision"] != revision {
+                    (409, Value::Null)
+                } else {
+                    *self.inventory.borrow_mut() = body["inventory"].clone();
+                    *self.revision.borrow_mut() += 1;
+                    (200, json!({ "revision": revision + 1 }))
+                }
+            }
+            _ => (404, Value::Null),
+        };
+        Ok(HttpResponse {
+            status,
+            body: serde_json::to_vec(&body).unwrap(),
+        })
+    }
+}
+
+fn household_key(server: &StandInServer, passphrase: &str) -> DerivedKey {
+    DerivedKey::derive(passphrase, *server.household_id.as_bytes()).unwrap()
+}
+
+#[test]
+fn changing_the_passphrase_re_encrypts_the_server_copy() {
+    let shared = SharedInventory {
+        items: vec![item("🥛牛奶")],
+        history: Vec::new(),
+    };
+    let server = StandInServer::new(&SharedInventory::default());
+    let member = Uuid::new_v4();
+    let old_key = household_key(&server, "old passphrase");
+    let new_key = household_key(&server, "new passphrase");
+
+    let old_client = HouseholdClient::new("http://sync.test", &server).with_encryption(Some(old_key.clone()));
+    block_on(old_client.push(server.household_id, member, 1, &shared)).unwrap();
+    assert!(server.is_encrypted());
+
+    block_on(old_client.rekey(server.household_id, member, &new_key)).unwrap();
+
+    // 换口令之后，只有新密钥能读取服务器上的数据
+    let new_client = HouseholdClient::new("http://sync.test", &server).with_encryption(Some(new_key.clone()));
+    assert_eq!(block_on(new_client.fetch(server.household_id)).unwrap().inventory, shared);
+    assert!(block_on(old_client.fetch(server.household_id)).is_err());
+
+    // 其他成员输入新口令时，服务器上的数据已经能用新密钥读取，不再推送
+    let revision = *server.revision.borrow();
+    block_on(old_client.rekey(server.household_id, member, &new_key)).unwrap();
+    assert_eq!(*server.revision.borrow(), revision);
+}
+
+#[test]
+fn a_mistyped_passphrase_cannot_replace_the_household_key() {
+    let server = StandInServer::new(&SharedInventory::default());
+    let member = Uuid::new_v4();
+    let key = household_key(&server, "family passphrase");
+    let client = HouseholdClient::new("http://sync.test", &server).with_encryption(Some(key));
+    block_on(client.push(server.household_id, member, 1, &SharedInventory::default())).unwrap();
+
+    // 还没有密钥的成员输错了口令：既不能用它读取，也不会覆盖服务器上的数据
+    let stranger = HouseholdClient::new("http://sync.test", &server);
+    let typo = household_key(&server, "family passphrse");
+    let revision = *server.revision.borrow();
+    assert!(block_on(stranger.rekey(server.household_id, member, &typo)).is_err());
+    assert_eq!(*server.revision.borrow(), revision);
+}
@@ -0,0 +1,86 @@
+//! 导入时的错误要说明是哪个文件、哪一行、哪个字段出了问题。
+
+mod common;
+
+use common::{item, today};
+use ukeep::crypto;
+use ukeep::error::{FileProblem, RowError, RowField, UkeepError};
+use ukeep::storage::{import_data_from_bytes, import_data_from_json, import_items_from_csv, serialize_backup, BACKUP_VERSION};
+
+#[test]
+fn csv_reports_every_invalid_row_and_field() {
+    let csv = "name,expiry,quantity,location\n\
+               🥛牛奶,2026-03-01,2,fridge\n\
+               ,2026-13-01,1,fridge\n\
+               🍞面包,2026-03-02,-1,attic\n";
+    let error = import_items_from_csv(csv, &[], today()).unwrap_err();
+    assert_eq!(
+        error,
+        UkeepError::InvalidRows(vec![
+            RowError { line: 3, field: RowField::Name, value: None },
+            RowError { line: 3, field: RowField::Expiry, value: Some("2026-13-01".to_string()) },
+            RowError { line: 4, field: RowField::Quantity, value: Some("-1".to_string()) },
+            RowError { line: 4, field: RowField::Location, value: Some("attic".to_string()) },
+        ])
+    );
+}
+
+#[test]
+fn csv_without_required_column_is_rejected() {
+    assert_eq!(
+        import_items_from_csv("name,quantity\n🥛牛奶,1\n", &[], today()).unwrap_err(),
+        UkeepError::InvalidFile(FileProblem::MissingColumn("expiry"))
+    );
+    assert_eq!(import_items_from_csv("", &[], today()).unwrap_err(), UkeepError::InvalidFile(FileProblem::Empty));
+}
+
+#[test]
+fn backups_carry_their_format_version() {
+    let items = vec![item("🥛牛奶")];
+    let json = serialize_backup(&items).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["version"], BACKUP_VERSION);
+    assert_eq!(import_data_from_json(&json, today()).unwrap(), items);
+
+    // 早期不带版本字段的物品数组仍然可以导入
+    let legacy = serde_json::to_string(&items).unwrap();
+    assert_eq!(import_data_from_json(&legacy, today()).unwrap(), items);
+
+    assert!(matches!(
+        import_data_from_json(r#"{"items": []}"#, today()).unwrap_err(),
+        UkeepError::InvalidFile(FileProblem::Malformed(_))
+    ));
+}
+
+#[test]
+fn legacy_items_without_added_on_are_stocked_on_the_import_day() {
+    let legacy = r#"[{"id": "8d3f5a9e-0c1b-4a4e-9f25-6f1d2c3b4a5e", "name": "牛奶", "emoji": "🥛",
+        "expiry_date": "2026-03-05", "quantity": 2, "location": "Fridge"}]"#;
+    let items = import_data_from_json(legacy, today()).unwrap();
+    assert_eq!(items[0].added_on(), today());
+    assert!(items[0].batches().iter().all(|b| b.added_on() == today()));
+}
+
+#[test]
+fn json_from_another_version_is_a_version_mismatch() {
+    assert_eq!(
+        import_data_from_json(r#"{"version": 2, "items": []}"#, today()).unwrap_err(),
+        UkeepError::VersionMismatch { found: 2, supported: BACKUP_VERSION }
+    );
+    assert!(matches!(
+        import_data_from_json("not json", today()).unwrap_err(),
+        UkeepError::InvalidFile(FileProblem::Malformed(_))
+    ));
+    assert_eq!(import_data_from_json("  \n", today()).unwrap_err(), UkeepError::InvalidFile(FileProblem::Empty));
+}
+
+#[test]
+fn encrypted_backup_needs_the_right_passphrase() {
+    let bytes = crypto::encrypt(b"[]", "family passphrase").unwrap();
+    assert_eq!(import_data_from_bytes(&bytes, None, today()).unwrap_err(), UkeepError::PassphraseRequired);
+    assert_eq!(
+        import_data_from_bytes(&bytes, Some("wrong"), today()).unwrap_err(),
+        UkeepError::Crypto(crypto::CryptoError::WrongPassphrase)
+    );
+    assert_eq!(import_data_from_bytes(&bytes, Some("family passphrase"), today()).unwrap(), Vec::new());
+}
@@ -0,0 +1,67 @@
+//! 首页的搜索条件：拼音匹配，以及保存在 URL 中再读回来。
+
+mod common;
+
+use common::{item, today};
+use ukeep::models::{matches_text, InventoryQuery, Location, SortOrder, StatusFilter};
+use ukeep::utils::percent_decode;
+
+// 路由先整体解码一次查询字符串，再交给 `From<&str>`
+fn through_url(query: &InventoryQuery) -> InventoryQuery {
+    InventoryQuery::from(percent_decode(&query.to_string()).as_str())
+}
+
+#[test]
+fn pinyin_initials_and_full_pinyin_match_chinese_names() {
+    assert!(matches_text("🥛牛奶", "nn"));
+    assert!(matches_text("🥛牛奶", "niunai"));
+    assert!(matches_text("🥛牛奶", "NiuNai"));
+    assert!(matches_text("🥛牛奶", "牛"));
+    assert!(matches_text("🥛牛奶", ""));
+    assert!(!matches_text("🥛牛奶", "mb"));
+    assert!(matches_text("全麦面包", "qmmb"));
+    assert!(matches_text("Greek Yogurt", "yogurt"));
+}
+
+#[test]
+fn search_text_filters_the_inventory() {
+    let items = vec![
+        item("🥛牛奶"),
+        item("🍞面包"),
+    ];
+    let query = InventoryQuery { text: "nn".to_string(), ..Default::default() };
+    let names: Vec<String> = query.apply(&items, &[], today()).iter().map(|i| i.name().to_string()).collect();
+    assert_eq!(names, vec!["牛奶".to_string()]);
+}
+
+#[test]
+fn query_round_trips_through_the_url() {
+    let query = InventoryQuery {
+        text: "nn".to_string(),
+        status: Some(StatusFilter::Urgent),
+        location: Some(Location::Fridge),
+        category: Some("dairy".to_string()),
+        expires: Some(today()),
+        sort: SortOrder::Name,
+    };
+    assert_eq!(query.to_string(), "q=nn&status=urgent&location=fridge&category=dairy&expires=2026-03-01&sort=name");
+    assert_eq!(through_url(&query), query);
+    assert_eq!(through_url(&InventoryQuery::default()), InventoryQuery::default());
+    assert_eq!(InventoryQuery::default().to_string(), "");
+}
+
+#[test]
+fn special_characters_in_values_survive_the_url() {
+    let query = InventoryQuery {
+        text: "50% off & more = #1+2".to_string(),
+        category: Some("a&b".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(through_url(&query), query);
+}
+
+#[test]
+fn unknown_or_invalid_parameters_are_ignored() {
+    let query = InventoryQuery::from("status=soon&sort=weird&location=attic&expires=tomorrow&foo=bar&q=nn");
+    assert_eq!(query, InventoryQuery { text: "nn".to_string(), ..Default::default() });
+}
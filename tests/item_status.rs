@@ -0,0 +1,25 @@
+//! 新鲜程度按分类阈值判断，配色由外观设置决定。
+
+use chrono::{Duration, NaiveDate};
+use ukeep::models::{Item, ItemStatus, Theme, Thresholds};
+
+#[test]
+fn status_follows_category_thresholds() {
+    let today = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+    let thresholds = Thresholds { warning_days: 5, critical_days: 2 };
+    let status_in = |days: i64| Item::new("🥛牛奶".to_string(), today + Duration::days(days), today).status(&thresholds, today);
+    assert_eq!(status_in(-1), ItemStatus::Expired);
+    assert_eq!(status_in(0), ItemStatus::Critical);
+    assert_eq!(status_in(2), ItemStatus::Critical);
+    assert_eq!(status_in(3), ItemStatus::Warning);
+    assert_eq!(status_in(5), ItemStatus::Warning);
+    assert_eq!(status_in(6), ItemStatus::Safe);
+}
+
+#[test]
+fn system_theme_follows_the_os() {
+    assert!(Theme::System.is_dark(true));
+    assert!(!Theme::System.is_dark(false));
+    assert!(Theme::Dark.is_dark(false));
+    assert!(!Theme::Light.is_dark(true));
+}
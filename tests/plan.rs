@@ -0,0 +1,54 @@
+//! 饭菜计划：按先到期先用的批次检查过期，以及用掉预留食材时的提醒。
+
+mod common;
+
+use chrono::NaiveDate;
+use common::{day, expiring, today};
+use ukeep::models::{reservation_shortfall, Item, Meal, MealSlot, Quantity, Reservation};
+
+// 两批鸡蛋：3 个后天过期，6 个十天后过期
+fn eggs() -> Item {
+    let mut eggs = expiring("🥚鸡蛋", 2, Quantity::pieces(3.0));
+    eggs.add_batch(Quantity::pieces(6.0), day(10), today());
+    eggs
+}
+
+fn meal(date: NaiveDate, slot: MealSlot, item: &Item, amount: f64) -> Meal {
+    Meal::new(date, slot, "番茄炒蛋".to_string(), vec![Reservation { item_id: item.id(), amount }])
+}
+
+#[test]
+fn a_meal_is_flagged_by_the_batches_it_would_use() {
+    let eggs = eggs();
+    let items = vec![eggs.clone()];
+
+    // 前面的早餐先用掉后天过期的 3 个，这一餐用的是十天后过期的那批
+    let breakfast = meal(day(1), MealSlot::Breakfast, &eggs, 3.0);
+    let dinner = meal(day(5), MealSlot::Dinner, &eggs, 2.0);
+    let meals = vec![dinner.clone(), breakfast.clone()];
+    assert!(breakfast.expiring_before(&items, &meals).is_empty());
+    assert!(dinner.expiring_before(&items, &meals).is_empty());
+
+    // 没有早餐时，这一餐会用到后天过期的那批
+    assert_eq!(dinner.expiring_before(&items, std::slice::from_ref(&dinner)).len(), 1);
+
+    // 前面只占用 2 个时，剩下的 1 个旧鸡蛋还是会落到这一餐
+    let small = meal(day(1), MealSlot::Lunch, &eggs, 2.0);
+    assert_eq!(dinner.expiring_before(&items, &[small, dinner.clone()]).len(), 1);
+
+    // 用餐时两批都过期了
+    let late = meal(day(12), MealSlot::Dinner, &eggs, 5.0);
+    assert_eq!(late.expiring_before(&items, &[breakfast, late.clone()]).len(), 1);
+}
+
+#[test]
+fn using_reserved_stock_reports_the_shortfall() {
+    let eggs = eggs();
+    let meals = vec![meal(day(3), MealSlot::Dinner, &eggs, 4.0)];
+    // 9 个里预留了 4 个，吃掉 5 个正好不动预留
+    assert_eq!(reservation_shortfall(&meals, &eggs, 5.0), 0.0);
+    assert_eq!(reservation_shortfall(&meals, &eggs, 7.0), 2.0);
+    // 全部吃掉时挤占的不超过预留数量
+    assert_eq!(reservation_shortfall(&meals, &eggs, 9.0), 4.0);
+    assert_eq!(reservation_shortfall(&[], &eggs, 9.0), 0.0);
+}
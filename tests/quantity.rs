@@ -0,0 +1,92 @@
+//! 带单位的数量：换算、扣减、旧数据迁移和右滑的数量选项。
+
+mod common;
+
+use common::today;
+use ukeep::gesture::QuantitySteps;
+use ukeep::models::{HistoryEvent, Item, Quantity, Unit};
+
+#[test]
+fn converts_only_within_the_same_dimension() {
+    assert_eq!(Quantity::new(1.5, Unit::Liter).to(Unit::Milliliter), Some(Quantity::new(1500.0, Unit::Milliliter)));
+    assert_eq!(Quantity::new(250.0, Unit::Gram).to(Unit::Kilogram), Some(Quantity::new(0.25, Unit::Kilogram)));
+    assert_eq!(Quantity::new(1.0, Unit::Kilogram).to(Unit::Liter), None);
+    assert_eq!(Quantity::pieces(2.0).to(Unit::Pack), None);
+}
+
+#[test]
+fn subtraction_converts_units_and_stops_at_zero() {
+    let milk = Quantity::new(1.0, Unit::Liter);
+    assert_eq!(milk.checked_sub(Quantity::new(250.0, Unit::Milliliter)), Some(Quantity::new(0.75, Unit::Liter)));
+    assert!(milk.checked_sub(Quantity::new(2.0, Unit::Liter)).unwrap().is_empty());
+    // 0.1 + 0.2 之类的误差不会留下“还剩 0.0000001”
+    let rest = Quantity::new(0.3, Unit::Liter).checked_sub(Quantity::new(0.1, Unit::Liter)).unwrap();
+    assert!(rest.checked_sub(Quantity::new(0.2, Unit::Liter)).unwrap().is_empty());
+    assert_eq!(milk.checked_sub(Quantity::pieces(1.0)), None);
+}
+
+#[test]
+fn invalid_amounts_become_zero() {
+    assert_eq!(Quantity::new(-1.0, Unit::Gram).amount(), 0.0);
+    assert_eq!(Quantity::new(f64::NAN, Unit::Gram).amount(), 0.0);
+}
+
+#[test]
+fn display_uses_fractions_and_smaller_units() {
+    assert_eq!(Quantity::pieces(2.0).to_string(), "x2");
+    assert_eq!(Quantity::new(0.5, Unit::Pack).to_string(), "½包");
+    assert_eq!(Quantity::new(0.5, Unit::Liter).to_string(), "500 ml");
+    assert_eq!(Quantity::new(1.25, Unit::Kilogram).to_string(), "1.25 kg");
+}
+
+#[test]
+fn baseline_items_with_integer_quantity_migrate_to_pieces() {
+    // 旧版本保存的物品：只有一个过期日期和整数数量
+    let json = r#"{
+        "id": "4f1c2a7e-3b9d-4c55-8a0e-2d6f9b1c7e10",
+        "name": "牛奶",
+        "emoji": "🥛",
+        "expiry_date": "2026-03-01",
+        "quantity": 3
+    }"#;
+    let item: Item = serde_json::from_str(json).unwrap();
+    assert_eq!(item.quantity(), Quantity::pieces(3.0));
+    assert_eq!(item.expiry_date(), today());
+    assert_eq!(item.batches().len(), 1);
+
+    // 没有数量的旧数据按 1 个处理
+    let json = r#"{"id": "4f1c2a7e-3b9d-4c55-8a0e-2d6f9b1c7e11", "name": "面包", "emoji": "🍞", "expiry_date": "2026-03-02"}"#;
+    let item: Item = serde_json::from_str(json).unwrap();
+    assert_eq!(item.quantity(), Quantity::pieces(1.0));
+
+    // 迁移后再保存、读回，数量和单位不变
+    let again: Item = serde_json::from_str(&serde_json::to_string(&item).unwrap()).unwrap();
+    assert_eq!(again, item);
+}
+
+#[test]
+fn baseline_history_with_integer_count_migrates_to_pieces() {
+    let json = r#"{
+        "id": "4f1c2a7e-3b9d-4c55-8a0e-2d6f9b1c7e12",
+        "item_id": "4f1c2a7e-3b9d-4c55-8a0e-2d6f9b1c7e10",
+        "item_name": "牛奶",
+        "emoji": "🥛",
+        "kind": "Consumed",
+        "count": 2,
+        "at": "2026-03-01T08:00:00"
+    }"#;
+    let event: HistoryEvent = serde_json::from_str(json).unwrap();
+    assert_eq!(event.count(), Quantity::pieces(2.0));
+}
+
+#[test]
+fn consume_steps_follow_the_unit() {
+    let flour = QuantitySteps::new(Quantity::new(2.0, Unit::Pack));
+    assert_eq!(flour.steps(), &[0.25, 0.5, 1.0, 2.0]);
+
+    let butter = QuantitySteps::new(Quantity::new(200.0, Unit::Gram));
+    assert_eq!(butter.steps(), &[50.0, 100.0, 200.0]);
+
+    let half = QuantitySteps::new(Quantity::pieces(0.5));
+    assert_eq!(half.steps(), &[0.5]);
+}
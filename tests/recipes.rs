@@ -0,0 +1,85 @@
+//! 菜谱推荐的集成测试：按临期食材排序、一键做菜扣减库存，以及打包菜谱的完整性。
+
+mod common;
+
+use common::{expiring, today};
+use ukeep::models::{bundled_recipes, cook, parse_recipes, suggest_recipes, Category, Quantity, Unit};
+
+const BOOK: &str = r#"[
+    {"id": "tomato_egg", "name": "番茄炒蛋", "ingredients": [
+        {"name": "番茄", "quantity": 2},
+        {"name": "鸡蛋", "quantity": 3},
+        {"name": "葱", "optional": true}
+    ]},
+    {"id": "custard", "name": "牛奶炖蛋", "ingredients": [
+        {"name": "牛奶", "quantity": {"amount": 250, "unit": "Milliliter"}},
+        {"name": "鸡蛋", "quantity": 2}
+    ]},
+    {"id": "salad", "name": "沙拉", "ingredients": [{"name": "生菜"}]},
+    {"id": "double_egg", "name": "双蛋饭", "ingredients": [
+        {"name": "鸡蛋", "quantity": 1},
+        {"name": "蛋", "keywords": ["蛋"], "quantity": 1}
+    ]}
+]"#;
+
+#[test]
+fn bundled_recipes_parse() {
+    let recipes = bundled_recipes();
+    assert!(!recipes.is_empty());
+    for recipe in recipes {
+        assert!(!recipe.id().is_empty());
+        assert!(!recipe.ingredients().is_empty(), "{} has no ingredients", recipe.name());
+    }
+}
+
+#[test]
+fn suggestions_rank_by_the_most_urgent_ingredients() {
+    let recipes = parse_recipes(BOOK, false).unwrap();
+    let items = vec![
+        expiring("🍅番茄", 1, Quantity::pieces(3.0)),
+        expiring("🥛牛奶", 2, Quantity::new(1.0, Unit::Liter)),
+        expiring("🥚鸡蛋", 10, Quantity::pieces(6.0)),
+    ];
+    let suggestions = suggest_recipes(&recipes, &items, &Category::all(&[]), today());
+    let names: Vec<&str> = suggestions.iter().map(|s| s.recipe.name()).collect();
+    // 番茄 1 天后过期，分数 1/2；牛奶 2 天，分数 1/3；鸡蛋不临期，沙拉和双蛋饭不推荐
+    assert_eq!(names, vec!["番茄炒蛋", "牛奶炖蛋"]);
+    assert_eq!(suggestions[0].urgent_count(), 1);
+    assert!(suggestions[0].missing.is_empty());
+    assert_eq!(suggestions[0].matches.len(), 2);
+}
+
+#[test]
+fn an_item_matches_at_most_one_ingredient() {
+    let recipes = parse_recipes(BOOK, false).unwrap();
+    let eggs = expiring("🥚鸡蛋", 1, Quantity::pieces(6.0));
+    let mut items = vec![eggs.clone()];
+    let suggestions = suggest_recipes(&recipes, &items, &Category::all(&[]), today());
+    let double = suggestions.iter().find(|s| s.recipe.id() == "double_egg").unwrap();
+    assert_eq!(double.matches.len(), 1);
+    assert_eq!(double.missing, vec!["蛋".to_string()]);
+
+    let consumed = cook(double, &mut items);
+    assert_eq!(consumed.len(), 1);
+    assert_eq!(items[0].quantity(), Quantity::pieces(5.0));
+}
+
+#[test]
+fn cooking_consumes_each_ingredient_and_removes_used_up_items() {
+    let recipes = parse_recipes(BOOK, false).unwrap();
+    let mut items = vec![
+        expiring("🥛牛奶", 1, Quantity::new(1.0, Unit::Liter)),
+        expiring("🥚鸡蛋", 2, Quantity::pieces(2.0)),
+    ];
+    let suggestions = suggest_recipes(&recipes, &items, &Category::all(&[]), today());
+    let custard = suggestions.iter().find(|s| s.recipe.id() == "custard").unwrap();
+
+    let consumed = cook(custard, &mut items);
+    assert_eq!(consumed.len(), 2);
+    // 牛奶按菜谱用量换算为升
+    assert_eq!(consumed[0].amount, Quantity::new(0.25, Unit::Liter));
+    assert!(!consumed[0].removed);
+    assert!(consumed[1].removed);
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].quantity(), Quantity::new(0.75, Unit::Liter));
+}
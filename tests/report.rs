@@ -0,0 +1,115 @@
+//! 价格与月度报告的集成测试：合并批次时的单价、按比例计算的部分价值，以及按月汇总。
+
+mod common;
+
+use common::expiring;
+use ukeep::models::{monthly_reports, Currency, EventKind, HistoryEvent, Item, Price, Quantity};
+use ukeep::utils::{parse_csv, write_csv};
+
+fn cheese(amount: f64, price: Option<Price>) -> Item {
+    expiring("🧀奶酪", 9, Quantity::pieces(amount)).with_total_price(price)
+}
+
+fn cny(amount: f64) -> Option<Price> {
+    Some(Price::new(amount, Currency::Cny))
+}
+
+// 把记录的时间改到指定的日子，模拟不同月份的操作
+fn on(event: HistoryEvent, date: &str) -> HistoryEvent {
+    let mut json = serde_json::to_value(&event).unwrap();
+    json["at"] = serde_json::Value::String(format!("{}T12:00:00", date));
+    serde_json::from_value(json).unwrap()
+}
+
+#[test]
+fn consuming_part_of_an_item_records_a_proportional_value() {
+    let item = cheese(4.0, cny(20.0));
+    assert_eq!(item.unit_price(), cny(5.0));
+    assert_eq!(item.value_of(1.0), cny(5.0));
+    assert_eq!(item.value(), cny(20.0));
+    assert_eq!(cheese(4.0, None).value_of(1.0), None);
+}
+
+#[test]
+fn same_currency_batches_average_the_unit_price() {
+    let mut existing = cheese(2.0, cny(10.0));
+    assert!(existing.absorb(cheese(2.0, cny(30.0))).is_ok());
+    assert_eq!(existing.quantity(), Quantity::pieces(4.0));
+    assert_eq!(existing.unit_price(), cny(10.0));
+    assert_eq!(existing.value(), cny(40.0));
+}
+
+#[test]
+fn items_with_incompatible_prices_stay_separate() {
+    // 货币不同
+    let mut existing = cheese(2.0, cny(10.0));
+    let usd = cheese(2.0, Some(Price::new(3.0, Currency::Usd)));
+    assert!(existing.absorb(usd).is_err());
+    assert_eq!(existing.value(), cny(10.0));
+
+    // 已有库存没有价格，新买的价格不能算到旧库存上
+    let mut unpriced = cheese(2.0, None);
+    assert!(unpriced.absorb(cheese(2.0, cny(10.0))).is_err());
+    assert_eq!(unpriced.value(), None);
+
+    // 新的一批没有价格，也不能按已有单价估价
+    let mut priced = cheese(2.0, cny(10.0));
+    assert!(priced.absorb(cheese(2.0, None)).is_err());
+    assert_eq!(priced.quantity(), Quantity::pieces(2.0));
+
+    // 都没有价格时照常合并
+    assert!(unpriced.absorb(cheese(1.0, None)).is_ok());
+    assert_eq!(unpriced.quantity(), Quantity::pieces(3.0));
+}
+
+#[test]
+fn monthly_reports_group_by_month_and_currency() {
+    let item = cheese(4.0, cny(20.0));
+    let one = Quantity::pieces(1.0);
+    let events = vec![
+        on(HistoryEvent::new(&item, EventKind::Added, item.quantity(), false, None).with_value(item.value()), "2026-02-01"),
+        on(HistoryEvent::new(&item, EventKind::Consumed, one, false, None).with_value(item.value_of(1.0)), "2026-02-03"),
+        on(HistoryEvent::new(&item, EventKind::Wasted, one, false, None).with_value(item.value_of(1.0)), "2026-02-20"),
+        on(HistoryEvent::new(&item, EventKind::Consumed, one, false, None).with_value(item.value_of(1.0)), "2026-03-02"),
+        on(
+            HistoryEvent::new(&item, EventKind::Consumed, one, false, None).with_value(Some(Price::new(2.0, Currency::Usd))),
+            "2026-03-05",
+        ),
+        // 没有价值的记录不计入
+        on(HistoryEvent::new(&item, EventKind::Wasted, one, true, None), "2026-04-01"),
+    ];
+
+    let reports = monthly_reports(&events);
+    assert_eq!(reports.len(), 2);
+
+    let march = &reports[0];
+    assert_eq!((march.year(), march.month()), (2026, 3));
+    assert_eq!(march.currencies(), vec![Currency::Cny, Currency::Usd]);
+    assert_eq!(march.consumed(Currency::Cny), 5.0);
+    assert_eq!(march.consumed(Currency::Usd), 2.0);
+    assert_eq!(march.waste_ratio(Currency::Cny), 0.0);
+
+    let february = &reports[1];
+    assert_eq!(february.label(), "2026年2月");
+    // 入库不算吃掉
+    assert_eq!(february.consumed(Currency::Cny), 5.0);
+    assert_eq!(february.wasted(Currency::Cny), 5.0);
+    assert_eq!(february.waste_ratio(Currency::Cny), 0.5);
+}
+
+#[test]
+fn parse_csv_handles_quotes_crlf_and_bom() {
+    let text = "\u{FEFF}name,note\r\n\"奶酪, 切片\",\"说 \"\"好吃\"\"\"\r\n\r\n\"两\n行\",\n";
+    let rows = parse_csv(text);
+    assert_eq!(
+        rows,
+        vec![
+            vec!["name".to_string(), "note".to_string()],
+            vec!["奶酪, 切片".to_string(), "说 \"好吃\"".to_string()],
+            vec!["两\n行".to_string(), String::new()],
+        ]
+    );
+    assert_eq!(parse_csv(&write_csv(&rows)), rows);
+    assert!(parse_csv("").is_empty());
+    assert_eq!(parse_csv("a,b"), vec![vec!["a".to_string(), "b".to_string()]]);
+}
@@ -0,0 +1,121 @@
+//! 快照保留策略：自动快照按时间分段保留，导入前等快照单独保留，总大小有上限。
+
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use ukeep::models::{apply_retention, needs_auto_snapshot, record_snapshot, Item, Snapshot, SnapshotKind};
+
+const UNLIMITED: usize = usize::MAX;
+
+fn now() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2026, 3, 1).unwrap().and_hms_opt(12, 30, 0).unwrap()
+}
+
+fn inventory(count: usize) -> Vec<Item> {
+    (0..count)
+        .map(|i| Item::new(format!("🥛牛奶{}", i), NaiveDate::from_ymd_opt(2026, 3, 10).unwrap(), now().date()))
+        .collect()
+}
+
+fn snapshot(kind: SnapshotKind, minutes_ago: i64) -> Snapshot {
+    Snapshot::new_at(&inventory(1), kind, now() - Duration::minutes(minutes_ago))
+}
+
+#[test]
+fn auto_snapshots_keep_one_per_hour_day_and_week() {
+    let mut snapshots: Vec<Snapshot> = [5, 20, 70, 130, 60 * 30, 60 * 31, 60 * 24 * 10, 60 * 24 * 40]
+        .into_iter()
+        .map(|m| snapshot(SnapshotKind::Auto, m))
+        .collect();
+    apply_retention(&mut snapshots, now(), UNLIMITED);
+
+    let ages: Vec<i64> = snapshots.iter().map(|s| (now() - s.taken_at()).num_minutes()).collect();
+    // 同一小时内只留最新的；昨天的两份留一份；10 天前的按周保留；40 天前的超出所有窗口
+    assert_eq!(ages, vec![5, 70, 130, 60 * 30, 60 * 24 * 10]);
+}
+
+#[test]
+fn manual_snapshots_do_not_evict_the_hourly_auto_snapshot() {
+    let mut snapshots = vec![snapshot(SnapshotKind::Auto, 20)];
+    record_snapshot(&mut snapshots, snapshot(SnapshotKind::BeforeImport, 5), now());
+    record_snapshot(&mut snapshots, snapshot(SnapshotKind::BeforeRestore, 1), now());
+
+    let kinds: Vec<SnapshotKind> = snapshots.iter().map(|s| s.kind()).collect();
+    assert_eq!(kinds, vec![SnapshotKind::BeforeRestore, SnapshotKind::BeforeImport, SnapshotKind::Auto]);
+}
+
+#[test]
+fn only_the_latest_manual_snapshots_are_kept() {
+    let mut snapshots: Vec<Snapshot> = (0..8).map(|m| snapshot(SnapshotKind::BeforeBulk, m)).collect();
+    apply_retention(&mut snapshots, now(), UNLIMITED);
+
+    let ages: Vec<i64> = snapshots.iter().map(|s| (now() - s.taken_at()).num_minutes()).collect();
+    assert_eq!(ages, vec![0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn manual_snapshots_do_not_block_the_hourly_auto_snapshot() {
+    let snapshots = vec![snapshot(SnapshotKind::BeforeImport, 5)];
+    assert!(needs_auto_snapshot(&snapshots, &inventory(2), &inventory(1), now()));
+}
+
+// 与应用中的自动快照一样：库存变化时按需保存变化前的状态
+fn change(snapshots: &mut Vec<Snapshot>, previous: &[Item], current: &[Item], at: NaiveDateTime) {
+    if needs_auto_snapshot(snapshots, previous, current, at) {
+        record_snapshot(snapshots, Snapshot::new_at(previous, SnapshotKind::Auto, at), at);
+    }
+}
+
+#[test]
+fn a_destructive_first_change_of_the_hour_can_be_undone() {
+    let mut snapshots = Vec::new();
+    let two = inventory(2);
+    let three = inventory(3);
+    change(&mut snapshots, &[], &two, now() - Duration::minutes(80));
+    change(&mut snapshots, &two, &three, now() - Duration::minutes(50));
+    assert_eq!(snapshots.len(), 1);
+
+    // 新的一小时里第一次改动就误删了全部物品
+    change(&mut snapshots, &three, &[], now());
+    assert_eq!(snapshots.len(), 2);
+    assert_eq!(snapshots[0].items(), three.as_slice());
+
+    // 同一小时内的后续改动不再保存
+    change(&mut snapshots, &[], &two, now() + Duration::minutes(5));
+    assert_eq!(snapshots.len(), 2);
+}
+
+#[test]
+fn unchanged_or_duplicate_states_are_not_saved() {
+    let two = inventory(2);
+    assert!(!needs_auto_snapshot(&[], &two, &two, now()));
+    assert!(!needs_auto_snapshot(&[], &[], &two, now()));
+
+    let earlier = vec![Snapshot::new_at(&two, SnapshotKind::Auto, now() - Duration::hours(2))];
+    assert!(!needs_auto_snapshot(&earlier, &two, &inventory(1), now()));
+    assert!(needs_auto_snapshot(&earlier, &inventory(3), &inventory(1), now()));
+}
+
+#[test]
+fn total_size_is_bounded_by_dropping_the_oldest_auto_snapshots_first() {
+    let large = inventory(200);
+    let mut snapshots = vec![
+        Snapshot::new_at(&large, SnapshotKind::Auto, now() - Duration::hours(50)),
+        Snapshot::new_at(&large, SnapshotKind::Auto, now() - Duration::hours(30)),
+        Snapshot::new_at(&large, SnapshotKind::BeforeImport, now() - Duration::hours(40)),
+        Snapshot::new_at(&large, SnapshotKind::Auto, now() - Duration::hours(1)),
+    ];
+    let one = serde_json::to_vec(&snapshots[0]).unwrap().len();
+    apply_retention(&mut snapshots, now(), one * 2 + one / 2);
+
+    let kept: Vec<(SnapshotKind, i64)> = snapshots
+        .iter()
+        .map(|s| (s.kind(), (now() - s.taken_at()).num_hours()))
+        .collect();
+    assert_eq!(kept, vec![(SnapshotKind::Auto, 1), (SnapshotKind::BeforeImport, 40)]);
+}
+
+#[test]
+fn the_newest_snapshot_survives_even_when_over_budget() {
+    let mut snapshots = vec![Snapshot::new_at(&inventory(50), SnapshotKind::Auto, now())];
+    apply_retention(&mut snapshots, now(), 1);
+    assert_eq!(snapshots.len(), 1);
+}
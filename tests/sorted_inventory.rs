@@ -0,0 +1,91 @@
+//! 增量维护的库存列表：多次修改后的顺序应该和重新整体排序一致。
+
+mod common;
+
+use common::{expiring, today};
+use ukeep::models::{InventoryQuery, Item, Location, Quantity, SortOrder, SortedInventory};
+use ukeep::virtual_list::{padding, VisibleRows, OVERSCAN, ROW_HEIGHT};
+use uuid::Uuid;
+
+fn item(name: &str, days: i64, amount: f64) -> Item {
+    expiring(name, days, Quantity::pieces(amount))
+}
+
+fn rebuilt(query: &InventoryQuery, items: &[Item]) -> Vec<Uuid> {
+    let mut view = SortedInventory::new(query.clone());
+    view.sync(items, &[], today());
+    view.ids().collect()
+}
+
+#[test]
+fn incremental_sync_matches_full_rebuild() {
+    let mut items: Vec<Item> = (0..40).map(|i| item(&format!("物品{i}"), (i * 13 % 17) as i64 - 3, (i % 5 + 1) as f64)).collect();
+    for sort in SortOrder::ALL {
+        let query = InventoryQuery { sort, ..Default::default() };
+        let mut view = SortedInventory::new(query.clone());
+        view.sync(&items, &[], today());
+        assert_eq!(view.len(), items.len());
+
+        // 一次只改一两件：走增量路径
+        items[3].consume(1.0);
+        items[7].shift_expiry(20);
+        view.sync(&items, &[], today());
+        assert_eq!(view.ids().collect::<Vec<_>>(), rebuilt(&query, &items));
+
+        let gone = items.remove(11).id();
+        items.push(item("新来的", 2, 3.0));
+        view.sync(&items, &[], today());
+        assert_eq!(view.index_of(gone), None);
+        assert_eq!(view.ids().collect::<Vec<_>>(), rebuilt(&query, &items));
+
+        // 行号和库存下标可以互相查到
+        let first = view.id_at(0).unwrap();
+        assert_eq!(view.index_of(first), Some(0));
+        assert_eq!(items[view.position(first).unwrap()].id(), first);
+    }
+}
+
+#[test]
+fn filters_follow_item_changes() {
+    let mut items = vec![item("牛奶", 1, 1.0), item("面包", 5, 1.0), item("冻饺子", 60, 2.0).with_location(Location::Freezer)];
+    let query = InventoryQuery {
+        location: Some(Location::Freezer),
+        ..Default::default()
+    };
+    let mut view = SortedInventory::new(query);
+    view.sync(&items, &[], today());
+    assert_eq!(view.len(), 1);
+
+    // 移进冷冻室后出现在列表中，按过期日期排在饺子前面
+    items[0].set_location(Location::Freezer);
+    view.sync(&items, &[], today());
+    assert_eq!(view.ids().collect::<Vec<_>>(), vec![items[0].id(), items[2].id()]);
+
+    // 搜索支持拼音首字母
+    let mut search = SortedInventory::new(InventoryQuery {
+        text: "mb".to_string(),
+        ..Default::default()
+    });
+    search.sync(&items, &[], today());
+    assert_eq!(search.ids().collect::<Vec<_>>(), vec![items[1].id()]);
+}
+
+#[test]
+fn visible_rows_cover_viewport_with_overscan() {
+    // 列表顶部在视口 200px 处，844px 高的视口能看到 8 行
+    let rows = VisibleRows::measure(200.0, 844.0);
+    assert_eq!(rows.range(10_000), 0..(8 + 1 + OVERSCAN));
+
+    // 向下滚过 100 行
+    let rows = VisibleRows::measure(-100.0 * ROW_HEIGHT, 844.0);
+    let range = rows.range(10_000);
+    assert_eq!(range.start, 100 - OVERSCAN);
+    assert!(rows.contains(101));
+    assert!(!rows.contains(99));
+    let (top, bottom) = padding(&range, 10_000);
+    assert_eq!(top, (100 - OVERSCAN) as f64 * ROW_HEIGHT);
+    assert_eq!(top + bottom + range.len() as f64 * ROW_HEIGHT, 10_000.0 * ROW_HEIGHT);
+
+    // 列表比视口短时不超出列表长度
+    assert_eq!(VisibleRows::measure(0.0, 844.0).range(3), 0..3);
+}
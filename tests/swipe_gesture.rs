@@ -0,0 +1,238 @@
+//! 滑动手势状态机的测试：只用坐标和视口大小驱动，不需要浏览器。
+
+use ukeep::gesture::{GestureAction, MenuFrame, PointerInput, QuantitySteps, Swipe, SwipeGesture, Viewport};
+use ukeep::models::{Quantity, Unit};
+
+const VIEWPORT: Viewport = Viewport {
+    width: 390.0,
+    height: 844.0,
+};
+
+fn at(id: i32, x: f64, y: f64) -> PointerInput {
+    PointerInput { id, x, y }
+}
+
+fn pieces(n: f64) -> QuantitySteps {
+    QuantitySteps::new(Quantity::pieces(n))
+}
+
+#[test]
+fn steps_and_labels_follow_unit() {
+    let three = pieces(3.0);
+    assert_eq!(three.steps(), &[1.0, 2.0, 3.0]);
+    assert!(three.has_menu());
+    assert_eq!(three.quick(), 1.0);
+    assert_eq!(three.label(2), "3");
+
+    let many = pieces(20.0);
+    assert_eq!(many.steps(), &[1.0, 2.0, 3.0, 5.0, 10.0, 20.0]);
+    assert_eq!(many.label(5), "全部(20)");
+
+    let flour = QuantitySteps::new(Quantity::new(1.0, Unit::Kilogram));
+    assert_eq!(flour.steps(), &[0.1, 0.25, 0.5, 1.0]);
+    assert_eq!(flour.quick(), 0.1);
+    assert_eq!(flour.label(3), format!("全部({})", Quantity::new(1.0, Unit::Kilogram)));
+
+    let last = pieces(1.0);
+    assert!(!last.has_menu());
+    assert_eq!(last.quick(), 1.0);
+}
+
+#[test]
+fn short_drag_does_nothing() {
+    let steps = pieces(3.0);
+    let mut g = SwipeGesture::default();
+    assert!(g.pointer_down(at(1, 100.0, 300.0)));
+    g.pointer_move(at(1, 150.0, 300.0), &steps, VIEWPORT);
+    assert_eq!(g.drag_x(), 50.0);
+    assert_eq!(g.pending(), None);
+    assert_eq!(g.pointer_up(at(1, 150.0, 300.0), &steps, VIEWPORT), None);
+    assert!(!g.is_active());
+    assert_eq!(g.drag_x(), 0.0);
+}
+
+#[test]
+fn left_swipe_wastes_unless_pulled_back() {
+    let steps = pieces(3.0);
+    let mut g = SwipeGesture::default();
+    g.pointer_down(at(1, 100.0, 300.0));
+    g.pointer_move(at(1, 10.0, 300.0), &steps, VIEWPORT);
+    assert_eq!(g.pending(), Some(Swipe::Left));
+    assert_eq!(g.pointer_up(at(1, 10.0, 300.0), &steps, VIEWPORT), Some(GestureAction::SwipeLeft));
+
+    g.pointer_down(at(1, 100.0, 300.0));
+    g.pointer_move(at(1, 10.0, 300.0), &steps, VIEWPORT);
+    g.pointer_move(at(1, 60.0, 300.0), &steps, VIEWPORT);
+    assert_eq!(g.pending(), None);
+    assert_eq!(g.pointer_up(at(1, 60.0, 300.0), &steps, VIEWPORT), None);
+}
+
+#[test]
+fn single_option_consumes_without_menu() {
+    let steps = pieces(1.0);
+    let mut g = SwipeGesture::default();
+    g.pointer_down(at(1, 100.0, 300.0));
+    g.pointer_move(at(1, 190.0, 300.0), &steps, VIEWPORT);
+    assert_eq!(g.menu(&steps, VIEWPORT), None);
+    assert_eq!(g.pointer_up(at(1, 190.0, 300.0), &steps, VIEWPORT), Some(GestureAction::SwipeRight(1.0)));
+}
+
+#[test]
+fn menu_anchors_at_threshold_and_hit_tests_rows() {
+    let steps = pieces(3.0);
+    let mut g = SwipeGesture::default();
+    g.pointer_down(at(1, 100.0, 300.0));
+    g.pointer_move(at(1, 190.0, 300.0), &steps, VIEWPORT);
+    assert_eq!(g.pending(), Some(Swipe::Right));
+    // 锚点右移 12px，竖直方向上移半行
+    let frame = g.menu(&steps, VIEWPORT).unwrap();
+    assert_eq!(frame, MenuFrame { left: 202.0, top: 276.0, height: 144.0 });
+    // 菜单在指针右侧，锚点本身不命中
+    assert_eq!(g.hovered(), None);
+
+    // 继续移动不会改变锚点
+    g.pointer_move(at(1, 220.0, 330.0), &steps, VIEWPORT);
+    assert_eq!(g.menu(&steps, VIEWPORT), Some(frame));
+    assert_eq!(g.hovered(), Some(1));
+
+    assert_eq!(g.pointer_up(at(1, 220.0, 390.0), &steps, VIEWPORT), Some(GestureAction::SwipeRight(3.0)));
+}
+
+#[test]
+fn backtracking_keeps_menu_open() {
+    let steps = pieces(3.0);
+    let mut g = SwipeGesture::default();
+    g.pointer_down(at(1, 100.0, 300.0));
+    g.pointer_move(at(1, 190.0, 300.0), &steps, VIEWPORT);
+
+    // 往回滑到阈值以内，菜单仍在，只是没有命中任何一行
+    g.pointer_move(at(1, 150.0, 380.0), &steps, VIEWPORT);
+    assert_eq!(g.pending(), Some(Swipe::Right));
+    assert!(g.menu(&steps, VIEWPORT).is_some());
+    assert_eq!(g.hovered(), None);
+
+    // 一直滑到左边也不会变成扔掉
+    g.pointer_move(at(1, 0.0, 300.0), &steps, VIEWPORT);
+    assert_eq!(g.pending(), Some(Swipe::Right));
+
+    // 再回到菜单里选中第三行
+    g.pointer_move(at(1, 210.0, 380.0), &steps, VIEWPORT);
+    assert_eq!(g.hovered(), Some(2));
+    assert_eq!(g.pointer_up(at(1, 210.0, 380.0), &steps, VIEWPORT), Some(GestureAction::SwipeRight(3.0)));
+}
+
+#[test]
+fn releasing_outside_menu_cancels_unless_offscreen() {
+    let steps = pieces(3.0);
+    let mut g = SwipeGesture::default();
+    g.pointer_down(at(1, 100.0, 300.0));
+    g.pointer_move(at(1, 190.0, 300.0), &steps, VIEWPORT);
+    assert_eq!(g.pointer_up(at(1, 100.0, 700.0), &steps, VIEWPORT), None);
+
+    // 划到屏幕右边缘松手：使用默认数量
+    g.pointer_down(at(1, 100.0, 300.0));
+    g.pointer_move(at(1, 190.0, 300.0), &steps, VIEWPORT);
+    assert_eq!(g.pointer_up(at(1, 385.0, 700.0), &steps, VIEWPORT), Some(GestureAction::SwipeRight(1.0)));
+}
+
+#[test]
+fn menu_is_clamped_to_viewport() {
+    let steps = pieces(3.0);
+    let mut g = SwipeGesture::default();
+    g.pointer_down(at(1, 200.0, 830.0));
+    g.pointer_move(at(1, 380.0, 830.0), &steps, VIEWPORT);
+    // 右边和下边都不能超出视口（留白 12px）
+    let frame = g.menu(&steps, VIEWPORT).unwrap();
+    assert_eq!(frame.left, 390.0 - 72.0 - 12.0);
+    assert_eq!(frame.top, 844.0 - 144.0 - 12.0);
+
+    // 视口比菜单还小时贴着左上角
+    let tiny = Viewport { width: 50.0, height: 100.0 };
+    assert_eq!(MenuFrame::new((40.0, 90.0), 3, tiny), MenuFrame { left: 12.0, top: 12.0, height: 144.0 });
+    assert!(tiny.is_offscreen(42.0));
+    assert!(!tiny.is_offscreen(41.0));
+}
+
+#[test]
+fn other_pointers_are_ignored() {
+    let steps = pieces(3.0);
+    let mut g = SwipeGesture::default();
+    assert!(g.pointer_down(at(1, 100.0, 300.0)));
+    // 第二根手指按下、移动、松开都不影响第一根
+    assert!(!g.pointer_down(at(2, 300.0, 300.0)));
+    g.pointer_move(at(2, 10.0, 300.0), &steps, VIEWPORT);
+    assert_eq!(g.drag_x(), 0.0);
+    assert_eq!(g.pointer_up(at(2, 10.0, 300.0), &steps, VIEWPORT), None);
+    g.pointer_cancel(2);
+    assert_eq!(g.pointer(), Some(1));
+
+    g.pointer_move(at(1, 10.0, 300.0), &steps, VIEWPORT);
+    assert_eq!(g.pointer_up(at(1, 10.0, 300.0), &steps, VIEWPORT), Some(GestureAction::SwipeLeft));
+}
+
+#[test]
+fn cancel_discards_pending_swipe() {
+    let steps = pieces(3.0);
+    let mut g = SwipeGesture::default();
+    g.pointer_down(at(1, 100.0, 300.0));
+    g.pointer_move(at(1, 190.0, 300.0), &steps, VIEWPORT);
+    g.pointer_cancel(1);
+    assert!(!g.is_active());
+    assert_eq!(g.pending(), None);
+    assert_eq!(g.menu(&steps, VIEWPORT), None);
+    // 取消后迟到的松开事件不会触发任何动作
+    assert_eq!(g.pointer_up(at(1, 190.0, 300.0), &steps, VIEWPORT), None);
+    // 可以开始新的一轮
+    assert!(g.pointer_down(at(3, 100.0, 300.0)));
+}
+
+#[test]
+fn long_press_requires_holding_still() {
+    let steps = pieces(3.0);
+    let mut g = SwipeGesture::default();
+    assert_eq!(g.long_press(), None);
+
+    g.pointer_down(at(1, 100.0, 300.0));
+    g.pointer_move(at(1, 104.0, 302.0), &steps, VIEWPORT);
+    assert_eq!(g.long_press(), Some(GestureAction::LongPress));
+    assert!(!g.is_active());
+
+    // 移动过再回到原处也不算长按
+    g.pointer_down(at(1, 100.0, 300.0));
+    g.pointer_move(at(1, 130.0, 300.0), &steps, VIEWPORT);
+    g.pointer_move(at(1, 100.0, 300.0), &steps, VIEWPORT);
+    assert_eq!(g.long_press(), None);
+    assert!(g.is_active());
+}
+
+#[test]
+fn usual_amount_and_custom_row() {
+    // 24 个里常吃 7 个：插入到 5 和 10 之间，并作为默认数量
+    let steps = pieces(24.0).with_usual(Some(7.0)).with_custom();
+    assert_eq!(steps.steps(), &[1.0, 2.0, 3.0, 5.0, 7.0, 10.0, 24.0]);
+    assert_eq!(steps.rows(), 8);
+    assert_eq!(steps.quick(), 7.0);
+    assert_eq!(steps.label(4), "7 常用");
+    assert!(steps.is_custom(7));
+    assert_eq!(steps.label(7), "自定义…");
+
+    // 不少于全部的常用数量被忽略；只剩一个时不需要“自定义…”
+    let last = pieces(1.0).with_usual(Some(2.0)).with_custom();
+    assert_eq!(last.usual(), None);
+    assert!(!last.has_menu());
+
+    // 重量只剩一个选项时也可以自定义
+    let rice = QuantitySteps::new(Quantity::new(40.0, Unit::Gram)).with_custom();
+    assert!(rice.has_menu());
+}
+
+#[test]
+fn custom_row_asks_for_amount() {
+    let steps = pieces(3.0).with_custom();
+    let mut g = SwipeGesture::default();
+    g.pointer_down(at(1, 100.0, 300.0));
+    g.pointer_move(at(1, 190.0, 300.0), &steps, VIEWPORT);
+    // 四行：1、2、3、自定义…
+    assert_eq!(g.menu(&steps, VIEWPORT).unwrap().height, 192.0);
+    assert_eq!(g.pointer_up(at(1, 220.0, 430.0), &steps, VIEWPORT), Some(GestureAction::CustomAmount));
+}
@@ -0,0 +1,267 @@
+//! WebDAV 备份的集成测试：在本地启动一个最小的 WebDAV 替身服务器，
+//! 通过阻塞的 TCP 传输层驱动 `WebDavClient`。
+
+use chrono::{NaiveDate, NaiveDateTime};
+use futures::executor::block_on;
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use ukeep::crypto::{self, DerivedKey};
+use ukeep::error::UkeepError;
+use ukeep::models::Item;
+use ukeep::storage::import_data_from_bytes;
+use ukeep::sync::{HttpRequest, HttpResponse, HttpTransport, WebDavClient};
+
+const USER: &str = "alice";
+const PASSWORD: &str = "secret";
+const COLLECTION: &str = "/dav/ukeep";
+
+// 替身服务器的文件系统：集合（目录）和文件
+#[derive(Default)]
+struct Store {
+    collections: Vec<String>,
+    files: BTreeMap<String, Vec<u8>>,
+}
+
+struct StandInServer {
+    base_url: String,
+    store: Arc<Mutex<Store>>,
+}
+
+impl StandInServer {
+    fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}{}", listener.local_addr().unwrap(), COLLECTION);
+        let store = Arc::new(Mutex::new(Store::default()));
+        let shared = store.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else { break };
+                handle(stream, &shared);
+            }
+        });
+        Self { base_url, store }
+    }
+
+    fn file_names(&self) -> Vec<String> {
+        let store = self.store.lock().unwrap();
+        store.files.keys().map(|path| path.rsplit('/').next().unwrap().to_string()).collect()
+    }
+}
+
+fn handle(mut stream: TcpStream, store: &Mutex<Store>) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).unwrap();
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().trim_end_matches('/').to_string();
+
+    let mut content_length = 0;
+    let mut authorized = false;
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        let (key, value) = line.split_once(':').unwrap();
+        match key.to_ascii_lowercase().as_str() {
+            "content-length" => content_length = value.trim().parse().unwrap(),
+            "authorization" => {
+                use base64::Engine;
+                let expected = base64::engine::general_purpose::STANDARD.encode(format!("{}:{}", USER, PASSWORD));
+                authorized = value.trim() == format!("Basic {}", expected);
+            }
+            _ => {}
+        }
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).unwrap();
+
+    let (status, response) = if !authorized {
+        (401, Vec::new())
+    } else {
+        respond(&method, &path, body, &mut store.lock().unwrap())
+    };
+    let head = format!(
+        "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        response.len()
+    );
+    stream.write_all(head.as_bytes()).unwrap();
+    stream.write_all(&response).unwrap();
+}
+
+fn respond(method: &str, path: &str, body: Vec<u8>, store: &mut Store) -> (u16, Vec<u8>) {
+    let parent = path.rsplit_once('/').map(|(p, _)| p).unwrap_or_default();
+    match method {
+        "MKCOL" if store.collections.iter().any(|c| c == path) => (405, Vec::new()),
+        "MKCOL" => {
+            store.collections.push(path.to_string());
+            (201, Vec::new())
+        }
+        "PUT" if !store.collections.iter().any(|c| c == parent) => (409, Vec::new()),
+        "PUT" => {
+            store.files.insert(path.to_string(), body);
+            (201, Vec::new())
+        }
+        "GET" => match store.files.get(path) {
+            Some(bytes) => (200, bytes.clone()),
+            None => (404, Vec::new()),
+        },
+        "DELETE" => match store.files.remove(path) {
+            Some(_) => (204, Vec::new()),
+            None => (404, Vec::new()),
+        },
+        "PROPFIND" if store.collections.iter().any(|c| c == path) => {
+            // 与 Nextcloud 一样使用大写的 D: 前缀，并对文件名做百分号编码
+            let mut xml = String::from(r#"<?xml version="1.0"?><D:multistatus xmlns:D="DAV:">"#);
+            xml += &format!("<D:response><D:href>{}/</D:href></D:response>", path);
+            for file in store.files.keys().filter(|f| f.starts_with(&format!("{}/", path))) {
+                xml += &format!("<D:response><D:href>{}</D:href></D:response>", file.replace('_', "%5F"));
+            }
+            xml += "</D:multistatus>";
+            (207, xml.into_bytes())
+        }
+        _ => (404, Vec::new()),
+    }
+}
+
+// 使用标准库 TCP 连接实现的阻塞传输层
+struct StdTransport;
+
+impl HttpTransport for StdTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse, String> {
+        let rest = request.url.strip_prefix("http://").ok_or("only http is supported")?;
+        let (host, path) = rest.split_once('/').map(|(h, p)| (h, format!("/{}", p))).unwrap();
+        let mut stream = TcpStream::connect(host).map_err(|e| e.to_string())?;
+
+        let body = request.body.unwrap_or_default();
+        let mut head = format!("{} {} HTTP/1.1\r\nHost: {}\r\nContent-Length: {}\r\n", request.method, path, host, body.len());
+        for (key, value) in &request.headers {
+            head += &format!("{}: {}\r\n", key, value);
+        }
+        head += "\r\n";
+        stream.write_all(head.as_bytes()).map_err(|e| e.to_string())?;
+        stream.write_all(&body).map_err(|e| e.to_string())?;
+
+        let mut raw = Vec::new();
+        stream.read_to_end(&mut raw).map_err(|e| e.to_string())?;
+        let split = raw.windows(4).position(|w| w == b"\r\n\r\n").ok_or("bad response")?;
+        let head = String::from_utf8_lossy(&raw[..split]);
+        let status = head.split_whitespace().nth(1).and_then(|s| s.parse().ok()).ok_or("bad status")?;
+        Ok(HttpResponse {
+            status,
+            body: raw[split + 4..].to_vec(),
+        })
+    }
+}
+
+fn client(server: &StandInServer) -> WebDavClient<StdTransport> {
+    WebDavClient::new(&server.base_url, USER, PASSWORD, StdTransport)
+}
+
+fn at(hour: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2025, 3, 1).unwrap().and_hms_opt(hour, 0, 0).unwrap()
+}
+
+fn sample_items() -> Vec<Item> {
+    let expiry = NaiveDate::from_ymd_opt(2025, 3, 8).unwrap();
+    vec![
+        Item::new_with_quantity("🥛全脂牛奶".into(), expiry, 2, at(9).date()),
+        Item::new("🥚鸡蛋".into(), expiry, at(9).date()),
+    ]
+}
+
+#[test]
+fn backup_creates_collection_and_restores_round_trip() {
+    let server = StandInServer::start();
+    let webdav = client(&server);
+    let items = sample_items();
+
+    let name = block_on(webdav.backup(&items, None, at(8), 5)).unwrap();
+    assert_eq!(name, "ukeep_backup_20250301_080000.json");
+
+    let listed = block_on(webdav.list()).unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].name, name);
+    assert_eq!(listed[0].taken_at(), Some(at(8)));
+
+    let bytes = block_on(webdav.download(&name)).unwrap();
+    assert_eq!(import_data_from_bytes(&bytes, None, at(8).date()).unwrap(), items);
+}
+
+#[test]
+fn rotation_keeps_latest_snapshots() {
+    let server = StandInServer::start();
+    let webdav = client(&server);
+    let items = sample_items();
+
+    for hour in 1..=5 {
+        block_on(webdav.backup(&items, None, at(hour), 3)).unwrap();
+    }
+
+    let names: Vec<String> = block_on(webdav.list()).unwrap().into_iter().map(|s| s.name).collect();
+    assert_eq!(
+        names,
+        vec![
+            "ukeep_backup_20250301_050000.json",
+            "ukeep_backup_20250301_040000.json",
+            "ukeep_backup_20250301_030000.json",
+        ]
+    );
+    assert_eq!(server.file_names().len(), 3);
+}
+
+#[test]
+fn list_ignores_unrelated_files() {
+    let server = StandInServer::start();
+    let webdav = client(&server);
+    block_on(webdav.ensure_collection()).unwrap();
+    block_on(webdav.upload("notes.txt", b"hello".to_vec())).unwrap();
+    block_on(webdav.backup(&sample_items(), None, at(9), 1)).unwrap();
+
+    let listed = block_on(webdav.list()).unwrap();
+    assert_eq!(listed.len(), 1);
+    // 轮换只删除 uKeep 自己的备份
+    assert!(server.file_names().contains(&"notes.txt".to_string()));
+}
+
+#[test]
+fn encrypted_backup_is_unreadable_on_server() {
+    let server = StandInServer::start();
+    let webdav = client(&server);
+    let items = sample_items();
+    let key = DerivedKey::derive_random("family passphrase").unwrap();
+
+    let name = block_on(webdav.backup(&items, Some(&key), at(10), 3)).unwrap();
+    assert!(name.ends_with(".ukeep"));
+
+    let bytes = block_on(webdav.download(&name)).unwrap();
+    assert!(crypto::is_encrypted(&bytes));
+    assert!(!String::from_utf8_lossy(&bytes).contains("全脂牛奶"));
+
+    let plain = key.decrypt(&bytes).unwrap();
+    assert_eq!(import_data_from_bytes(&plain, None, at(8).date()).unwrap(), items);
+    assert_eq!(import_data_from_bytes(&bytes, Some("family passphrase"), at(8).date()).unwrap(), items);
+}
+
+#[test]
+fn wrong_credentials_are_reported() {
+    let server = StandInServer::start();
+    let webdav = WebDavClient::new(&server.base_url, USER, "wrong", StdTransport);
+    let err = block_on(webdav.backup(&sample_items(), None, at(11), 3)).unwrap_err();
+    assert_eq!(err, UkeepError::Unauthorized);
+}
+
+#[test]
+fn missing_backups_report_the_http_status() {
+    let server = StandInServer::start();
+    let webdav = client(&server);
+    let err = block_on(webdav.download("ukeep_backup_20250301_080000.json")).unwrap_err();
+    assert_eq!(err, UkeepError::HttpStatus(404));
+}